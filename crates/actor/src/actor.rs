@@ -1,6 +1,10 @@
 use super::*;
+use block_traits::execution_context::{
+    check_capabilities, MissingCapability, RequiredCapabilities,
+};
 use block_traits::{
-    execute_status, Effect, EffectConsumerTrait, ExecuteTrait, Intent, IntentConsumerTrait,
+    execute_status, Effect, EffectConsumerTrait, ExecuteTrait, ExecutionContextTrait, Intent,
+    IntentConsumerTrait,
 };
 use trade_types::Contract;
 
@@ -39,13 +43,19 @@ impl<T> ActorAlgo for T where
 /// Each tick, we create a fresh `ReconcileIntentConsumer<'_>` that borrows the buffer
 /// and starts at `idx = 0` (so an algorithm can be executed repeatedly without having
 /// to remember to reset indices).
+///
+/// How intents update the buffer is decided by the reconciliator's `ReconciliationPolicy`.
 struct Reconciliator {
     orders: Vec<Order>,
+    policy: Box<dyn ReconciliationPolicy>,
+    budget: IntentBudget,
 }
 impl Reconciliator {
-    pub fn new(size: usize) -> Self {
+    pub fn new(size: usize, policy: Box<dyn ReconciliationPolicy>) -> Self {
         Self {
             orders: vec![Order::default(); size],
+            policy,
+            budget: IntentBudget::default(),
         }
     }
 
@@ -53,7 +63,13 @@ impl Reconciliator {
     ///
     /// Each call creates a new consumer with `idx = 0`.
     pub fn intent_consumer(&mut self) -> ReconcileIntentConsumer<'_> {
-        ReconcileIntentConsumer::new(&mut self.orders)
+        ReconcileIntentConsumer::with_policy(&mut self.orders, self.policy.as_ref())
+            .with_budget(self.budget)
+    }
+
+    /// The orders to send, batched across slots as the policy specifies.
+    pub fn outbound_orders(&self) -> Vec<Order> {
+        batch_orders(&self.orders, self.policy.batching())
     }
 }
 
@@ -62,52 +78,106 @@ impl Reconciliator {
 /// The algorithm produces `Intent`s by calling `IntentConsumerTrait::consume`.
 /// This consumer translates intents into an updated order buffer.
 ///
+/// Orders for the same contract and side compete by the priority of their
+/// intents: whichever of two such orders in the current tick has the lower
+/// priority is withdrawn (its slot becomes `NoOrder`), regardless of the
+/// policy. Orders of equal priority are all kept.
+///
 /// Important invariants:
 /// - `idx` advances once per consumed intent
-/// - the buffer length must be at least the maximum number of intents the algorithm can emit
-///   (the actor enforces this by sizing from `algo.num_intents()`).
+/// - the buffer length should be at least the maximum number of intents the algorithm can emit
+///   (the actor ensures this by sizing from `algo.num_intents()`); intents beyond it are
+///   over budget, as are intents beyond the consumer's `IntentBudget`.
 pub struct ReconcileIntentConsumer<'a> {
     orders: &'a mut [Order],
+    policy: &'a dyn ReconciliationPolicy,
+    budget: IntentBudget,
     idx: usize,
 }
 impl<'a> ReconcileIntentConsumer<'a> {
-    /// Create a new consumer over an existing order buffer.
+    /// Create a new consumer over an existing order buffer using the default `ReplacePolicy`.
     ///
     /// The buffer is borrowed mutably and updated in-place. The consumer always starts at `idx = 0`.
     pub fn new(orders: &'a mut [Order]) -> Self {
-        Self { orders, idx: 0 }
+        Self::with_policy(orders, &ReplacePolicy)
     }
 
-    /// Convert an intent into the next order state given the previous order state.
-    ///
-    /// This is deliberately “mock-simple” right now:
-    /// - `NoIntent` keeps the previous order
-    /// - `Place` overwrites with a new order request
-    fn process_intent(&self, _prev_order: &Order, intent: &Intent) -> Order {
-        match intent {
-            Intent::NoIntent => Order::NoOrder,
-            Intent::Place {
-                contract,
-                side,
-                price,
-                quantity,
-            } => Order::New {
-                contract: contract.clone(),
-                side: side.clone(),
-                price: price.clone(),
-                quantity: quantity.clone(),
-            },
+    /// Create a new consumer over an existing order buffer using the given policy.
+    pub fn with_policy(orders: &'a mut [Order], policy: &'a dyn ReconciliationPolicy) -> Self {
+        Self {
+            orders,
+            policy,
+            budget: IntentBudget::default(),
+            idx: 0,
         }
     }
 
+    /// Limit the intents consumed by this consumer to `budget`.
+    pub fn with_budget(mut self, budget: IntentBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Convert an intent into the next order state given the previous order state.
+    fn process_intent(&self, prev_order: &Order, intent: &Intent) -> Order {
+        self.policy.reconcile(prev_order, intent)
+    }
+
     /// Consume one intent and update one slot of the order buffer.
     ///
-    /// Panics if the algorithm emits more intents than the buffer length.
+    /// Intents beyond the budget or the buffer length fail the tick with the
+    /// reason `INTENT_BUDGET_EXCEEDED`, or are ignored if the budget truncates.
     fn consume(&mut self, intent: &Intent) -> Result<(), execute_status::FailureStatus> {
+        if self.idx >= self.orders.len().min(self.budget.max_per_tick) {
+            return match self.budget.on_exceed {
+                OverBudget::Fail => Err(execute_status::FailureStatus::with_reason(
+                    INTENT_BUDGET_EXCEEDED,
+                )),
+                OverBudget::Truncate => Ok(()),
+            };
+        }
         self.orders[self.idx] = self.process_intent(&self.orders[self.idx], intent);
+        self.apply_priority();
         self.idx += 1;
         Ok(())
     }
+
+    /// Resolve competition between the order just placed at `idx` and the orders
+    /// placed earlier in this tick for the same contract and side.
+    fn apply_priority(&mut self) {
+        let (earlier, rest) = self.orders.split_at_mut(self.idx);
+        let Order::New {
+            contract,
+            side,
+            priority,
+            ..
+        } = &rest[0]
+        else {
+            return;
+        };
+        let mut withdrawn = false;
+        for order in earlier.iter_mut() {
+            if let Order::New {
+                contract: other_contract,
+                side: other_side,
+                priority: other_priority,
+                ..
+            } = order
+            {
+                if other_contract != contract || other_side != side {
+                    continue;
+                }
+                if *other_priority < *priority {
+                    *order = Order::NoOrder;
+                } else if *other_priority > *priority {
+                    withdrawn = true;
+                }
+            }
+        }
+        if withdrawn {
+            rest[0] = Order::NoOrder;
+        }
+    }
 }
 
 impl<'a> IntentConsumerTrait for ReconcileIntentConsumer<'a> {
@@ -168,6 +238,89 @@ impl<'a> EffectConsumerTrait for EffectConsumer<'a> {
     }
 }
 
+/// Reason given when an algorithm emits more intents than its `IntentBudget` allows.
+pub const INTENT_BUDGET_EXCEEDED: &str = "intent budget exceeded";
+
+/// What happens to intents beyond an actor's `IntentBudget`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverBudget {
+    /// Fail the tick with the reason `INTENT_BUDGET_EXCEEDED`.
+    #[default]
+    Fail,
+    /// Ignore the excess intents and carry on with the tick.
+    Truncate,
+}
+
+/// Limit on the number of intents an algorithm may emit in one tick.
+///
+/// A safeguard against runaway order generation, on top of the order buffer
+/// sized from `num_intents`. The default budget is unlimited, so only the
+/// buffer size applies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntentBudget {
+    pub max_per_tick: usize,
+    pub on_exceed: OverBudget,
+}
+
+impl IntentBudget {
+    /// A budget of `max_per_tick` intents that fails ticks going over it.
+    pub fn new(max_per_tick: usize) -> Self {
+        Self {
+            max_per_tick,
+            on_exceed: OverBudget::Fail,
+        }
+    }
+
+    /// The same budget, ignoring excess intents instead of failing.
+    pub fn truncating(self) -> Self {
+        Self {
+            on_exceed: OverBudget::Truncate,
+            ..self
+        }
+    }
+}
+
+impl Default for IntentBudget {
+    fn default() -> Self {
+        Self::new(usize::MAX)
+    }
+}
+
+/// What a suspended actor waits for before it runs again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Suspension {
+    /// Suspended until explicitly resumed with `ActorController::resume`.
+    UntilResumed,
+    /// Suspended until the controller's time reaches the given tick, set by a
+    /// `Timer` effect scheduled alongside `Suspend`.
+    UntilTime(u64),
+}
+
+/// What happened during one successful actor tick.
+#[derive(Clone, Debug)]
+pub struct TickOutcome {
+    /// The orders to send, as reported by `Actor::outbound_orders` after the tick.
+    pub orders: Vec<Order>,
+    /// Number of effects the algorithm scheduled and the actor handled.
+    pub effects_handled: usize,
+    /// Status returned by the algorithm.
+    pub status: execute_status::SuccessStatus,
+}
+
+impl Default for TickOutcome {
+    /// A successful tick without orders or effects.
+    fn default() -> Self {
+        Self {
+            orders: Vec::new(),
+            effects_handled: 0,
+            status: execute_status::Success,
+        }
+    }
+}
+
+/// Result of one actor tick: its outcome, or why the actor failed.
+pub type TickResult = Result<TickOutcome, execute_status::FailureStatus>;
+
 /// Runs one algorithm instance, maintaining per-actor reconciliation and effect buffers.
 ///
 /// Execution model:
@@ -175,6 +328,9 @@ impl<'a> EffectConsumerTrait for EffectConsumer<'a> {
 /// - create a fresh effect consumer borrowing the actor’s effect buffer (clears old effects)
 /// - call `algo.execute(...)`
 /// - if the algorithm returns Ok(_), handle effects
+/// - apply `Subscribe`/`Unsubscribe` effects to the actor's contracts and queue them for the controller
+/// - suspend the actor if it scheduled a `Suspend` effect; a `Timer` effect in the same tick
+///   sets when it resumes
 /// - if it returns `None`, the actor is considered failed and the caller should terminate it
 pub struct Actor<Algo>
 where
@@ -184,6 +340,12 @@ where
     algo: Box<Algo>,
     reconciliator: Reconciliator,
     effect_handler: EffectHandler,
+    /// Contracts the actor ticks on: the algorithm's dependencies plus dynamic subscriptions.
+    contracts: Vec<Contract>,
+    /// Subscription effects not yet picked up by the controller.
+    subscription_changes: Vec<Effect>,
+    /// Set while the actor is suspended; the controller skips it until it resumes.
+    suspension: Option<Suspension>,
 }
 
 impl<Algo> Actor<Algo>
@@ -194,47 +356,152 @@ where
     ///
     /// The reconciliator is sized according to `algo.num_intents()`.
     /// The effect handler starts with an empty effect buffer as the number of effects is dynamic.
+    /// Intents are reconciled into orders with the default `ReplacePolicy`.
     pub fn new(id: u32, algo: Box<Algo>) -> Self {
+        Self::new_with_policy(id, algo, Box::new(ReplacePolicy))
+    }
+
+    /// Create a new actor instance that reconciles intents into orders with the given policy.
+    pub fn new_with_policy(
+        id: u32,
+        algo: Box<Algo>,
+        policy: Box<dyn ReconciliationPolicy>,
+    ) -> Self {
         let num_intents = algo.num_intents();
-        let reconciliator = Reconciliator::new(num_intents);
+        let reconciliator = Reconciliator::new(num_intents, policy);
         let effect_handler = EffectHandler::new();
+        let contracts = algo.contract_deps();
         Self {
             id,
             algo,
             reconciliator,
             effect_handler,
+            contracts,
+            subscription_changes: Vec::new(),
+            suspension: None,
         }
     }
 
+    /// Limit the intents the algorithm may emit per tick, see `IntentBudget`.
+    pub fn with_intent_budget(mut self, budget: IntentBudget) -> Self {
+        self.reconciliator.budget = budget;
+        self
+    }
+
+    /// The orders resulting from the last execution, ready to be sent.
+    ///
+    /// Empty slots are left out, and orders of different slots are batched
+    /// according to the policy's `batching`, e.g. deduplicated or netted.
+    pub fn outbound_orders(&self) -> Vec<Order> {
+        self.reconciliator.outbound_orders()
+    }
+
     /// Get the actor’s unique ID.
     fn actor_id(&self) -> u32 {
         self.id
     }
 
-    /// Get the contracts this actor’s algorithm depends on.
+    /// Get the contracts this actor ticks on.
     ///
+    /// These start out as the algorithm's contract dependencies and change with the
+    /// `Subscribe` and `Unsubscribe` effects it schedules.
     /// This is used by the controller to subscribe to market data updates.
     fn contracts(&self) -> Vec<Contract> {
-        self.algo.contract_deps()
+        self.contracts.clone()
+    }
+
+    /// Take the subscription changes made since the last call.
+    fn take_subscription_changes(&mut self) -> Vec<Effect> {
+        std::mem::take(&mut self.subscription_changes)
+    }
+
+    /// Apply the `Subscribe` and `Unsubscribe` effects of the last execution.
+    ///
+    /// Only effects that change the contract set are queued for the controller.
+    fn apply_subscription_effects(&mut self) {
+        for effect in self.effect_handler.effects.iter() {
+            match effect {
+                Effect::Subscribe(contract) if !self.contracts.contains(contract) => {
+                    self.contracts.push(contract.clone());
+                    self.subscription_changes.push(effect.clone());
+                }
+                Effect::Unsubscribe(contract) if self.contracts.contains(contract) => {
+                    self.contracts.retain(|c| c != contract);
+                    self.subscription_changes.push(effect.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Get what the actor is waiting for, if it is suspended.
+    fn suspension(&self) -> Option<Suspension> {
+        self.suspension
+    }
+
+    /// Clear the actor's suspension so it runs on its next tick.
+    fn resume(&mut self) {
+        self.suspension = None;
+    }
+
+    /// Suspend the actor if the last execution scheduled a `Suspend` effect.
+    ///
+    /// A `Timer(duration)` effect from the same execution makes the actor resume
+    /// `duration` ticks after `time`; without one it waits for an explicit resume.
+    fn apply_suspend_effects(&mut self, time: u64) {
+        let effects = &self.effect_handler.effects;
+        if !effects.contains(&Effect::Suspend) {
+            return;
+        }
+        let timer = effects.iter().find_map(|effect| match effect {
+            Effect::Timer(duration) => Some(*duration),
+            _ => None,
+        });
+        self.suspension = Some(match timer {
+            Some(duration) => Suspension::UntilTime(time + duration),
+            None => Suspension::UntilResumed,
+        });
     }
 
     /// Execute the actor’s algorithm for one tick.
     ///
-    /// Returns `Ok(execute_status::Success)` on success, or `Err(execute_status::Failure)`
+    /// Returns the tick's `TickOutcome` on success, or `Err(execute_status::Failure)`
     /// if the algorithm failed.
     ///
     /// Intents are handled by the reconciliator updating the order buffer in-place.
     /// Effects are collected in the effect handler and processed after execution.
-    fn execute(&mut self, context: &ActorExecutionContext) -> execute_status::ExecuteResult {
+    ///
+    /// If the context has a deadline that passed during execution, the tick fails with
+    /// `FailureStatus::with_reason("deadline")` and the collected effects are not handled.
+    fn execute(&mut self, context: &ActorExecutionContext) -> TickResult {
         let effect_handler = &mut self.effect_handler;
         let reconciliator = &mut self.reconciliator;
-        self.algo
-            .execute(
-                context,
-                &mut reconciliator.intent_consumer(),
-                &mut effect_handler.effect_consumer(),
-            )
-            .and_then(|_| effect_handler.handle_effects())
+        let status = self.algo.execute(
+            context,
+            &mut reconciliator.intent_consumer(),
+            &mut effect_handler.effect_consumer(),
+        )?;
+        context.check_deadline()?;
+        effect_handler.handle_effects()?;
+        self.apply_subscription_effects();
+        self.apply_suspend_effects(context.time());
+        Ok(TickOutcome {
+            orders: self.outbound_orders(),
+            effects_handled: self.effect_handler.effects.len(),
+            status,
+        })
+    }
+}
+
+impl<Algo> Actor<Algo>
+where
+    Algo: ActorAlgo + RequiredCapabilities,
+{
+    /// Like `new`, but first check that the actor's execution context provides
+    /// the capabilities the algorithm requires.
+    pub fn new_checked(id: u32, algo: Box<Algo>) -> Result<Self, MissingCapability> {
+        check_capabilities::<ActorExecutionContext>(algo.as_ref())?;
+        Ok(Self::new(id, algo))
     }
 }
 
@@ -251,11 +518,30 @@ pub trait ActorTrait {
     /// The controller uses this to subscribe to market data updates.
     fn contracts(&self) -> Vec<Contract>;
 
+    /// Take the `Subscribe` and `Unsubscribe` effects applied since the last call.
+    ///
+    /// The controller calls this after each tick to update its contract tables.
+    /// Actors without dynamic subscriptions can rely on the default, which reports none.
+    fn take_subscription_changes(&mut self) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    /// Get what the actor is waiting for, if it is suspended.
+    ///
+    /// The controller does not execute suspended actors. Actors that never
+    /// suspend can rely on the default, which reports none.
+    fn suspension(&self) -> Option<Suspension> {
+        None
+    }
+
+    /// Clear the actor's suspension so it runs on its next tick.
+    fn resume(&mut self) {}
+
     /// Execute the actor’s algorithm for one tick.
     ///
-    /// Returns `Ok(execute_status::Success)` on success, or `Err(execute_status::Failure)`
+    /// Returns the tick's `TickOutcome` on success, or `Err(execute_status::Failure)`
     /// if the algorithm failed.
-    fn execute(&mut self, ctx: &ActorExecutionContext) -> execute_status::ExecuteResult;
+    fn execute(&mut self, ctx: &ActorExecutionContext) -> TickResult;
 }
 
 impl<Algo> ActorTrait for Actor<Algo>
@@ -268,7 +554,16 @@ where
     fn contracts(&self) -> Vec<Contract> {
         Actor::contracts(self)
     }
-    fn execute(&mut self, ctx: &ActorExecutionContext) -> execute_status::ExecuteResult {
+    fn take_subscription_changes(&mut self) -> Vec<Effect> {
+        Actor::take_subscription_changes(self)
+    }
+    fn suspension(&self) -> Option<Suspension> {
+        Actor::suspension(self)
+    }
+    fn resume(&mut self) {
+        Actor::resume(self)
+    }
+    fn execute(&mut self, ctx: &ActorExecutionContext) -> TickResult {
         Actor::execute(self, ctx)
     }
 }
@@ -452,6 +747,46 @@ mod tests {
         assert_eq!(actor.effect_handler.effects.len(), 1);
     }
 
+    #[test]
+    fn subscription_effects_update_contracts() {
+        let a = Contract::new("A");
+        let b = Contract::new("B");
+        let effects = vec![
+            Effect::subscribe(b.clone()),
+            Effect::subscribe(b.clone()),
+            Effect::unsubscribe(a.clone()),
+        ];
+        let algo = Box::new(MockAlgo::new(0, vec![a.clone()], vec![], effects));
+        let mut actor = Actor::new(1, algo);
+
+        actor.execute(&ActorExecutionContext::new(0)).unwrap();
+        assert_eq!(actor.contracts(), vec![b.clone()]);
+        // Repeated subscriptions are only reported once.
+        assert_eq!(
+            actor.take_subscription_changes(),
+            vec![Effect::subscribe(b), Effect::unsubscribe(a)]
+        );
+        assert!(actor.take_subscription_changes().is_empty());
+    }
+
+    #[test]
+    fn suspend_effect_suspends_the_actor() {
+        let algo = Box::new(MockAlgo::new(0, vec![], vec![], vec![Effect::suspend()]));
+        let mut actor = Actor::new(1, algo);
+        assert_eq!(actor.suspension(), None);
+
+        actor.execute(&ActorExecutionContext::new(3)).unwrap();
+        assert_eq!(actor.suspension(), Some(Suspension::UntilResumed));
+        actor.resume();
+        assert_eq!(actor.suspension(), None);
+
+        // A timer in the same tick sets when the actor wakes up.
+        let effects = vec![Effect::timer(10), Effect::suspend()];
+        let mut actor = Actor::new(1, Box::new(MockAlgo::new(0, vec![], vec![], effects)));
+        actor.execute(&ActorExecutionContext::new(3)).unwrap();
+        assert_eq!(actor.suspension(), Some(Suspension::UntilTime(13)));
+    }
+
     #[test]
     fn execute_returns_none_when_algo_fails() {
         let algo = Box::new(MockAlgo::new(1, vec![], vec![], vec![]).fail_on(0));
@@ -460,4 +795,303 @@ mod tests {
         let ctx = ActorExecutionContext::new(0);
         assert!(actor.execute(&ctx).is_err());
     }
+
+    #[test]
+    fn execute_fails_with_deadline_reason_when_deadline_passed() {
+        use std::time::{Duration, Instant};
+
+        let effects = vec![Effect::suspend()];
+        let algo = Box::new(MockAlgo::new(1, vec![], vec![], effects));
+        let mut actor = Actor::new(1, algo);
+
+        let past = Instant::now()
+            .checked_sub(Duration::from_millis(1))
+            .unwrap_or_else(Instant::now);
+        let ctx = ActorExecutionContext::new(0).with_deadline(past);
+
+        let err = actor.execute(&ctx).unwrap_err();
+        assert_eq!(
+            err.reason(),
+            Some(block_traits::execution_context::DEADLINE_EXCEEDED)
+        );
+    }
+
+    #[test]
+    fn intents_over_budget_fail_the_tick() {
+        let intents = vec![place(1), place(2), place(3)];
+        let algo = Box::new(MockAlgo::new(3, vec![], intents, vec![]));
+        let mut actor = Actor::new(1, algo).with_intent_budget(IntentBudget::new(2));
+
+        let err = actor.execute(&ActorExecutionContext::new(0)).unwrap_err();
+        assert_eq!(err.reason(), Some(INTENT_BUDGET_EXCEEDED));
+    }
+
+    #[test]
+    fn truncating_budget_ignores_excess_intents() {
+        let intents = vec![place(1), place(2), place(3)];
+        let algo = Box::new(MockAlgo::new(3, vec![], intents, vec![]));
+        let budget = IntentBudget::new(2).truncating();
+        let mut actor = Actor::new(1, algo).with_intent_budget(budget);
+
+        let outcome = actor.execute(&ActorExecutionContext::new(0)).unwrap();
+        let kws: Vec<f64> = outcome
+            .orders
+            .iter()
+            .map(|order| match order {
+                Order::New { quantity, .. } => quantity.as_kw_f64(),
+                other => panic!("expected New order, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(kws, [1.0, 2.0]);
+    }
+
+    #[test]
+    fn intents_beyond_the_buffer_fail_instead_of_panicking() {
+        let mut orders = vec![Order::NoOrder];
+        let mut consumer = ReconcileIntentConsumer::new(&mut orders);
+        assert!(consumer.consume(&place(1)).is_ok());
+        let err = consumer.consume(&place(2)).unwrap_err();
+        assert_eq!(err.reason(), Some(INTENT_BUDGET_EXCEEDED));
+    }
+
+    #[test]
+    fn execute_succeeds_before_deadline() {
+        use std::time::{Duration, Instant};
+
+        let algo = Box::new(MockAlgo::new(1, vec![], vec![], vec![]));
+        let mut actor = Actor::new(1, algo);
+
+        let ctx =
+            ActorExecutionContext::new(0).with_deadline(Instant::now() + Duration::from_secs(60));
+        assert!(actor.execute(&ctx).is_ok());
+    }
+
+    /// Policy that nets new placements into the existing order by adding quantities.
+    struct AccumulatePolicy;
+
+    impl ReconciliationPolicy for AccumulatePolicy {
+        fn reconcile(&self, prev_order: &Order, intent: &Intent) -> Order {
+            match (prev_order, ReplacePolicy.reconcile(prev_order, intent)) {
+                (
+                    Order::New {
+                        quantity: prev_quantity,
+                        ..
+                    },
+                    Order::New {
+                        contract,
+                        side,
+                        price,
+                        quantity,
+                        priority,
+                    },
+                ) => Order::New {
+                    contract,
+                    side,
+                    price,
+                    quantity: trade_types::MilliKw(
+                        prev_quantity.in_milli_kw().0 + quantity.in_milli_kw().0,
+                    )
+                    .into(),
+                    priority,
+                },
+                (_, Order::NoOrder) => prev_order.clone(),
+                (_, order) => order,
+            }
+        }
+    }
+
+    fn place(kw: u32) -> Intent {
+        Intent::Place {
+            contract: Contract::new("A"),
+            side: trade_types::Side::Buy,
+            price: trade_types::Cents(100).into(),
+            quantity: trade_types::Kw(kw).into(),
+            priority: 0,
+        }
+    }
+
+    fn place_with_priority(kw: u32, priority: u8) -> Intent {
+        match place(kw) {
+            Intent::Place {
+                contract,
+                side,
+                price,
+                quantity,
+                ..
+            } => Intent::Place {
+                contract,
+                side,
+                price,
+                quantity,
+                priority,
+            },
+            intent => intent,
+        }
+    }
+
+    #[test]
+    fn higher_priority_place_wins_for_the_same_contract_and_side() {
+        let kws = |actor: &Actor<MockAlgo>| -> Vec<Option<f64>> {
+            actor
+                .reconciliator
+                .orders
+                .iter()
+                .map(|order| match order {
+                    Order::New { quantity, .. } => Some(quantity.as_kw_f64()),
+                    _ => None,
+                })
+                .collect()
+        };
+        let ctx = ActorExecutionContext::new(0);
+
+        // The lower-priority order is withdrawn whether it comes first or last.
+        let intents = vec![place_with_priority(5, 1), place_with_priority(7, 2)];
+        let mut actor = Actor::new(1, Box::new(MockAlgo::new(2, vec![], intents, vec![])));
+        actor.execute(&ctx).unwrap();
+        assert_eq!(kws(&actor), vec![None, Some(7.0)]);
+
+        let intents = vec![place_with_priority(7, 2), place_with_priority(5, 1)];
+        let mut actor = Actor::new(1, Box::new(MockAlgo::new(2, vec![], intents, vec![])));
+        actor.execute(&ctx).unwrap();
+        assert_eq!(kws(&actor), vec![Some(7.0), None]);
+
+        // With equal (default) priorities both orders stand.
+        let mut actor = Actor::new(
+            1,
+            Box::new(MockAlgo::new(2, vec![], vec![place(5), place(7)], vec![])),
+        );
+        actor.execute(&ctx).unwrap();
+        assert_eq!(kws(&actor), vec![Some(5.0), Some(7.0)]);
+    }
+
+    #[test]
+    fn netting_policy_cancels_equal_buy_and_sell() {
+        let sell = match place(5) {
+            Intent::Place {
+                contract,
+                price,
+                quantity,
+                priority,
+                ..
+            } => Intent::Place {
+                contract,
+                side: trade_types::Side::Sell,
+                price,
+                quantity,
+                priority,
+            },
+            intent => intent,
+        };
+        let intents = vec![place(5), sell, place(2), place(2)];
+        let ctx = ActorExecutionContext::new(0);
+
+        let algo = MockAlgo::new(4, vec![], intents.clone(), vec![]);
+        let mut actor = Actor::new(1, Box::new(algo));
+        actor.execute(&ctx).unwrap();
+        assert_eq!(actor.outbound_orders().len(), 4);
+
+        let algo = MockAlgo::new(4, vec![], intents, vec![]);
+        let policy = BatchingPolicy::new(ReplacePolicy, Batching::Net);
+        let mut actor = Actor::new_with_policy(1, Box::new(algo), Box::new(policy));
+        actor.execute(&ctx).unwrap();
+        // The duplicate buy of 2 counts once, and the buy and sell of 5 cancel out.
+        let kws: Vec<f64> = actor
+            .outbound_orders()
+            .iter()
+            .map(|order| match order {
+                Order::New { quantity, .. } => quantity.as_kw_f64(),
+                other => panic!("expected New order, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(kws, [2.0]);
+    }
+
+    #[test]
+    fn tick_outcome_reports_orders_and_effects() {
+        let intents = vec![place(5), Intent::NoIntent];
+        let algo = MockAlgo::new(2, vec![], intents, vec![Effect::timer(3)]);
+        let mut actor = Actor::new(1, Box::new(algo));
+
+        let outcome = actor.execute(&ActorExecutionContext::new(0)).unwrap();
+        assert_eq!(outcome.orders.len(), 1);
+        assert!(matches!(
+            &outcome.orders[0],
+            Order::New { quantity, .. } if quantity.as_kw_f64() == 5.0
+        ));
+        assert_eq!(outcome.effects_handled, 1);
+        assert!(matches!(outcome.status, execute_status::Success));
+    }
+
+    #[test]
+    fn default_policy_overwrites_orders_each_tick() {
+        let algo = Box::new(MockAlgo::new(1, vec![], vec![place(5)], vec![]));
+        let mut actor = Actor::new(1, algo);
+
+        let ctx = ActorExecutionContext::new(0);
+        actor.execute(&ctx).unwrap();
+        actor.execute(&ctx).unwrap();
+
+        match &actor.reconciliator.orders[0] {
+            Order::New { quantity, .. } => assert_eq!(quantity.as_kw_f64(), 5.0),
+            other => panic!("expected New order, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn custom_policy_accumulates_quantities() {
+        let algo = Box::new(MockAlgo::new(1, vec![], vec![place(5)], vec![]));
+        let mut actor = Actor::new_with_policy(1, algo, Box::new(AccumulatePolicy));
+
+        let ctx = ActorExecutionContext::new(0);
+        for _ in 0..3 {
+            actor.execute(&ctx).unwrap();
+        }
+
+        match &actor.reconciliator.orders[0] {
+            Order::New { quantity, .. } => assert_eq!(quantity.as_kw_f64(), 15.0),
+            other => panic!("expected New order, got {other:?}"),
+        }
+
+        // Fractions of a kilowatt add up instead of being rounded away.
+        let half = Intent::Place {
+            contract: Contract::new("A"),
+            side: trade_types::Side::Buy,
+            price: trade_types::Cents(100).into(),
+            quantity: trade_types::MilliKw(500).into(),
+            priority: 0,
+        };
+        let first = AccumulatePolicy.reconcile(&Order::NoOrder, &half);
+        match AccumulatePolicy.reconcile(&first, &half) {
+            Order::New { quantity, .. } => assert_eq!(quantity.as_kw_f64(), 1.0),
+            other => panic!("expected New order, got {other:?}"),
+        }
+    }
+
+    /// Side-effect-only algorithm relying on the default `num_intents`.
+    struct EffectOnlyAlgo;
+
+    impl block_traits::ContractDeps for EffectOnlyAlgo {}
+
+    impl<'a> ExecuteTrait<ActorExecutionContext, ReconcileIntentConsumer<'a>, EffectConsumer<'a>>
+        for EffectOnlyAlgo
+    {
+        fn execute(
+            &self,
+            _context: &ActorExecutionContext,
+            _intent_consumer: &mut ReconcileIntentConsumer<'a>,
+            effect_consumer: &mut EffectConsumer<'a>,
+        ) -> execute_status::ExecuteResult {
+            effect_consumer.schedule_alarm_clock_effect(5)?;
+            Ok(execute_status::Success)
+        }
+    }
+
+    #[test]
+    fn algorithm_without_num_intents_defaults_to_zero() {
+        let mut actor = Actor::new(1, Box::new(EffectOnlyAlgo));
+        assert!(actor.reconciliator.orders.is_empty());
+
+        actor.execute(&ActorExecutionContext::new(0)).unwrap();
+        assert_eq!(actor.effect_handler.effects, vec![Effect::timer(5)]);
+    }
 }
@@ -1,6 +1,9 @@
 // This module is all mock for now
 
-use block_traits::execution_context::{ExecutionContextTrait, OrderBookTrait};
+use block_traits::execution_context::{
+    Blackboard, Capability, ExecutionContextTrait, OrderBookTrait, Scratch,
+};
+use std::time::Instant;
 use trade_types::{Cents, Contract, Price, Side};
 
 pub struct OrderBook;
@@ -14,10 +17,28 @@ impl OrderBookTrait for OrderBook {
 
 pub struct ActorExecutionContext {
     time: u64,
+    deadline: Option<Instant>,
+    blackboard: Blackboard,
 }
 impl ActorExecutionContext {
     pub fn new(time: u64) -> Self {
-        Self { time }
+        Self {
+            time,
+            deadline: None,
+            blackboard: Blackboard::new(),
+        }
+    }
+
+    /// Set a wall-clock deadline for executions in this context.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Use the given blackboard for the blocks' scratch spaces.
+    pub fn with_blackboard(mut self, blackboard: Blackboard) -> Self {
+        self.blackboard = blackboard;
+        self
     }
 }
 impl ExecutionContextTrait for ActorExecutionContext {
@@ -34,4 +55,14 @@ impl ExecutionContextTrait for ActorExecutionContext {
         // mock position
         None
     }
+    fn capabilities() -> &'static [Capability] {
+        // Positions are not tracked yet.
+        &[Capability::OrderBook, Capability::Scratch]
+    }
+    fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+    fn scratch(&self, block_id: u32) -> Scratch {
+        self.blackboard.scratch(block_id)
+    }
 }
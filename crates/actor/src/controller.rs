@@ -1,7 +1,8 @@
 use super::*;
 
-use super::{ActorAlgo, ActorExecutionContext, ActorTrait};
-use block_traits::execute_status;
+use super::{ActorAlgo, ActorExecutionContext, ActorTrait, Suspension, TickOutcome, TickResult};
+use block_traits::execution_context::Blackboard;
+use block_traits::Effect;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -31,19 +32,63 @@ impl ActorHandle {
         self.0.borrow().contracts()
     }
 
-    pub fn execute(&self, context: &ActorExecutionContext) -> execute_status::ExecuteResult {
+    pub fn execute(&self, context: &ActorExecutionContext) -> TickResult {
         self.0.borrow_mut().execute(context)
     }
 
+    pub fn take_subscription_changes(&self) -> Vec<Effect> {
+        self.0.borrow_mut().take_subscription_changes()
+    }
+
+    pub fn suspension(&self) -> Option<Suspension> {
+        self.0.borrow().suspension()
+    }
+
+    pub fn resume(&self) {
+        self.0.borrow_mut().resume()
+    }
+
     pub fn ptr_eq(a: &Self, b: &Self) -> bool {
         Rc::ptr_eq(&a.0, &b.0)
     }
 }
 
+/// Listener for actor lifecycle events in an `ActorController`.
+///
+/// All callbacks default to doing nothing, so a listener only implements the
+/// events it cares about.
+pub trait ActorListener {
+    /// Called after an actor is added with `add_actor`.
+    fn on_add(&mut self, _id: u32) {}
+    /// Called after an actor is removed with `remove_actor_by_id`.
+    fn on_remove(&mut self, _id: u32) {}
+    /// Called when an actor fails during `tick_delta`, before it is removed.
+    fn on_fail(&mut self, _id: u32) {}
+}
+
+/// Snapshot of an `ActorController`'s counters, for monitoring.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ControllerMetrics {
+    /// Number of actors currently in the controller.
+    pub actors: usize,
+    /// Number of actors executed by the last `tick_delta`.
+    pub executed_last_tick: usize,
+    /// Total number of actors removed because they failed during a tick.
+    pub failures_removed: u64,
+    /// The controller's current time.
+    pub time: u64,
+}
+
 pub struct ActorController {
     time: u64, // mock time
     id_to_actors: HashMap<u32, ActorHandle>,
     contracts_to_actors: HashMap<Contract, Vec<ActorHandle>>,
+    /// Scratch spaces of each actor's blocks, keyed by actor id. Block ids are
+    /// only unique within an actor, so actors do not share a blackboard.
+    blackboards: HashMap<u32, Blackboard>,
+    listener: Option<Box<dyn ActorListener>>,
+    executed_last_tick: usize,
+    failures_removed: u64,
 }
 
 impl ActorController {
@@ -52,16 +97,69 @@ impl ActorController {
             time: 0,
             id_to_actors: HashMap::new(),
             contracts_to_actors: HashMap::new(),
+            blackboards: HashMap::new(),
+            listener: None,
+            executed_last_tick: 0,
+            failures_removed: 0,
         }
     }
 
+    /// Current values of the controller's counters.
+    pub fn metrics(&self) -> ControllerMetrics {
+        ControllerMetrics {
+            actors: self.id_to_actors.len(),
+            executed_last_tick: self.executed_last_tick,
+            failures_removed: self.failures_removed,
+            time: self.time,
+        }
+    }
+
+    /// Notify `listener` of actors being added, removed, or failing.
+    pub fn with_listener(mut self, listener: impl ActorListener + 'static) -> Self {
+        self.listener = Some(Box::new(listener));
+        self
+    }
+
+    /// Add an actor to the controller.
+    ///
+    /// The actors of each contract are kept sorted by actor id, so they tick in id
+    /// order regardless of the order they were added in. Actors with the same id
+    /// tick in the order they were added.
     pub fn add_actor(&mut self, actor: ActorHandle) {
-        self.id_to_actors.insert(actor.actor_id(), actor.clone());
+        let id = actor.actor_id();
+        self.id_to_actors.insert(id, actor.clone());
         for contract in actor.contracts() {
-            self.contracts_to_actors
-                .entry(contract)
-                .or_default()
-                .push(actor.clone());
+            self.insert_actor_for_contract(contract, &actor);
+        }
+        if let Some(listener) = self.listener.as_mut() {
+            listener.on_add(id);
+        }
+    }
+
+    fn insert_actor_for_contract(&mut self, contract: Contract, actor: &ActorHandle) {
+        let id = actor.actor_id();
+        let actors = self.contracts_to_actors.entry(contract).or_default();
+        let pos = actors.partition_point(|a| a.actor_id() <= id);
+        actors.insert(pos, actor.clone());
+    }
+
+    fn remove_actor_for_contract(&mut self, contract: &Contract, actor: &ActorHandle) {
+        if let Some(actors) = self.contracts_to_actors.get_mut(contract) {
+            actors.retain(|a| !ActorHandle::ptr_eq(a, actor));
+            if actors.is_empty() {
+                self.contracts_to_actors.remove(contract);
+            }
+        }
+    }
+
+    /// Apply the subscription changes an actor made during a tick.
+    fn apply_subscription_changes(&mut self, actor: &ActorHandle, changes: Vec<Effect>) {
+        for change in changes {
+            match change {
+                Effect::Subscribe(contract) => self.insert_actor_for_contract(contract, actor),
+                Effect::Unsubscribe(contract) => self.remove_actor_for_contract(&contract, actor),
+                _ => {}
+            }
         }
     }
 
@@ -69,20 +167,45 @@ impl ActorController {
         self.id_to_actors.get(&id).cloned()
     }
 
+    /// Resume a suspended actor so it runs on its next tick.
+    ///
+    /// Returns `false` if there is no actor with the given id.
+    pub fn resume(&mut self, id: u32) -> bool {
+        match self.id_to_actors.get(&id) {
+            Some(actor) => {
+                actor.resume();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether `actor` should run at the current time, resuming it if its
+    /// suspension timer has fired.
+    fn is_runnable(&self, actor: &ActorHandle) -> bool {
+        match actor.suspension() {
+            None => true,
+            Some(Suspension::UntilTime(time)) if time <= self.time => {
+                actor.resume();
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
     pub fn remove_actor_by_id(&mut self, id: u32) {
         if let Some(actor) = self.id_to_actors.remove(&id) {
+            self.blackboards.remove(&id);
             self.remove_actor_rc_from_contract_tables(&actor);
+            if let Some(listener) = self.listener.as_mut() {
+                listener.on_remove(id);
+            }
         }
     }
 
     fn remove_actor_rc_from_contract_tables(&mut self, actor: &ActorHandle) {
         for contract in actor.contracts() {
-            if let Some(actors) = self.contracts_to_actors.get_mut(&contract) {
-                actors.retain(|a| !ActorHandle::ptr_eq(a, actor));
-                if actors.is_empty() {
-                    self.contracts_to_actors.remove(&contract);
-                }
-            }
+            self.remove_actor_for_contract(&contract, actor);
         }
     }
 
@@ -96,33 +219,76 @@ impl ActorController {
                 self.contracts_to_actors.remove(contract);
             }
         }
-        // Remove from id mapping
+        // Remove from id mapping and the tables of any other contracts
         for actor in dead {
+            let id = actor.actor_id();
+            if self
+                .id_to_actors
+                .get(&id)
+                .is_some_and(|a| ActorHandle::ptr_eq(a, actor))
+            {
+                self.id_to_actors.remove(&id);
+                self.blackboards.remove(&id);
+            }
             self.remove_actor_rc_from_contract_tables(actor);
         }
+        self.failures_removed += dead.len() as u64;
     }
 
-    pub fn tick_delta(&mut self, Delta(contract): &Delta) {
-        let ctx = ActorExecutionContext::new(self.time);
-
+    /// Execute the actors subscribed to the delta's contract and advance the time.
+    ///
+    /// Suspended actors are skipped until they resume, either through
+    /// `resume` or when their suspension timer fires.
+    ///
+    /// Returns the ids and `TickOutcome`s of the actors that executed
+    /// successfully, in the order they ran. Failed actors are reported to the
+    /// listener and removed instead.
+    pub fn tick_delta(&mut self, Delta(contract): &Delta) -> Vec<(u32, TickOutcome)> {
+        let mut outcomes = Vec::new();
         // Optional to avoid allocation if no actors fail
         let mut dead: Option<Vec<ActorHandle>> = None;
+        // Subscription changes are applied after the loop, which borrows the contract tables.
+        let mut subscriptions: Vec<(ActorHandle, Vec<Effect>)> = Vec::new();
+        self.executed_last_tick = 0;
         if let Some(actors) = self.contracts_to_actors.get(contract) {
             for actor in actors {
+                if !self.is_runnable(actor) {
+                    continue;
+                }
+                self.executed_last_tick += 1;
+                // Each actor keeps its blackboard across ticks so block scratch spaces persist.
+                let blackboard = self.blackboards.entry(actor.actor_id()).or_default();
+                let ctx = ActorExecutionContext::new(self.time).with_blackboard(blackboard.clone());
                 // Execute actor and track failures
-                let failure = actor.execute(&ctx).is_err();
-                if failure {
-                    dead.get_or_insert_with(Vec::new).push(actor.clone());
+                match actor.execute(&ctx) {
+                    Ok(outcome) => outcomes.push((actor.actor_id(), outcome)),
+                    Err(_) => {
+                        dead.get_or_insert_with(Vec::new).push(actor.clone());
+                        continue;
+                    }
+                }
+                let changes = actor.take_subscription_changes();
+                if !changes.is_empty() {
+                    subscriptions.push((actor.clone(), changes));
                 }
             }
         }
+        for (actor, changes) in subscriptions {
+            self.apply_subscription_changes(&actor, changes);
+        }
         // Handle dead actors
         if let Some(dead) = dead.as_deref() {
+            if let Some(listener) = self.listener.as_mut() {
+                for actor in dead {
+                    listener.on_fail(actor.actor_id());
+                }
+            }
             self.remove_failed_actors(dead, contract);
         }
 
         // Mock updating state...
         self.time += 1;
+        outcomes
     }
 }
 
@@ -136,6 +302,7 @@ impl Default for ActorController {
 mod tests {
     use super::*;
     use ::block_macros::*;
+    use block_traits::execute_status;
 
     mod add_actor_indexes_by_id {
         use super::*;
@@ -352,4 +519,466 @@ mod tests {
             );
         }
     }
+
+    mod actors_tick_in_id_order {
+        use super::*;
+        use ::trade_types::Contract;
+
+        struct RecordingActor {
+            id: u32,
+            log: Rc<RefCell<Vec<u32>>>,
+        }
+
+        impl ActorTrait for RecordingActor {
+            fn actor_id(&self) -> u32 {
+                self.id
+            }
+            fn contracts(&self) -> Vec<Contract> {
+                vec![Contract::new("A")]
+            }
+            fn execute(&mut self, _ctx: &ActorExecutionContext) -> TickResult {
+                self.log.borrow_mut().push(self.id);
+                Ok(TickOutcome::default())
+            }
+        }
+
+        #[test]
+        fn test() {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            let mut ctrl = ActorController::new();
+            for id in [3, 1, 2] {
+                ctrl.add_actor(ActorHandle::new(RecordingActor {
+                    id,
+                    log: log.clone(),
+                }));
+            }
+
+            ctrl.tick_delta(&Delta(Contract::new("A")));
+            assert_eq!(*log.borrow(), [1, 2, 3]);
+
+            // Removing and re-adding an actor keeps the id order.
+            ctrl.remove_actor_by_id(1);
+            ctrl.add_actor(ActorHandle::new(RecordingActor {
+                id: 1,
+                log: log.clone(),
+            }));
+            log.borrow_mut().clear();
+            ctrl.tick_delta(&Delta(Contract::new("A")));
+            assert_eq!(*log.borrow(), [1, 2, 3]);
+        }
+    }
+
+    mod tick_delta_returns_outcomes_of_successful_actors {
+        use super::*;
+        use ::trade_types::Contract;
+
+        /// Reports its id as the number of handled effects, or fails if `fail`.
+        struct OutcomeActor {
+            id: u32,
+            fail: bool,
+        }
+
+        impl ActorTrait for OutcomeActor {
+            fn actor_id(&self) -> u32 {
+                self.id
+            }
+            fn contracts(&self) -> Vec<Contract> {
+                vec![Contract::new("A")]
+            }
+            fn execute(&mut self, _ctx: &ActorExecutionContext) -> TickResult {
+                if self.fail {
+                    return Err(execute_status::Failure);
+                }
+                Ok(TickOutcome {
+                    effects_handled: self.id as usize,
+                    ..TickOutcome::default()
+                })
+            }
+        }
+
+        #[test]
+        fn test() {
+            let mut ctrl = ActorController::new();
+            for (id, fail) in [(2, false), (1, false), (3, true)] {
+                ctrl.add_actor(ActorHandle::new(OutcomeActor { id, fail }));
+            }
+
+            let outcomes = ctrl.tick_delta(&Delta(Contract::new("A")));
+            let handled: Vec<(u32, usize)> = outcomes
+                .iter()
+                .map(|(id, outcome)| (*id, outcome.effects_handled))
+                .collect();
+            assert_eq!(handled, [(1, 1), (2, 2)]);
+        }
+    }
+
+    mod listener_sees_actor_lifecycle {
+        use super::*;
+        use ::trade_types::Contract;
+
+        struct FlakyActor {
+            id: u32,
+            fail: bool,
+        }
+
+        impl ActorTrait for FlakyActor {
+            fn actor_id(&self) -> u32 {
+                self.id
+            }
+            fn contracts(&self) -> Vec<Contract> {
+                vec![Contract::new("A")]
+            }
+            fn execute(&mut self, _ctx: &ActorExecutionContext) -> TickResult {
+                if self.fail {
+                    Err(execute_status::Failure)
+                } else {
+                    Ok(TickOutcome::default())
+                }
+            }
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Event {
+            Add(u32),
+            Remove(u32),
+            Fail(u32),
+        }
+
+        struct RecordingListener(Rc<RefCell<Vec<Event>>>);
+
+        impl ActorListener for RecordingListener {
+            fn on_add(&mut self, id: u32) {
+                self.0.borrow_mut().push(Event::Add(id));
+            }
+            fn on_remove(&mut self, id: u32) {
+                self.0.borrow_mut().push(Event::Remove(id));
+            }
+            fn on_fail(&mut self, id: u32) {
+                self.0.borrow_mut().push(Event::Fail(id));
+            }
+        }
+
+        #[test]
+        fn test() {
+            let events = Rc::new(RefCell::new(Vec::new()));
+            let mut ctrl = ActorController::new().with_listener(RecordingListener(events.clone()));
+            ctrl.add_actor(ActorHandle::new(FlakyActor { id: 1, fail: false }));
+            ctrl.add_actor(ActorHandle::new(FlakyActor { id: 2, fail: true }));
+
+            ctrl.tick_delta(&Delta(Contract::new("A")));
+            assert_eq!(
+                ctrl.metrics(),
+                ControllerMetrics {
+                    actors: 1,
+                    executed_last_tick: 2,
+                    failures_removed: 1,
+                    time: 1,
+                }
+            );
+            ctrl.remove_actor_by_id(1);
+            // Removing an unknown actor is not reported.
+            ctrl.remove_actor_by_id(3);
+
+            assert_eq!(
+                *events.borrow(),
+                [
+                    Event::Add(1),
+                    Event::Add(2),
+                    Event::Fail(2),
+                    Event::Remove(1)
+                ]
+            );
+        }
+    }
+
+    mod failed_actors_cannot_be_looked_up_by_id {
+        use super::*;
+        use ::trade_types::Contract;
+
+        struct FailingActor;
+
+        impl ActorTrait for FailingActor {
+            fn actor_id(&self) -> u32 {
+                1
+            }
+            fn contracts(&self) -> Vec<Contract> {
+                vec![Contract::new("A")]
+            }
+            fn execute(&mut self, _ctx: &ActorExecutionContext) -> TickResult {
+                Err(execute_status::Failure)
+            }
+        }
+
+        #[test]
+        fn test() {
+            let mut ctrl = ActorController::new();
+            ctrl.add_actor(ActorHandle::new(FailingActor));
+            assert!(ctrl.get_actor_by_id(1).is_some());
+
+            ctrl.tick_delta(&Delta(Contract::new("A")));
+            assert!(ctrl.get_actor_by_id(1).is_none());
+        }
+    }
+
+    mod actors_get_their_own_scratch_spaces {
+        use super::*;
+        use ::trade_types::Contract;
+        use block_traits::ExecutionContextTrait;
+
+        /// Counts its executions in the scratch space of block 0, failing on
+        /// the tick its count reaches `fail_at`.
+        struct CountingActor {
+            id: u32,
+            fail_at: Option<u32>,
+            log: Rc<RefCell<Vec<(u32, u32)>>>,
+        }
+
+        impl ActorTrait for CountingActor {
+            fn actor_id(&self) -> u32 {
+                self.id
+            }
+            fn contracts(&self) -> Vec<Contract> {
+                vec![Contract::new("A")]
+            }
+            fn execute(&mut self, ctx: &ActorExecutionContext) -> TickResult {
+                let scratch = ctx.scratch(0);
+                let mut scratch = scratch.borrow_mut();
+                let count = scratch
+                    .entry("count".to_string())
+                    .or_insert_with(|| Box::new(0u32))
+                    .downcast_mut::<u32>()
+                    .unwrap();
+                *count += 1;
+                self.log.borrow_mut().push((self.id, *count));
+                if Some(*count) == self.fail_at {
+                    return Err(execute_status::Failure);
+                }
+                Ok(TickOutcome::default())
+            }
+        }
+
+        #[test]
+        fn test() {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            let actor = |id, fail_at| {
+                ActorHandle::new(CountingActor {
+                    id,
+                    fail_at,
+                    log: log.clone(),
+                })
+            };
+            let mut ctrl = ActorController::new();
+            ctrl.add_actor(actor(1, None));
+            ctrl.add_actor(actor(2, Some(2)));
+
+            ctrl.tick_delta(&Delta(Contract::new("A")));
+            ctrl.tick_delta(&Delta(Contract::new("A")));
+            assert_eq!(*log.borrow(), [(1, 1), (2, 1), (1, 2), (2, 2)]);
+
+            // Removed and failed actors lose their scratch spaces.
+            ctrl.remove_actor_by_id(1);
+            ctrl.add_actor(actor(1, None));
+            ctrl.add_actor(actor(2, None));
+            log.borrow_mut().clear();
+            ctrl.tick_delta(&Delta(Contract::new("A")));
+            assert_eq!(*log.borrow(), [(1, 1), (2, 1)]);
+        }
+    }
+
+    mod actors_subscribe_to_new_contracts {
+        use super::*;
+        use ::trade_types::Contract;
+
+        /// Subscribes to "B" on its first tick and unsubscribes on its first tick on "B".
+        struct SubscribingActor {
+            contracts: Vec<Contract>,
+            changes: Vec<Effect>,
+            log: Rc<RefCell<Vec<u64>>>,
+        }
+
+        impl ActorTrait for SubscribingActor {
+            fn actor_id(&self) -> u32 {
+                1
+            }
+            fn contracts(&self) -> Vec<Contract> {
+                self.contracts.clone()
+            }
+            fn take_subscription_changes(&mut self) -> Vec<Effect> {
+                std::mem::take(&mut self.changes)
+            }
+            fn execute(&mut self, ctx: &ActorExecutionContext) -> TickResult {
+                use block_traits::ExecutionContextTrait;
+                self.log.borrow_mut().push(ctx.time());
+                let b = Contract::new("B");
+                let change = if self.contracts.contains(&b) {
+                    self.contracts.retain(|c| *c != b);
+                    Effect::unsubscribe(b)
+                } else {
+                    self.contracts.push(b.clone());
+                    Effect::subscribe(b)
+                };
+                self.changes.push(change);
+                Ok(TickOutcome::default())
+            }
+        }
+
+        #[test]
+        fn test() {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            let mut ctrl = ActorController::new();
+            ctrl.add_actor(ActorHandle::new(SubscribingActor {
+                contracts: vec![Contract::new("A")],
+                changes: Vec::new(),
+                log: log.clone(),
+            }));
+
+            // Not subscribed to "B" yet.
+            ctrl.tick_delta(&Delta(Contract::new("B")));
+            assert!(log.borrow().is_empty());
+
+            ctrl.tick_delta(&Delta(Contract::new("A")));
+            ctrl.tick_delta(&Delta(Contract::new("B")));
+            assert_eq!(*log.borrow(), [1, 2]);
+
+            // The tick on "B" unsubscribed again.
+            ctrl.tick_delta(&Delta(Contract::new("B")));
+            assert_eq!(*log.borrow(), [1, 2]);
+            assert!(!ctrl.contracts_to_actors.contains_key(&Contract::new("B")));
+        }
+    }
+
+    mod suspended_actors_skip_ticks_until_resumed {
+        use super::*;
+        use ::trade_types::Contract;
+
+        /// Suspends itself on every tick, with a timer if `timer` is set.
+        struct SleepyActor {
+            id: u32,
+            timer: Option<u64>,
+            suspension: Option<Suspension>,
+            log: Rc<RefCell<Vec<u64>>>,
+        }
+
+        impl ActorTrait for SleepyActor {
+            fn actor_id(&self) -> u32 {
+                self.id
+            }
+            fn contracts(&self) -> Vec<Contract> {
+                vec![Contract::new("A")]
+            }
+            fn suspension(&self) -> Option<Suspension> {
+                self.suspension
+            }
+            fn resume(&mut self) {
+                self.suspension = None;
+            }
+            fn execute(&mut self, ctx: &ActorExecutionContext) -> TickResult {
+                use block_traits::ExecutionContextTrait;
+                self.log.borrow_mut().push(ctx.time());
+                self.suspension = Some(match self.timer {
+                    Some(duration) => Suspension::UntilTime(ctx.time() + duration),
+                    None => Suspension::UntilResumed,
+                });
+                Ok(TickOutcome::default())
+            }
+        }
+
+        fn tick(ctrl: &mut ActorController) {
+            ctrl.tick_delta(&Delta(Contract::new("A")));
+        }
+
+        #[test]
+        fn explicit_resume() {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            let mut ctrl = ActorController::new();
+            ctrl.add_actor(ActorHandle::new(SleepyActor {
+                id: 1,
+                timer: None,
+                suspension: None,
+                log: log.clone(),
+            }));
+
+            tick(&mut ctrl);
+            tick(&mut ctrl);
+            tick(&mut ctrl);
+            assert_eq!(*log.borrow(), [0]);
+            assert_eq!(ctrl.metrics().executed_last_tick, 0);
+            // Skipped actors are not failures.
+            assert_eq!(ctrl.metrics().actors, 1);
+
+            assert!(ctrl.resume(1));
+            assert!(!ctrl.resume(2));
+            tick(&mut ctrl);
+            assert_eq!(*log.borrow(), [0, 3]);
+            assert_eq!(ctrl.metrics().executed_last_tick, 1);
+        }
+
+        #[test]
+        fn timer_resume() {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            let mut ctrl = ActorController::new();
+            ctrl.add_actor(ActorHandle::new(SleepyActor {
+                id: 1,
+                timer: Some(2),
+                suspension: None,
+                log: log.clone(),
+            }));
+
+            for _ in 0..5 {
+                tick(&mut ctrl);
+            }
+            assert_eq!(*log.borrow(), [0, 2, 4]);
+        }
+    }
+
+    mod new_checked_rejects_missing_capabilities {
+        use super::*;
+        use ::block_traits::execution_context::MissingCapability;
+        use ::block_traits::{BlockSpec, Capability};
+
+        make_defaults!(input, output, state, init_params);
+
+        #[block(intents = block_traits::intents::ZeroIntents, contract_deps = false)]
+        pub struct TestBlock {
+            pub block_id: u32,
+        }
+
+        impl BlockSpec for TestBlock {
+            fn block_id(&self) -> u32 {
+                self.block_id
+            }
+
+            fn new_from_init_params(_params: &InitParams) -> Self {
+                Self { block_id: 0 }
+            }
+
+            fn required_capabilities() -> &'static [Capability] {
+                &[Capability::OrderBook, Capability::Position]
+            }
+
+            fn init_state(&self) -> State {
+                State
+            }
+
+            #[execute]
+            fn execute(&self, _input: Input) -> Self::Intents {
+                ::block_traits::intents::ZeroIntents
+            }
+        }
+
+        #[test]
+        fn test() {
+            use ::block_traits::BlockPackage;
+
+            let mut reg = ::channels::ChannelRegistry::new();
+            let package: BlockPackage<TestBlock> =
+                BlockPackage::new(InputKeys {}, OutputKeys {}, InitParams, None);
+            let block = package.weave(&mut reg).unwrap();
+
+            // The actor context does not provide positions.
+            let err = Actor::new_checked(1, Box::new(block)).err().unwrap();
+            assert_eq!(err, MissingCapability(Capability::Position));
+            assert!(err.to_string().contains("Position"));
+        }
+    }
 }
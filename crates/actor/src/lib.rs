@@ -6,10 +6,17 @@ mod actor;
 mod actor_execution_context;
 mod controller;
 mod orders;
-pub use actor::{Actor, ActorAlgo, ActorTrait};
+mod reconciliation;
+pub use actor::{
+    Actor, ActorAlgo, ActorTrait, IntentBudget, OverBudget, Suspension, TickOutcome, TickResult,
+    INTENT_BUDGET_EXCEEDED,
+};
 pub use actor_execution_context::ActorExecutionContext;
-pub use controller::ActorController;
+pub use controller::{ActorController, ActorListener, ControllerMetrics};
 pub use orders::Order;
+pub use reconciliation::{
+    batch_orders, Batching, BatchingPolicy, ReconciliationPolicy, ReplacePolicy,
+};
 
 /// Mock delta
 #[derive(Debug)]
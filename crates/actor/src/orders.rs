@@ -10,6 +10,8 @@ pub enum Order {
         side: Side,
         price: Price,
         quantity: Quantity,
+        /// Priority of the intent that placed the order.
+        priority: u8,
     },
     Cancel {
         contract: Contract,
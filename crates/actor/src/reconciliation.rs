@@ -0,0 +1,249 @@
+use block_traits::Intent;
+use trade_types::{MilliKw, Side};
+
+use super::Order;
+
+/// Policy for turning an intent into the next order state of a slot.
+///
+/// The reconciliator keeps one order per intent slot. On each tick, every
+/// intent emitted by the algorithm is combined with the previous order in its
+/// slot to produce the new order. Venues differ in how this should work
+/// (replace vs. net positions), so the policy can be plugged into an actor
+/// with `Actor::new_with_policy`.
+pub trait ReconciliationPolicy {
+    /// Compute the new order state of a slot given its previous order and the intent.
+    fn reconcile(&self, prev_order: &Order, intent: &Intent) -> Order;
+
+    /// How the orders of all slots are combined into the outbound orders.
+    ///
+    /// Defaults to `Batching::Keep`, sending every slot's order.
+    fn batching(&self) -> Batching {
+        Batching::Keep
+    }
+}
+
+/// How the reconciliator combines the orders of different slots before they
+/// are sent, see `batch_orders`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Batching {
+    /// Send every slot's order.
+    #[default]
+    Keep,
+    /// Send identical orders once.
+    Dedup,
+    /// Send identical orders once, then net the buys and sells for the same
+    /// contract and price into a single order, or none if they cancel out.
+    Net,
+}
+
+/// Wraps a policy to reconcile slots with it while batching the outbound
+/// orders as configured.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BatchingPolicy<P> {
+    policy: P,
+    batching: Batching,
+}
+
+impl<P: ReconciliationPolicy> BatchingPolicy<P> {
+    pub fn new(policy: P, batching: Batching) -> Self {
+        Self { policy, batching }
+    }
+}
+
+impl<P: ReconciliationPolicy> ReconciliationPolicy for BatchingPolicy<P> {
+    fn reconcile(&self, prev_order: &Order, intent: &Intent) -> Order {
+        self.policy.reconcile(prev_order, intent)
+    }
+
+    fn batching(&self) -> Batching {
+        self.batching
+    }
+}
+
+/// Combine the orders of all slots into the orders to send.
+///
+/// Empty slots are dropped. The remaining orders keep the order of their
+/// slots; a netted order takes the place of the first order it absorbed and
+/// the highest priority among them.
+pub fn batch_orders(orders: &[Order], batching: Batching) -> Vec<Order> {
+    let mut out: Vec<Order> = Vec::with_capacity(orders.len());
+    for order in orders {
+        let duplicate = batching != Batching::Keep && out.contains(order);
+        if *order != Order::NoOrder && !duplicate {
+            out.push(order.clone());
+        }
+    }
+    if batching == Batching::Net {
+        out = net_orders(out);
+    }
+    out
+}
+
+/// Net buys against sells with the same contract and price.
+fn net_orders(orders: Vec<Order>) -> Vec<Order> {
+    // Netted totals in milli-kW, and the position of the group's order in `out`.
+    struct Group {
+        at: usize,
+        buy: u64,
+        sell: u64,
+    }
+
+    let mut out: Vec<Order> = Vec::with_capacity(orders.len());
+    let mut groups: Vec<Group> = Vec::new();
+    for order in orders {
+        let Order::New {
+            contract,
+            side,
+            price,
+            quantity,
+            priority,
+        } = &order
+        else {
+            out.push(order);
+            continue;
+        };
+        let existing = groups.iter_mut().find(|group| {
+            matches!(&out[group.at], Order::New { contract: c, price: p, .. } if c == contract && p == price)
+        });
+        let group = match existing {
+            Some(group) => group,
+            None => {
+                out.push(order.clone());
+                groups.push(Group {
+                    at: out.len() - 1,
+                    buy: 0,
+                    sell: 0,
+                });
+                groups.last_mut().unwrap()
+            }
+        };
+        let MilliKw(milli_kw) = quantity.in_milli_kw();
+        match side {
+            Side::Buy => group.buy += milli_kw,
+            Side::Sell => group.sell += milli_kw,
+        }
+        if let Order::New { priority: p, .. } = &mut out[group.at] {
+            *p = (*p).max(*priority);
+        }
+    }
+
+    for group in &groups {
+        if let Order::New { side, quantity, .. } = &mut out[group.at] {
+            *side = if group.buy >= group.sell {
+                Side::Buy
+            } else {
+                Side::Sell
+            };
+            *quantity = MilliKw(group.buy.abs_diff(group.sell)).into();
+        }
+        if group.buy == group.sell {
+            out[group.at] = Order::NoOrder;
+        }
+    }
+    out.retain(|order| *order != Order::NoOrder);
+    out
+}
+
+/// The default reconciliation policy.
+///
+/// - `NoIntent` clears the slot
+/// - `Place` overwrites the slot with a new order request
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReplacePolicy;
+
+impl ReconciliationPolicy for ReplacePolicy {
+    fn reconcile(&self, _prev_order: &Order, intent: &Intent) -> Order {
+        match intent {
+            Intent::NoIntent => Order::NoOrder,
+            Intent::Place {
+                contract,
+                side,
+                price,
+                quantity,
+                priority,
+            } => Order::New {
+                contract: contract.clone(),
+                side: side.clone(),
+                price: price.clone(),
+                quantity: quantity.clone(),
+                priority: *priority,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use trade_types::{Cents, Contract, Kw, Price, Quantity, Side};
+
+    fn place(kw: u32) -> Intent {
+        Intent::Place {
+            contract: Contract::new("A"),
+            side: Side::Buy,
+            price: Price::from(Cents(100)),
+            quantity: Quantity::from(Kw(kw)),
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn replace_policy_overwrites_previous_order() {
+        let first = ReplacePolicy.reconcile(&Order::NoOrder, &place(1));
+        let second = ReplacePolicy.reconcile(&first, &place(2));
+
+        match second {
+            Order::New { quantity, .. } => assert_eq!(quantity.as_kw_f64(), 2.0),
+            other => panic!("expected New order, got {other:?}"),
+        }
+    }
+
+    fn order(side: Side, kw: u32) -> Order {
+        match ReplacePolicy.reconcile(&Order::NoOrder, &place(kw)) {
+            Order::New {
+                contract,
+                price,
+                quantity,
+                priority,
+                ..
+            } => Order::New {
+                contract,
+                side,
+                price,
+                quantity,
+                priority,
+            },
+            other => other,
+        }
+    }
+
+    #[test]
+    fn dedup_sends_identical_orders_once() {
+        let orders = [
+            order(Side::Buy, 5),
+            Order::NoOrder,
+            order(Side::Buy, 5),
+            order(Side::Buy, 3),
+        ];
+        assert_eq!(batch_orders(&orders, Batching::Keep).len(), 3);
+        assert_eq!(
+            batch_orders(&orders, Batching::Dedup),
+            [order(Side::Buy, 5), order(Side::Buy, 3)]
+        );
+    }
+
+    #[test]
+    fn net_leaves_the_difference_on_the_larger_side() {
+        let orders = [order(Side::Buy, 5), order(Side::Sell, 8)];
+        assert_eq!(batch_orders(&orders, Batching::Net), [order(Side::Sell, 3)]);
+    }
+
+    #[test]
+    fn replace_policy_clears_on_no_intent() {
+        let first = ReplacePolicy.reconcile(&Order::NoOrder, &place(1));
+        assert_eq!(
+            ReplacePolicy.reconcile(&first, &Intent::NoIntent),
+            Order::NoOrder
+        );
+    }
+}
@@ -11,8 +11,10 @@ pub fn block_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut state_type: Option<Path> = None;
     let mut init_type: Option<Path> = None;
     let mut intents_type: Option<Path> = None;
+    let mut block_name: Option<String> = None;
 
     let mut contract_deps_enabled: Option<bool> = None;
+    let mut block_spec_check = true;
 
     if !attr.is_empty() {
         let args: syn::punctuated::Punctuated<Meta, syn::Token![,]> =
@@ -29,6 +31,9 @@ pub fn block_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
 
             match &meta_name_value.value {
                 Expr::Lit(expr_lit) => match &expr_lit.lit {
+                    syn::Lit::Str(lit_str) if name == "name" => {
+                        block_name = Some(lit_str.value());
+                    }
                     syn::Lit::Str(lit_str) => {
                         let type_path: Path = syn::parse_str(&lit_str.value()).unwrap();
                         match name.as_str() {
@@ -40,10 +45,11 @@ pub fn block_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
                             _ => {}
                         }
                     }
-                    syn::Lit::Bool(lit_bool) => {
-                        if name == "contract_deps" {
-                            contract_deps_enabled = Some(lit_bool.value());
-                        }
+                    syn::Lit::Bool(lit_bool) if name == "contract_deps" => {
+                        contract_deps_enabled = Some(lit_bool.value());
+                    }
+                    syn::Lit::Bool(lit_bool) if name == "block_spec" => {
+                        block_spec_check = lit_bool.value();
                     }
                     _ => {}
                 },
@@ -61,6 +67,7 @@ pub fn block_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
     }
 
     let contract_deps_enabled = contract_deps_enabled.unwrap_or(true);
+    let block_name = block_name.unwrap_or_else(|| struct_name.to_string());
 
     let input_type = input_type.unwrap_or_else(|| syn::parse_str("Input").unwrap());
     let output_type = output_type.unwrap_or_else(|| syn::parse_str("Output").unwrap());
@@ -110,16 +117,33 @@ pub fn block_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
         quote! {}
     };
 
+    // Check that the struct implements `BlockSpec`, so a missing impl is
+    // reported at the struct rather than where the block is first used.
+    // Opt out with `#[block(block_spec = false)]`.
+    let block_spec_check = if block_spec_check {
+        quote::quote_spanned! {struct_name.span()=>
+            const _: fn() = || {
+                fn assert_block_spec<T: ::block_traits::BlockSpec>() {}
+                assert_block_spec::<#struct_name>();
+            };
+        }
+    } else {
+        quote! {}
+    };
+
     let expanded = quote! {
         #[derive(Clone, Debug)]
         #input
 
+        #block_spec_check
+
         impl ::block_traits::BlockSpecAssociatedTypes for #struct_name {
             type Input = #input_type;
             type Output = #output_type;
             type State = #state_type;
             type InitParameters = #init_params;
             type Intents = #intents_type;
+            const NAME: &'static str = #block_name;
         }
 
         #default_contract_deps_impl
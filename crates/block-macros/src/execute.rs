@@ -10,10 +10,21 @@ use syn::{
 ///
 /// This macro:
 /// - Normalizes the method signature to always include `context`, `input`, `state`, `effect_consumer`
+/// - Turns methods taking a `prev_output: Option<&Output>` parameter into
+///   `execute_with_prev_output`, with `execute` delegating to it and
+///   `USES_PREV_OUTPUT` set
 /// - Inserts the generic parameters `<C, E>` with the required trait bounds
 /// - Adapts various original return shapes (unit/output/state/intents/tuples/Option/Result)
 ///   into the canonical `(Output, State, Intents)` tuple wrapped in `Result`.
-pub fn execute_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
+///
+/// With `#[execute(first)]` the method becomes `execute_first`, run on the
+/// block's first execution, and `USES_FIRST_EXECUTE` is set.
+pub fn execute_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let first = match parse_args(attr) {
+        Ok(first) => first,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
     // Parse the annotated function.
     let mut f = parse_macro_input!(item as ItemFn);
 
@@ -41,16 +52,24 @@ pub fn execute_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut input_pat: Option<Box<Pat>> = None;
     let mut state_pat: Option<Box<Pat>> = None;
     let mut eff_pat: Option<Box<Pat>> = None;
+    let mut prev_pat: Option<Box<Pat>> = None;
 
     // Walk the input argument list and classify each typed argument into one of:
     // - context: `&C` where `C: ExecutionContextTrait` (or a generic single ident)
     // - input: `Input` (by value)
     // - state: `&State`
     // - effect consumer: `&mut E` where `E: EffectConsumerTrait` (or a generic single ident)
+    // - previous output: `Option<&Output>`
     //
     // Any other parameter shape is rejected to keep the rewriting predictable.
     for arg in f.sig.inputs.iter() {
         if let FnArg::Typed(PatType { pat, ty, .. }) = arg {
+            // `&State` is checked before the context, as a single-identifier
+            // reference would otherwise be taken for a generic context type.
+            if state_pat.is_none() && is_ref_to_state(ty) {
+                state_pat = Some(pat.clone());
+                continue;
+            }
             if ctx_pat.is_none() && is_ref_to_exec_ctx_trait_or_generic(ty) {
                 ctx_pat = Some(pat.clone());
                 continue;
@@ -59,18 +78,18 @@ pub fn execute_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 input_pat = Some(pat.clone());
                 continue;
             }
-            if state_pat.is_none() && is_ref_to_state(ty) {
-                state_pat = Some(pat.clone());
-                continue;
-            }
             if eff_pat.is_none() && is_mut_ref_to_effect_consumer_trait_or_generic(ty) {
                 eff_pat = Some(pat.clone());
                 continue;
             }
+            if prev_pat.is_none() && is_option_ref_to_output(ty) {
+                prev_pat = Some(pat.clone());
+                continue;
+            }
 
             return syn::Error::new(
                 ty.span(),
-                "unsupported parameter type for #[execute]. Allowed: &C (ExecutionContextTrait), Input (by value), &State, &mut E (EffectConsumerTrait)",
+                "unsupported parameter type for #[execute]. Allowed: &C (ExecutionContextTrait), Input (by value), &State, &mut E (EffectConsumerTrait), Option<&Output>",
             )
             .to_compile_error()
             .into();
@@ -99,10 +118,11 @@ pub fn execute_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     // - &C context
     // - Input (associated type)
     // - &State (associated type)
+    // - Option<&Output> previous output, for `execute_with_prev_output`
     // - &mut E effect consumer
     f.sig.inputs = {
         let mut inputs = syn::punctuated::Punctuated::new();
-        inputs.push(receiver);
+        inputs.push(receiver.clone());
         inputs.push(syn::parse_quote!(#ctx_pat: &C));
         inputs.push(syn::parse_quote!(
             #input_pat: <Self as ::block_traits::BlockSpecAssociatedTypes>::Input
@@ -110,6 +130,11 @@ pub fn execute_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
         inputs.push(syn::parse_quote!(
             #state_pat: &<Self as ::block_traits::BlockSpecAssociatedTypes>::State
         ));
+        if let Some(prev_pat) = &prev_pat {
+            inputs.push(syn::parse_quote!(
+                #prev_pat: ::core::option::Option<&<Self as ::block_traits::BlockSpecAssociatedTypes>::Output>
+            ));
+        }
         inputs.push(syn::parse_quote!(#eff_pat: &mut E));
         inputs
     };
@@ -242,7 +267,9 @@ pub fn execute_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     //
     // Supported original return shapes:
     // - no explicit return / `()` / `Result<(), _>` / `Option<()>`
-    // - `Result<T, FailureStatus>`: map Ok(T) into the canonical tuple, propagate Err(_)
+    // - `Result<T, E>` with `E: Into<FailureStatus>`: map Ok(T) into the canonical tuple,
+    //   convert and propagate Err(_). The closure is annotated with the original return
+    //   type so that `?` in the body still knows which error type to convert into.
     // - `Option<T>`: map Some(T) into Ok(tuple), map None into Err(Failure)
     // - plain `T`: wrap into Ok(tuple)
     let adapted: proc_macro2::TokenStream = match original_output {
@@ -261,17 +288,17 @@ pub fn execute_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
             } else if let Some(ok_ty) = result_ok_inner_type(ty) {
                 if is_unit_type(ok_ty) {
                     quote! {
-                        match (|| #original_block )() {
+                        match (|| -> #ty #original_block )() {
                             ::core::result::Result::Ok(()) => ::core::result::Result::Ok((#def, #def, #def)),
-                            ::core::result::Result::Err(e) => ::core::result::Result::Err(e),
+                            ::core::result::Result::Err(e) => ::core::result::Result::Err(::core::convert::Into::into(e)),
                         }
                     }
                 } else {
                     match adapt_ok_tuple_expr(quote!(val), ok_ty) {
                         Ok(tuple_expr) => quote! {
-                            match (|| #original_block )() {
+                            match (|| -> #ty #original_block )() {
                                 ::core::result::Result::Ok(val) => ::core::result::Result::Ok(#tuple_expr),
-                                ::core::result::Result::Err(e) => ::core::result::Result::Err(e),
+                                ::core::result::Result::Err(e) => ::core::result::Result::Err(::core::convert::Into::into(e)),
                             }
                         },
                         Err(e) => return e.to_compile_error().into(),
@@ -310,8 +337,76 @@ pub fn execute_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     // Replace the function body with the adapted body.
     f.block = syn::parse_quote!({ #adapted });
 
-    // Emit the rewritten function.
-    quote!(#f).into()
+    if first {
+        if let Some(prev_pat) = &prev_pat {
+            return syn::Error::new(
+                prev_pat.span(),
+                "#[execute(first)] methods cannot take a previous output; there is none on the first execution",
+            )
+            .to_compile_error()
+            .into();
+        }
+        f.sig.ident = syn::Ident::new("execute_first", f.sig.ident.span());
+        return quote! {
+            const USES_FIRST_EXECUTE: bool = true;
+
+            #f
+        }
+        .into();
+    }
+
+    if prev_pat.is_none() {
+        // Emit the rewritten function.
+        return quote!(#f).into();
+    }
+
+    // With a previous output the body becomes `execute_with_prev_output`, and
+    // `execute` runs it as a first execution.
+    f.sig.ident = syn::Ident::new("execute_with_prev_output", f.sig.ident.span());
+    let generics = &f.sig.generics;
+    let output = &f.sig.output;
+    quote! {
+        const USES_PREV_OUTPUT: bool = true;
+
+        fn execute #generics (
+            #receiver,
+            context: &C,
+            input: <Self as ::block_traits::BlockSpecAssociatedTypes>::Input,
+            state: &<Self as ::block_traits::BlockSpecAssociatedTypes>::State,
+            effect_consumer: &mut E,
+        ) #output {
+            self.execute_with_prev_output(context, input, state, ::core::option::Option::None, effect_consumer)
+        }
+
+        #f
+    }
+    .into()
+}
+
+/// Parse the arguments of `#[execute(...)]`, returning whether `first` was given.
+///
+/// `inner = "..."` is accepted as documented but does not change the generated
+/// code. Anything else is an error, so a misspelled `first` is not silently
+/// taken for a regular `#[execute]`.
+fn parse_args(attr: TokenStream) -> Result<bool, syn::Error> {
+    let args = syn::parse::Parser::parse(
+        syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+        attr,
+    )?;
+    let mut first = false;
+    for arg in args {
+        match &arg {
+            syn::Meta::Path(path) if path.is_ident("first") => first = true,
+            syn::Meta::NameValue(name_value) if name_value.path.is_ident("inner") => {}
+            _ => {
+                return Err(syn::Error::new(
+                    arg.span(),
+                    "unknown execute argument, expected `first` or `inner = \"...\"`",
+                ))
+            }
+        }
+    }
+    Ok(first)
 }
 
 /// Returns true if `ty` is an immutable reference to a type that is either:
@@ -337,6 +432,12 @@ fn is_ref_to_state(ty: &Type) -> bool {
     matches!(ty, Type::Reference(r) if r.mutability.is_none() && is_last_segment(&r.elem, "State"))
 }
 
+/// Returns true if `ty` is `Option<&T>` where `T` ends with `Output`.
+fn is_option_ref_to_output(ty: &Type) -> bool {
+    matches!(option_inner_type(ty), Some(Type::Reference(r))
+        if r.mutability.is_none() && is_output(&r.elem))
+}
+
 /// Returns true if `ty` is a simple path type with a single identifier and no generic arguments.
 fn is_ref_to_single_ident(ty: &Type) -> bool {
     let Type::Path(TypePath { qself: None, path }) = ty else {
@@ -0,0 +1,36 @@
+use syn::{punctuated::Punctuated, Attribute, Path, Token};
+
+/// Derives the macros always put on generated keys types.
+pub const KEYS_DERIVES: &[&str] = &[
+    "Clone",
+    "Debug",
+    "PartialEq",
+    "Serialize",
+    "Deserialize",
+    "Serializable",
+];
+
+/// The doc comments among `attrs`, to copy onto a generated item.
+pub fn doc_attrs(attrs: &[Attribute]) -> Vec<&Attribute> {
+    attrs.iter().filter(|a| a.path().is_ident("doc")).collect()
+}
+
+/// The paths in the user's `#[derive(...)]` attributes, except those whose last
+/// segment is in `existing`, so they can be added to a generated item that
+/// already derives `existing`.
+pub fn extra_derives(attrs: &[Attribute], existing: &[&str]) -> Vec<Path> {
+    attrs
+        .iter()
+        .filter(|a| a.path().is_ident("derive"))
+        .filter_map(|a| {
+            a.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .filter(|path| {
+            path.segments
+                .last()
+                .is_none_or(|seg| !existing.iter().any(|name| seg.ident == name))
+        })
+        .collect()
+}
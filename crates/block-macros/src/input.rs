@@ -1,9 +1,13 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Data, DeriveInput, Fields};
+use syn::{Data, DeriveInput, Fields, GenericArgument, PathArguments, Type};
 
-pub fn input_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn input_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input = syn::parse::<DeriveInput>(item).unwrap();
+    let register_defaults = match parse_args(attr) {
+        Ok(register_defaults) => register_defaults,
+        Err(err) => return err.to_compile_error().into(),
+    };
     let struct_name = &input.ident;
 
     let keys_name = syn::Ident::new(
@@ -15,6 +19,10 @@ pub fn input_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
         proc_macro2::Span::call_site(),
     );
 
+    // Carry the user's doc comments and extra derives over to the keys type.
+    let docs = crate::forward::doc_attrs(&input.attrs);
+    let derives = crate::forward::extra_derives(&input.attrs, crate::forward::KEYS_DERIVES);
+
     let fields_opt = match &input.data {
         Data::Struct(data_struct) => match &data_struct.fields {
             Fields::Named(fields_named) => Some(&fields_named.named),
@@ -30,7 +38,8 @@ pub fn input_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
             #[derive(Clone, Debug)]
             #input
 
-            #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, ::serialization_macros::Serializable)]
+            #(#docs)*
+            #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, ::serialization_macros::Serializable #(, #derives)*)]
             pub struct #keys_name {}
 
             pub struct #reader_name;
@@ -69,40 +78,167 @@ pub fn input_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     }
 
     // Named fields case (existing behavior)
-    let fields = fields_opt.unwrap();
-    let field_idents = fields.iter().map(|f| f.ident.as_ref().unwrap());
+    let all_fields = fields_opt.unwrap();
+
+    // Fields marked `#[from_context(source)]` are set from the execution
+    // context before each execution instead of being read from a channel, so
+    // they get no key.
+    let mut context_fields = Vec::new();
+    let mut fields = Vec::new();
+    for field in all_fields {
+        match context_source(field) {
+            Ok(Some(source)) => context_fields.push((&field.ident, source)),
+            Ok(None) => fields.push(field),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    }
+
+    // The attribute is ours; strip it from the struct we emit.
+    let mut item = input.clone();
+    if let Data::Struct(data_struct) = &mut item.data {
+        for field in data_struct.fields.iter_mut() {
+            field
+                .attrs
+                .retain(|attr| !attr.path().is_ident("from_context"));
+        }
+    }
 
+    // Fields of type `Option<T>` are optional channels: their key is an
+    // `Option<String>` that may be left out of the configuration, in which
+    // case the field reads as `None`.
     let key_fields = fields.iter().map(|field| {
         let field_name = &field.ident;
-        quote! { pub #field_name: String }
+        let field_docs = crate::forward::doc_attrs(&field.attrs);
+        if optional_inner_type(&field.ty).is_some() {
+            quote! {
+                #(#field_docs)*
+                #[serde(default)]
+                pub #field_name: Option<String>
+            }
+        } else {
+            quote! { #(#field_docs)* pub #field_name: String }
+        }
+    });
+
+    // Unset optional keys are left out of human-readable formats such as JSON,
+    // but binary formats, which are not self-describing, need every field.
+    let keys_name_str = keys_name.to_string();
+    let num_key_fields = fields.len();
+    let serialize_fields = fields.iter().map(|field| {
+        let field_name = &field.ident;
+        let field_name_str = field_name.as_ref().unwrap().to_string();
+        if optional_inner_type(&field.ty).is_some() {
+            quote! {
+                if readable && self.#field_name.is_none() {
+                    state.skip_field(#field_name_str)?;
+                } else {
+                    state.serialize_field(#field_name_str, &self.#field_name)?;
+                }
+            }
+        } else {
+            quote! { state.serialize_field(#field_name_str, &self.#field_name)?; }
+        }
+    });
+
+    let channel_names = fields.iter().map(|field| {
+        let field_name = &field.ident;
+        if optional_inner_type(&field.ty).is_some() {
+            quote! { names.extend(self.#field_name.clone()); }
+        } else {
+            quote! { names.push(self.#field_name.clone()); }
+        }
     });
 
     let reader_fields = fields.iter().map(|field| {
         let field_name = &field.ident;
         let field_type = &field.ty;
-        quote! { #field_name: std::rc::Rc<std::cell::RefCell<#field_type>> }
+        match optional_inner_type(field_type) {
+            Some(inner) => {
+                quote! { #field_name: Option<std::rc::Rc<std::cell::RefCell<#inner>>> }
+            }
+            None => quote! { #field_name: std::rc::Rc<std::cell::RefCell<#field_type>> },
+        }
     });
 
     let reader_assignments = fields.iter().map(|field| {
         let field_name = &field.ident;
         let field_type = &field.ty;
-        quote! { #field_name: registry.get::<#field_type>(&self.#field_name)? }
+        match optional_inner_type(field_type) {
+            Some(inner) => quote! {
+                #field_name: match &self.#field_name {
+                    Some(key) => Some(registry.get::<#inner>(key).map_err(|e| e.in_reader(key))?),
+                    None => None,
+                }
+            },
+            None => quote! {
+                #field_name: registry
+                    .get::<#field_type>(&self.#field_name)
+                    .map_err(|e| e.in_reader(&self.#field_name))?
+            },
+        }
+    });
+
+    // Creating missing channels with default values needs every field type to
+    // be `Default`, so it is only generated for `#[input(defaults)]`. Optional
+    // inputs are left out: a default value would read as `Some` and could not
+    // be told apart from a real one, so their missing channels stay missing.
+    let default_registrations = fields
+        .iter()
+        .filter(|field| optional_inner_type(&field.ty).is_none())
+        .map(|field| {
+            let field_name = &field.ident;
+            let field_type = &field.ty;
+            quote! { registry.ensure::<#field_type>(&self.#field_name)?; }
+        });
+    let register_defaults_fn = register_defaults.then(|| {
+        quote! {
+            #[allow(unused_variables)]
+            fn register_defaults(&self, registry: &mut ::channels::ChannelRegistry) -> Result<(), ::channels::RegistryError> {
+                #(#default_registrations)*
+                Ok(())
+            }
+        }
     });
 
     let read_assignments = fields.iter().map(|field| {
         let field_name = &field.ident;
-        quote! { #field_name: *self.#field_name.borrow() }
+        if optional_inner_type(&field.ty).is_some() {
+            quote! { #field_name: self.#field_name.as_ref().map(|cell| cell.borrow().clone()) }
+        } else {
+            quote! { #field_name: self.#field_name.borrow().clone() }
+        }
+    });
+
+    let context_defaults = context_fields.iter().map(|(field_name, _)| {
+        quote! { #field_name: ::core::default::Default::default() }
+    });
+
+    let context_assignments = context_fields.iter().map(|(field_name, source)| {
+        quote! { self.#field_name = context.#source(); }
     });
 
     let expanded = quote! {
         #[derive(Clone, Debug)]
-        #input
+        #item
 
-        #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, ::serialization_macros::Serializable)]
+        #(#docs)*
+        #[derive(Clone, Debug, PartialEq, serde::Deserialize, ::serialization_macros::Serializable #(, #derives)*)]
         pub struct #keys_name {
             #(#key_fields,)*
         }
 
+        impl serde::Serialize for #keys_name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use serde::ser::SerializeStruct;
+                #[allow(unused_variables)]
+                let readable = serializer.is_human_readable();
+                #[allow(unused_mut)]
+                let mut state = serializer.serialize_struct(#keys_name_str, #num_key_fields)?;
+                #(#serialize_fields)*
+                state.end()
+            }
+        }
+
         pub struct #reader_name {
             #(#reader_fields,)*
         }
@@ -111,6 +247,7 @@ pub fn input_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
             pub fn read(&self) -> #struct_name {
                 #struct_name {
                     #(#read_assignments,)*
+                    #(#context_defaults,)*
                 }
             }
         }
@@ -123,22 +260,98 @@ pub fn input_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
         impl ::channels::ChannelKeys for #keys_name {
             fn channel_names(&self) -> Vec<String> {
-                vec![ #(self.#field_idents.clone(),)* ]
+                #[allow(unused_mut)]
+                let mut names = Vec::new();
+                #(#channel_names)*
+                names
             }
         }
 
         impl ::channels::InputKeys<#struct_name> for #keys_name {
             type ReaderType = #reader_name;
 
+            #[allow(unused_variables)]
             fn reader(&self, registry: &::channels::ChannelRegistry) -> Result<Self::ReaderType, ::channels::RegistryError> {
                 Ok(#reader_name { #(#reader_assignments,)* })
             }
+
+            #register_defaults_fn
         }
 
         impl ::block_traits::BlockInput for #struct_name {
             type Keys = #keys_name;
+
+            #[allow(unused_variables)]
+            fn fill_from_context<C: ::block_traits::ExecutionContextTrait>(&mut self, context: &C) {
+                #(#context_assignments)*
+            }
         }
     };
 
     TokenStream::from(expanded)
 }
+
+/// Parse the arguments of `#[input(...)]`, returning whether `defaults` was given.
+///
+/// With `defaults`, the keys implement `InputKeys::register_defaults`, so
+/// lenient weaving can create missing input channels; every required input
+/// type must then implement `Default`. Optional inputs are never created.
+fn parse_args(attr: TokenStream) -> Result<bool, syn::Error> {
+    let args = syn::parse::Parser::parse(
+        syn::punctuated::Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated,
+        attr,
+    )?;
+    let mut register_defaults = false;
+    for arg in args {
+        match arg.to_string().as_str() {
+            "defaults" => register_defaults = true,
+            _ => {
+                return Err(syn::Error::new(
+                    arg.span(),
+                    "unknown input argument, expected `defaults`",
+                ))
+            }
+        }
+    }
+    Ok(register_defaults)
+}
+
+/// The context source of a field marked `#[from_context(source)]`, if any.
+///
+/// Sources are named after the `ExecutionContextTrait` method providing them;
+/// currently only `time` is supported.
+fn context_source(field: &syn::Field) -> Result<Option<syn::Ident>, syn::Error> {
+    let Some(attr) = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("from_context"))
+    else {
+        return Ok(None);
+    };
+    let source: syn::Ident = attr.parse_args()?;
+    match source.to_string().as_str() {
+        "time" => Ok(Some(source)),
+        _ => Err(syn::Error::new(
+            source.span(),
+            "unknown context source, expected `time`",
+        )),
+    }
+}
+
+/// If `ty` is `Option<T>`, returns `Some(T)`. Otherwise returns `None`.
+fn optional_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let seg = type_path.path.segments.last()?;
+    if seg.ident != "Option" {
+        return None;
+    }
+    match &seg.arguments {
+        PathArguments::AngleBracketed(ab) if ab.args.len() == 1 => match ab.args.first()? {
+            GenericArgument::Type(t) => Some(t),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -3,10 +3,16 @@ use proc_macro::TokenStream;
 mod block;
 mod contract_deps;
 mod execute;
+mod forward;
 mod input;
 mod make_defaults;
 mod output;
+mod state;
 
+/// With `#[input(defaults)]`, lenient weaving can create missing input
+/// channels holding their type's `Default`, which every field type must then
+/// implement. Channels of `Option<T>` fields are not created, so a lenient
+/// weave still fails if one has no producer.
 #[proc_macro_attribute]
 pub fn input(attr: TokenStream, item: TokenStream) -> TokenStream {
     input::input_impl(attr, item)
@@ -17,14 +23,11 @@ pub fn output(attr: TokenStream, item: TokenStream) -> TokenStream {
     output::output_impl(attr, item)
 }
 
+/// Fields marked `#[persist = false]` are not checkpointed: they are skipped
+/// when the state is serialized and set to their `Default` when it is loaded.
 #[proc_macro_attribute]
-pub fn state(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let item = syn::parse::<syn::DeriveInput>(item).unwrap();
-    let expanded = quote::quote! {
-        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ::serialization_macros::Serializable)]
-        #item
-    };
-    expanded.into()
+pub fn state(attr: TokenStream, item: TokenStream) -> TokenStream {
+    state::state_impl(attr, item)
 }
 
 #[proc_macro_attribute]
@@ -45,7 +48,7 @@ pub fn contract_deps_derive(item: TokenStream) -> TokenStream {
 pub fn init_params(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let item = syn::parse::<syn::DeriveInput>(item).unwrap();
     let expanded = quote::quote! {
-        #[derive(::block_macros::ContractDeps, Clone, Debug, serde::Serialize, serde::Deserialize, ::serialization_macros::Serializable)]
+        #[derive(::block_macros::ContractDeps, Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, ::serialization_macros::Serializable)]
         #item
     };
     expanded.into()
@@ -61,6 +64,7 @@ pub fn make_defaults(input: TokenStream) -> TokenStream {
 /// Optional args:
 ///   #[execute]
 ///   #[execute(inner="execute_impl")]   // name for the inner method if we must rename
+///   #[execute(first)]                  // run on the first execution, before #[execute]
 #[proc_macro_attribute]
 pub fn execute(attr: TokenStream, item: TokenStream) -> TokenStream {
     execute::execute_impl(attr, item)
@@ -15,6 +15,10 @@ pub fn output_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
         proc_macro2::Span::call_site(),
     );
 
+    // Carry the user's doc comments and extra derives over to the keys type.
+    let docs = crate::forward::doc_attrs(&input.attrs);
+    let derives = crate::forward::extra_derives(&input.attrs, crate::forward::KEYS_DERIVES);
+
     let fields_opt = match &input.data {
         Data::Struct(data_struct) => match &data_struct.fields {
             Fields::Named(fields_named) => Some(&fields_named.named),
@@ -30,7 +34,8 @@ pub fn output_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
             #[derive(Clone, Debug)]
             #input
 
-            #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, ::serialization_macros::Serializable)]
+            #(#docs)*
+            #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, ::serialization_macros::Serializable #(, #derives)*)]
             pub struct #keys_name {}
 
             pub struct #writer_name;
@@ -67,6 +72,12 @@ pub fn output_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
             impl ::block_traits::BlockOutput for #struct_name {
                 type Keys = #keys_name;
             }
+
+            impl channels::OutputKeysErased for #keys_name {
+                fn register_erased(&self, _registry: &mut channels::ChannelRegistry) -> Result<(), channels::RegistryError> {
+                    Ok(())
+                }
+            }
         };
         return TokenStream::from(expanded);
     }
@@ -77,7 +88,8 @@ pub fn output_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let key_fields = fields.iter().map(|field| {
         let field_name = &field.ident;
-        quote! { pub #field_name: String }
+        let field_docs = crate::forward::doc_attrs(&field.attrs);
+        quote! { #(#field_docs)* pub #field_name: String }
     });
 
     let writer_fields = fields.iter().map(|field| {
@@ -89,9 +101,16 @@ pub fn output_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let writer_assignments = fields.iter().map(|field| {
         let field_name = &field.ident;
         let field_type = &field.ty;
-        quote! { #field_name: registry.get::<#field_type>(&self.#field_name)? }
+        quote! {
+            #field_name: registry
+                .get_output::<#field_type>(&self.#field_name)
+                .map_err(|e| e.in_writer(&self.#field_name))?
+        }
     });
 
+    // Fields are written in declaration order. A consumer that reads the
+    // channels while the writer runs (e.g. in a lenient graph with a cycle)
+    // can rely on that order, so keep it when changing this.
     let write_assignments = fields.iter().map(|field| {
         let field_name = &field.ident;
         quote! { *self.#field_name.borrow_mut() = output.#field_name.clone() }
@@ -104,7 +123,8 @@ pub fn output_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
         #[derive(Clone, Debug)]
         #input
 
-        #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, ::serialization_macros::Serializable)]
+        #(#docs)*
+        #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, ::serialization_macros::Serializable #(, #derives)*)]
         pub struct #keys_name {
             #(#key_fields,)*
         }
@@ -114,6 +134,7 @@ pub fn output_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
         }
 
         impl #writer_name {
+            /// Write each field to its channel, in the order the fields are declared.
             pub fn write(&self, output: &#struct_name) {
                 #(#write_assignments;)*
             }
@@ -139,7 +160,9 @@ pub fn output_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
             }
 
             fn register(&self, registry: &mut channels::ChannelRegistry) -> Result<(), channels::RegistryError> {
-                #( registry.ensure::<#field_types>(&self.#field_names)?; )*
+                // Cloneable, so the channels are copied when the registry is forked,
+                // and recorded as woven outputs, so re-weaves can clean them up.
+                #( registry.register_output::<#field_types>(&self.#field_names)?; )*
                 Ok(())
             }
         }
@@ -147,6 +170,12 @@ pub fn output_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
         impl ::block_traits::BlockOutput for #struct_name {
             type Keys = #keys_name;
         }
+
+        impl channels::OutputKeysErased for #keys_name {
+            fn register_erased(&self, registry: &mut channels::ChannelRegistry) -> Result<(), channels::RegistryError> {
+                <Self as channels::OutputKeys<#struct_name>>::register(self, registry)
+            }
+        }
     };
 
     TokenStream::from(expanded)
@@ -0,0 +1,58 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Expr, ExprLit, Lit, Meta};
+
+pub fn state_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut item = syn::parse::<DeriveInput>(item).unwrap();
+
+    // Fields marked `#[persist = false]` hold transient data such as caches.
+    // They are left out of checkpoints (the serialized state) and restored
+    // with `Default` when a checkpoint is loaded.
+    if let Data::Struct(data_struct) = &mut item.data {
+        for field in data_struct.fields.iter_mut() {
+            let mut persist = true;
+            let mut error = None;
+            field.attrs.retain(|attr| {
+                if !attr.path().is_ident("persist") {
+                    return true;
+                }
+                match persist_value(&attr.meta) {
+                    Some(value) => persist = value,
+                    None => {
+                        error = Some(syn::Error::new_spanned(
+                            attr,
+                            "expected `#[persist = true]` or `#[persist = false]`",
+                        ))
+                    }
+                }
+                false
+            });
+            if let Some(err) = error {
+                return err.to_compile_error().into();
+            }
+            if !persist {
+                field.attrs.push(syn::parse_quote!(#[serde(skip)]));
+            }
+        }
+    }
+
+    let expanded = quote! {
+        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ::serialization_macros::Serializable)]
+        #item
+    };
+    expanded.into()
+}
+
+/// The value of a `persist = <bool>` attribute.
+fn persist_value(meta: &Meta) -> Option<bool> {
+    let Meta::NameValue(name_value) = meta else {
+        return None;
+    };
+    match &name_value.value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Bool(value),
+            ..
+        }) => Some(value.value),
+        _ => None,
+    }
+}
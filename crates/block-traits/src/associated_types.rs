@@ -5,6 +5,8 @@ use serialization::Serializable;
 ///
 /// This trait defines the associated types needed for a block's input.
 /// The `Keys` type must implement `InputKeys` to provide registry integration.
+/// Inputs must be `Debug` so that traced executions (`ExecutionPlan::with_tracing`)
+/// can describe what each block read.
 ///
 /// # Examples
 ///
@@ -15,7 +17,7 @@ use serialization::Serializable;
 /// use ::serialization::structs::Serializable;
 ///
 /// // Define a simple input type
-/// #[derive(Clone)]
+/// #[derive(Clone, Debug)]
 /// struct SimpleInput {
 ///     value: i32,
 /// }
@@ -48,14 +50,23 @@ use serialization::Serializable;
 ///     type Keys = SimpleInputKeys;
 /// }
 /// ```
-pub trait BlockInput: Sized {
+pub trait BlockInput: Sized + std::fmt::Debug {
     type Keys: InputKeys<Self> + Serializable;
+
+    /// Set the fields sourced from the execution context rather than a channel.
+    ///
+    /// Called on the input read from the channels before each execution.
+    /// `#[input]` generates it for fields marked `#[from_context(...)]`; the
+    /// default does nothing.
+    fn fill_from_context<C: crate::ExecutionContextTrait>(&mut self, _context: &C) {}
 }
 
 /// Trait for block output data types.
 ///
 /// This trait defines the associated types needed for a block's output.
 /// The `Keys` type must implement `OutputKeys` to provide registry integration.
+/// Outputs must be `Debug` so that traced executions can describe what each
+/// block wrote.
 ///
 /// # Examples
 ///
@@ -66,7 +77,7 @@ pub trait BlockInput: Sized {
 /// use ::serialization::structs::Serializable;
 ///
 /// // Define a simple output type
-/// #[derive(Clone)]
+/// #[derive(Clone, Debug)]
 /// struct SimpleOutput {
 ///     result: i32,
 /// }
@@ -109,7 +120,7 @@ pub trait BlockInput: Sized {
 ///     type Keys = SimpleOutputKeys;
 /// }
 /// ```
-pub trait BlockOutput: Sized {
+pub trait BlockOutput: Sized + std::fmt::Debug {
     type Keys: OutputKeys<Self> + Serializable;
 }
 
@@ -126,6 +137,12 @@ pub trait BlockSpecAssociatedTypes {
     type State: Clone + Serializable;
     type InitParameters: Clone + ContractDeps + Serializable;
     type Intents: crate::intents::BlockIntents;
+
+    /// Stable name of the block type for logs, metrics, and registries.
+    ///
+    /// `#[block]` sets it to the struct name; override it with
+    /// `#[block(name = "...")]`.
+    const NAME: &'static str;
 }
 
 /// Type aliases for input reader and output writer for a given block spec.
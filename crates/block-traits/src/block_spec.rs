@@ -64,16 +64,81 @@ use super::*;
 ///     }
 /// }
 /// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` must implement `BlockSpec`",
+    label = "`#[block]` structs must implement `BlockSpec`",
+    note = "add `impl BlockSpec for {Self}`, or use `#[block(block_spec = false)]` to skip this check"
+)]
 pub trait BlockSpec: BlockSpecAssociatedTypes + ContractDeps {
     /// Return the ID of the block. Must be unique within an algorithm.
     fn block_id(&self) -> u32;
 
+    /// Give the block the ID `block_id`.
+    ///
+    /// Called when the block is woven, with the `block_id` configured in its
+    /// `BlockPackage`. Blocks whose ID comes from elsewhere can ignore it,
+    /// which the default does.
+    fn set_block_id(&mut self, _block_id: u32) {}
+
     /// Initialize the block's state.
     fn init_state(&self) -> Self::State;
 
     /// Create a new block instance from initialization parameters.
     fn new_from_init_params(params: &Self::InitParameters) -> Self;
 
+    /// Check initialization parameters before a block is created from them.
+    ///
+    /// Returns a description of the problem if the parameters are invalid.
+    /// The default accepts all parameters.
+    fn validate_init_params(_params: &Self::InitParameters) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Check the input keys a block is wired with against its initialization
+    /// parameters, for blocks whose parameters decide which inputs they read.
+    ///
+    /// Returns a description of the problem if they do not fit together.
+    /// Checked by `BlockPackage::new_checked` and when the block is woven, so a
+    /// miswired block is rejected rather than failing on every tick.
+    /// The default accepts all keys.
+    fn validate_input_keys(
+        _params: &Self::InitParameters,
+        _input_keys: &<Self::Input as BlockInput>::Keys,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Context capabilities the block relies on.
+    ///
+    /// Checked against the context's `capabilities` when setting up execution,
+    /// so a block that needs, say, position data fails early in a context that
+    /// cannot provide it. The default requires nothing.
+    fn required_capabilities() -> &'static [Capability] {
+        &[]
+    }
+
+    /// Whether the type-erased wrappers keep the block's last output and pass
+    /// it to `execute_with_prev_output`.
+    ///
+    /// `#[execute]` sets this for methods taking a `prev_output: Option<&Output>`
+    /// parameter. Blocks implementing `execute_with_prev_output` by hand must
+    /// set it themselves.
+    const USES_PREV_OUTPUT: bool = false;
+
+    /// Whether the type-erased wrappers run `execute_first` instead of the
+    /// usual execution until the block has executed successfully once.
+    ///
+    /// `#[execute(first)]` sets this for the method it annotates. Blocks
+    /// implementing `execute_first` by hand must set it themselves.
+    const USES_FIRST_EXECUTE: bool = false;
+
+    /// Tiebreaker for the block's place in the execution order.
+    ///
+    /// Among blocks whose inputs are all ready at the same point of the
+    /// topological sort, those with a higher priority run first. It never
+    /// overrides channel dependencies. The default is 0.
+    const WEAVE_PRIORITY: i32 = 0;
+
     /// Execute the block's logic.
     ///
     /// When the block is type-erased into a `Block` the
@@ -100,6 +165,53 @@ pub trait BlockSpec: BlockSpecAssociatedTypes + ContractDeps {
     where
         C: ExecutionContextTrait,
         E: EffectConsumerTrait;
+
+    /// Execute the block's logic with access to the output of its previous
+    /// successful execution, `None` on the first one.
+    ///
+    /// Only called by the wrappers when `USES_PREV_OUTPUT` is set, which saves
+    /// differencing and edge-detecting blocks from copying their output into
+    /// their state. The previous output is not part of a package's state, so
+    /// a block restored from a checkpoint starts over with `None`.
+    /// The default ignores `prev_output` and calls `execute`.
+    #[allow(clippy::type_complexity)]
+    fn execute_with_prev_output<C, E>(
+        &self,
+        context: &C,
+        input: Self::Input,
+        state: &Self::State,
+        _prev_output: Option<&Self::Output>,
+        effect_consumer: &mut E,
+    ) -> Result<(Self::Output, Self::State, Self::Intents), execute_status::FailureStatus>
+    where
+        C: ExecutionContextTrait,
+        E: EffectConsumerTrait,
+    {
+        self.execute(context, input, state, effect_consumer)
+    }
+
+    /// Execute the block's logic on its first execution, e.g. to warm up.
+    ///
+    /// Only called by the wrappers when `USES_FIRST_EXECUTE` is set, and only
+    /// until it succeeds; later executions go through `execute` as usual.
+    /// Blocks woven from a package with a state, such as one restored from a
+    /// checkpoint or carried over by `incremental_weave`, count as having
+    /// executed and do not run this again; see also `SyncBlock::with_state`.
+    /// The default calls `execute`.
+    #[allow(clippy::type_complexity)]
+    fn execute_first<C, E>(
+        &self,
+        context: &C,
+        input: Self::Input,
+        state: &Self::State,
+        effect_consumer: &mut E,
+    ) -> Result<(Self::Output, Self::State, Self::Intents), execute_status::FailureStatus>
+    where
+        C: ExecutionContextTrait,
+        E: EffectConsumerTrait,
+    {
+        self.execute(context, input, state, effect_consumer)
+    }
 }
 
 /// Default ContractDeps implementation for blocks without contract dependencies.
@@ -1,4 +1,5 @@
 use super::*;
+use crate::execution_plan::BlockTrace;
 use channels::{InputKeys, OutputKeys};
 use weave::EmbeddedNode;
 
@@ -11,6 +12,8 @@ pub struct BlockEmbedding<B: BlockSpec> {
     in_reader: block_keys::InReader<B>,
     out_writer: block_keys::OutWriter<B>,
     state_cell: std::cell::RefCell<B::State>,
+    prev_output: std::cell::RefCell<Option<B::Output>>,
+    executed: std::cell::RefCell<bool>,
 }
 
 impl<B: BlockSpec> BlockEmbedding<B> {
@@ -19,11 +22,17 @@ impl<B: BlockSpec> BlockEmbedding<B> {
         registry: &mut channels::ChannelRegistry,
     ) -> Result<Self, channels::RegistryError> {
         let package = package.clone();
+        B::validate_input_keys(&package.init_params, &package.input_keys)
+            .map_err(channels::RegistryError::InvalidKeys)?;
 
         let in_reader = package.input_keys.reader(registry)?;
         let out_writer = package.output_keys.writer(registry)?;
 
-        let block = B::new_from_init_params(&package.init_params);
+        let mut block = B::new_from_init_params(&package.init_params);
+        block.set_block_id(package.block_id);
+        // A package with a state comes from a block that has already run,
+        // e.g. one restored from a checkpoint, so it skips `execute_first`.
+        let executed = package.state.is_some();
         let state = match &package.state {
             Some(state) => state.clone(),
             None => block.init_state(),
@@ -36,21 +45,56 @@ impl<B: BlockSpec> BlockEmbedding<B> {
             in_reader,
             out_writer,
             state_cell,
+            prev_output: std::cell::RefCell::new(None),
+            executed: std::cell::RefCell::new(executed),
         };
 
         Ok(embedded)
     }
 
+    /// Name of the embedded block type, see `BlockSpecAssociatedTypes::NAME`.
+    pub fn name(&self) -> &'static str {
+        B::NAME
+    }
+
     pub fn extract_package(&self) -> BlockPackage<B> {
         // Take the input/output from the stored package
         // but return the current state from the state cell.
         BlockPackage {
+            block_id: self.package.block_id,
             input_keys: self.package.input_keys.clone(),
             output_keys: self.package.output_keys.clone(),
             init_params: self.package.init_params.clone(),
             state: Some(self.state_cell.borrow().clone()),
         }
     }
+
+    fn execute_with_trace<C, I, E>(
+        &self,
+        context: &C,
+        intent_consumer: &mut I,
+        effect_consumer: &mut E,
+        trace: Option<&mut BlockTrace>,
+    ) -> execute_status::ExecuteResult
+    where
+        C: ExecutionContextTrait,
+        I: IntentConsumerTrait,
+        E: EffectConsumerTrait,
+    {
+        let mut state = self.state_cell.borrow_mut();
+        execute_block(
+            &self.block,
+            self.in_reader.read(),
+            &mut state,
+            &mut self.prev_output.borrow_mut(),
+            &mut self.executed.borrow_mut(),
+            |output| self.out_writer.write(output),
+            context,
+            intent_consumer,
+            effect_consumer,
+            trace,
+        )
+    }
 }
 
 impl<B> EmbeddedNode<BlockPackage<B>> for BlockEmbedding<B>
@@ -62,6 +106,15 @@ where
     }
 }
 
+impl<B> crate::execution_plan::BlockIds for BlockEmbedding<B>
+where
+    B: BlockSpec,
+{
+    fn block_ids(&self) -> Vec<u32> {
+        vec![self.block.block_id()]
+    }
+}
+
 impl<B> ContractDeps for BlockEmbedding<B>
 where
     B: BlockSpec,
@@ -71,6 +124,15 @@ where
     }
 }
 
+impl<B> crate::execution_context::RequiredCapabilities for BlockEmbedding<B>
+where
+    B: BlockSpec,
+{
+    fn required_capabilities(&self) -> Vec<Capability> {
+        B::required_capabilities().to_vec()
+    }
+}
+
 /// Implement ExecuteTrait for BlockPackage so we can use it type-erased in execution weaves.
 impl<B, C, I, E> ExecuteTrait<C, I, E> for BlockEmbedding<B>
 where
@@ -90,22 +152,87 @@ where
         intent_consumer: &mut I,
         effect_consumer: &mut E,
     ) -> execute_status::ExecuteResult {
-        let input = self.in_reader.read();
-        let old_state = self.state_cell.borrow();
-
-        let (output, new_state, new_intents) =
-            self.block
-                .execute(context, input, &old_state, effect_consumer)?;
-        drop(old_state); // Release borrow before mutable borrow
-
-        self.out_writer.write(&output);
-        *self.state_cell.borrow_mut() = new_state;
-        for intent in new_intents.as_slice() {
-            intent_consumer.consume(intent)?;
-        }
+        self.execute_with_trace(context, intent_consumer, effect_consumer, None)
+    }
+    fn execute_traced(
+        &self,
+        context: &C,
+        intent_consumer: &mut I,
+        effect_consumer: &mut E,
+        trace: &mut BlockTrace,
+    ) -> execute_status::ExecuteResult {
+        self.execute_with_trace(context, intent_consumer, effect_consumer, Some(trace))
+    }
+}
 
-        Ok(execute_trait::execute_status::Success)
+/// The execution pipeline shared by all type-erased block wrappers.
+///
+/// Executes `block` on `input`, with its context-sourced fields filled in from
+/// `context`, and the current `state`. On success, the output
+/// is passed to `write_output`, `state` is replaced by the new state, and the
+/// intents are handed to the intent consumer, in that order. On failure,
+/// nothing is written and the state is left unchanged.
+///
+/// For blocks with `USES_PREV_OUTPUT`, `prev_output` is passed to the block
+/// and replaced by the new output on success. Otherwise it is left untouched.
+///
+/// For blocks with `USES_FIRST_EXECUTE`, `execute_first` runs while `executed`
+/// is false. `executed` is set on every success.
+///
+/// With a `trace`, the input is described once its context-sourced fields are
+/// filled in, and the output when it is written.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute_block<B, C, I, E>(
+    block: &B,
+    input: B::Input,
+    state: &mut B::State,
+    prev_output: &mut Option<B::Output>,
+    executed: &mut bool,
+    write_output: impl FnOnce(&B::Output),
+    context: &C,
+    intent_consumer: &mut I,
+    effect_consumer: &mut E,
+    mut trace: Option<&mut BlockTrace>,
+) -> execute_status::ExecuteResult
+where
+    B: BlockSpec,
+    C: ExecutionContextTrait,
+    I: IntentConsumerTrait + ?Sized,
+    E: EffectConsumerTrait,
+{
+    let mut input = input;
+    input.fill_from_context(context);
+    if let Some(trace) = trace.as_deref_mut() {
+        trace.input = Some(format!("{input:?}"));
+    }
+    let (output, new_state, new_intents) = if B::USES_FIRST_EXECUTE && !*executed {
+        block.execute_first(context, input, state, effect_consumer)?
+    } else if B::USES_PREV_OUTPUT {
+        block.execute_with_prev_output(
+            context,
+            input,
+            state,
+            prev_output.as_ref(),
+            effect_consumer,
+        )?
+    } else {
+        block.execute(context, input, state, effect_consumer)?
+    };
+
+    write_output(&output);
+    if let Some(trace) = trace {
+        trace.output = Some(format!("{output:?}"));
+    }
+    *state = new_state;
+    *executed = true;
+    if B::USES_PREV_OUTPUT {
+        *prev_output = Some(output);
     }
+    for intent in new_intents.as_slice() {
+        intent_consumer.consume(intent)?;
+    }
+
+    Ok(execute_trait::execute_status::Success)
 }
 
 #[cfg(test)]
@@ -215,7 +342,7 @@ mod tests {
         assert!(out_keys.register(&mut registry).is_ok());
 
         // Insert manual key manually as they don't support registration.
-        registry.put("in", 0i32);
+        registry.put("in", 0i32).unwrap();
 
         let package =
             BlockPackage::<TestBlock>::new(in_keys, out_keys, test_block::InitParams {}, None);
@@ -229,7 +356,7 @@ mod tests {
         let mut registry = channels::ChannelRegistry::default();
 
         // Put the input FIELD value (i32) into the channel used by InputKeys.x
-        registry.put("in", 10i32);
+        registry.put("in", 10i32).unwrap();
 
         let in_keys = input_keys("in");
         let out_keys = output_keys("out");
@@ -258,7 +385,7 @@ mod tests {
     fn block_wrapper_delegates_correctly() {
         let mut registry = channels::ChannelRegistry::default();
 
-        registry.put("in", 3i32);
+        registry.put("in", 3i32).unwrap();
 
         let in_keys = input_keys("in");
         let out_keys = output_keys("out");
@@ -280,4 +407,67 @@ mod tests {
         let out = cell.borrow();
         assert_eq!(*out, 6);
     }
+
+    #[test]
+    fn weave_before_output_registration_is_typed_error() {
+        let mut registry = channels::ChannelRegistry::default();
+        registry.put("in", 1i32).unwrap();
+
+        // Output keys are intentionally not registered.
+        let package = BlockPackage::<TestBlock>::new(
+            input_keys("in"),
+            output_keys("out"),
+            test_block::InitParams {},
+            None,
+        );
+
+        match package.weave(&mut registry) {
+            Err(err) => assert_eq!(
+                err,
+                channels::RegistryError::UnregisteredOutput("out".to_string()).in_writer("out")
+            ),
+            Ok(_) => panic!("expected weave to fail for an unregistered output"),
+        }
+    }
+
+    #[test]
+    fn register_all_outputs_before_weaving_in_any_order() {
+        let mut registry = channels::ChannelRegistry::default();
+        registry.put("src", 5i32).unwrap();
+
+        // `first` feeds `second` through "mid".
+        let first_out = output_keys("mid");
+        let second_out = output_keys("out");
+        channels::register_all(&[&first_out, &second_out], &mut registry).unwrap();
+        assert!(registry.has("mid") && registry.has("out"));
+
+        // With all outputs registered, the consumer can be woven first.
+        let second = BlockPackage::<TestBlock>::new(
+            input_keys("mid"),
+            second_out,
+            test_block::InitParams {},
+            None,
+        )
+        .weave(&mut registry)
+        .unwrap();
+        let first = BlockPackage::<TestBlock>::new(
+            input_keys("src"),
+            first_out,
+            test_block::InitParams {},
+            None,
+        )
+        .weave(&mut registry)
+        .unwrap();
+
+        let ctx = ExecutionContext { time: 0 };
+        let mut intent_consumer = |_: &Intent| {};
+        let mut effect_consumer = |_: Effect| {};
+        first
+            .execute(&ctx, &mut intent_consumer, &mut effect_consumer)
+            .unwrap();
+        second
+            .execute(&ctx, &mut intent_consumer, &mut effect_consumer)
+            .unwrap();
+        assert_eq!(*registry.get::<i32>("out").unwrap().borrow(), 20);
+    }
 }
@@ -1,7 +1,7 @@
 use super::embed::BlockEmbedding;
 
 use super::{BlockInput, BlockOutput, BlockSpec};
-use channels::{ChannelKeys, OutputKeys, RegistryError};
+use channels::{ChannelKeys, InputKeys, OutputKeys, RegistryError};
 use serde::{Deserialize, Serialize};
 use serialization_macros::Serializable;
 use weave::NodePackage;
@@ -10,8 +10,14 @@ use weave::NodePackage;
 /// before we weave it and erase its concrete type.
 /// This struct is plain-old-data and must be weaved to add
 /// functionality to a block.
+///
+/// `block_id` is the id the block gets when woven (see `BlockSpec::block_id`),
+/// stored with the rest of the configuration so it stays the same across
+/// re-weaves and reloads. It defaults to 0 when missing from a configuration.
 #[derive(Debug, Serialize, Deserialize, Serializable)]
 pub struct BlockPackage<BSpec: BlockSpec> {
+    #[serde(default)]
+    pub block_id: u32,
     pub input_keys: <BSpec::Input as BlockInput>::Keys,
     pub output_keys: <BSpec::Output as BlockOutput>::Keys,
     pub init_params: BSpec::InitParameters,
@@ -32,6 +38,7 @@ where
         state: Option<B::State>,
     ) -> Self {
         BlockPackage {
+            block_id: 0,
             input_keys,
             output_keys,
             init_params,
@@ -39,6 +46,48 @@ where
         }
     }
 
+    /// Give the block the id `block_id` when it is woven.
+    ///
+    /// Ids must be unique within a plan, which `ExecutionPlan::new_checked`
+    /// checks.
+    pub fn with_block_id(mut self, block_id: u32) -> Self {
+        self.block_id = block_id;
+        self
+    }
+
+    /// Construct a package like `new`, but validate it first.
+    ///
+    /// The input and output keys must each pass `ChannelKeys::validate`, no channel
+    /// may be both an input and an output of the block, the init parameters must
+    /// pass `BlockSpec::validate_init_params`, and the input keys must fit them
+    /// according to `BlockSpec::validate_input_keys`. This catches configuration
+    /// errors at construction rather than when the block is weaved.
+    pub fn new_checked(
+        input_keys: <B::Input as BlockInput>::Keys,
+        output_keys: <B::Output as BlockOutput>::Keys,
+        init_params: B::InitParameters,
+        state: Option<B::State>,
+    ) -> Result<Self, RegistryError> {
+        input_keys.validate()?;
+        output_keys.validate()?;
+
+        let inputs = input_keys.channel_names();
+        if let Some(shared) = output_keys
+            .channel_names()
+            .into_iter()
+            .find(|name| inputs.contains(name))
+        {
+            return Err(RegistryError::InvalidKeys(format!(
+                "channel '{shared}' is both an input and an output"
+            )));
+        }
+
+        B::validate_init_params(&init_params).map_err(RegistryError::InvalidInitParams)?;
+        B::validate_input_keys(&init_params, &input_keys).map_err(RegistryError::InvalidKeys)?;
+
+        Ok(Self::new(input_keys, output_keys, init_params, state))
+    }
+
     /// A block will register its output channels for weaving.
     pub fn register_channels(
         &self,
@@ -89,6 +138,21 @@ where
     ) -> Result<BlockEmbedding<BSpec>, RegistryError> {
         BlockPackage::<BSpec>::weave(self, channels)
     }
+    fn node_id(&self) -> Option<u32> {
+        Some(self.block_id)
+    }
+    fn register_input_defaults(
+        &self,
+        channels: &mut ::channels::ChannelRegistry,
+    ) -> Result<(), RegistryError> {
+        self.input_keys.register_defaults(channels)
+    }
+    fn emits_intents(&self) -> bool {
+        <BSpec::Intents as crate::intents::BlockIntents>::N > 0
+    }
+    fn weave_priority(&self) -> i32 {
+        BSpec::WEAVE_PRIORITY
+    }
 }
 
 impl<BSpec> Clone for BlockPackage<BSpec>
@@ -101,6 +165,7 @@ where
 {
     fn clone(&self) -> Self {
         Self {
+            block_id: self.block_id,
             input_keys: self.input_keys.clone(),
             output_keys: self.output_keys.clone(),
             init_params: self.init_params.clone(),
@@ -109,6 +174,25 @@ where
     }
 }
 
+/// Packages are equal when they describe the same configuration: the same
+/// block id, input/output keys and init parameters. The state is runtime data carried
+/// along for (de)serialization and is not part of the comparison, so a package
+/// extracted from a running block compares equal to the package it was weaved from.
+impl<BSpec> PartialEq for BlockPackage<BSpec>
+where
+    BSpec: BlockSpec,
+    <BSpec::Input as BlockInput>::Keys: PartialEq,
+    <BSpec::Output as BlockOutput>::Keys: PartialEq,
+    BSpec::InitParameters: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.block_id == other.block_id
+            && self.input_keys == other.input_keys
+            && self.output_keys == other.output_keys
+            && self.init_params == other.init_params
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,6 +238,13 @@ mod tests {
             }
         }
 
+        fn validate_init_params(params: &InitParams) -> Result<(), String> {
+            if params.multiplier == 0 {
+                return Err("multiplier must be non-zero".to_string());
+            }
+            Ok(())
+        }
+
         fn init_state(&self) -> State {
             State
         }
@@ -252,4 +343,279 @@ mod tests {
 
         assert!(res.is_err());
     }
+
+    #[test]
+    fn new_checked_accepts_consistent_package() {
+        let pkg = BlockPackage::<MultiplyBlock>::new_checked(
+            keys_in("in"),
+            keys_out("out"),
+            InitParams { multiplier: 2 },
+            None,
+        )
+        .unwrap();
+        assert_eq!(pkg.init_params.multiplier, 2);
+    }
+
+    #[test]
+    fn new_checked_rejects_same_channel_for_input_and_output() {
+        let res = BlockPackage::<MultiplyBlock>::new_checked(
+            keys_in("loop"),
+            keys_out("loop"),
+            InitParams { multiplier: 2 },
+            None,
+        );
+        assert!(matches!(res, Err(RegistryError::InvalidKeys(_))));
+    }
+
+    #[test]
+    fn new_checked_rejects_invalid_init_params() {
+        let res = BlockPackage::<MultiplyBlock>::new_checked(
+            keys_in("in"),
+            keys_out("out"),
+            InitParams { multiplier: 0 },
+            None,
+        );
+        assert_eq!(
+            res.err(),
+            Some(RegistryError::InvalidInitParams(
+                "multiplier must be non-zero".to_string()
+            ))
+        );
+    }
+
+    mod counter {
+        use super::*;
+
+        #[input]
+        pub struct Input {
+            pub tick: i32,
+        }
+
+        #[output]
+        pub struct Output {
+            pub count: i32,
+        }
+
+        #[state]
+        pub struct State {
+            pub count: i32,
+        }
+
+        #[init_params]
+        pub struct InitParams {
+            pub step: i32,
+        }
+
+        #[block]
+        pub struct CounterBlock {
+            step: i32,
+        }
+
+        impl BlockSpec for CounterBlock {
+            fn block_id(&self) -> u32 {
+                0
+            }
+
+            fn new_from_init_params(params: &InitParams) -> Self {
+                CounterBlock { step: params.step }
+            }
+
+            fn init_state(&self) -> State {
+                State { count: 0 }
+            }
+
+            #[execute]
+            fn execute(&self, input: Input, state: &State) -> (Output, State) {
+                let count = state.count + self.step * input.tick;
+                (Output { count }, State { count })
+            }
+        }
+
+        pub fn package(out: &str, step: i32) -> BlockPackage<CounterBlock> {
+            BlockPackage::new(
+                InputKeys {
+                    tick: "tick".to_string(),
+                },
+                OutputKeys {
+                    count: out.to_string(),
+                },
+                InitParams { step },
+                None,
+            )
+        }
+    }
+
+    #[test]
+    fn package_equality_ignores_state() {
+        let pkg = counter::package("a", 1);
+        let mut with_state = counter::package("a", 1);
+        with_state.state = Some(counter::State { count: 5 });
+
+        assert!(pkg == with_state);
+        assert!(pkg != counter::package("a", 2));
+        assert!(pkg != counter::package("b", 1));
+    }
+
+    #[test]
+    fn incremental_weave_preserves_state_of_unchanged_blocks() {
+        use crate::{Effect, ExecuteTrait, Intent};
+        use weave::{incremental_weave, weave_nodes};
+
+        struct Ctx;
+        impl crate::ExecutionContextTrait for Ctx {
+            type OrderBook = OrderBook;
+            fn time(&self) -> u64 {
+                0
+            }
+            fn get_order_book(&self, _: &trade_types::Contract) -> Option<OrderBook> {
+                None
+            }
+            fn get_position(
+                &self,
+                _: u32,
+                _: &trade_types::Contract,
+            ) -> Option<trade_types::Quantity> {
+                None
+            }
+        }
+        struct OrderBook;
+        impl crate::execution_context::OrderBookTrait for OrderBook {
+            fn top_of_side(&self, _: trade_types::Side) -> Option<trade_types::Price> {
+                None
+            }
+        }
+
+        let mut registry = ::channels::ChannelRegistry::default();
+        registry.put("tick", 1i32).unwrap();
+
+        let packages = vec![counter::package("a", 1), counter::package("b", 10)];
+        let woven = weave_nodes(&packages, &mut registry).unwrap();
+        for _ in 0..3 {
+            for block in woven.iter() {
+                block
+                    .execute(&Ctx, &mut |_: &Intent| {}, &mut |_: Effect| {})
+                    .unwrap();
+            }
+        }
+
+        // Change the step of the second counter only.
+        let new_packages = vec![counter::package("a", 1), counter::package("b", 20)];
+        let rewoven = incremental_weave(&woven, &new_packages, &mut registry).unwrap();
+
+        let states: Vec<i32> = rewoven
+            .iter()
+            .map(|e| e.extract_package().state.unwrap().count)
+            .collect();
+        assert_eq!(states, vec![3, 0]);
+    }
+
+    #[test]
+    fn incremental_weave_removes_orphaned_output_channels() {
+        use weave::{incremental_weave, weave_nodes};
+
+        let mut registry = ::channels::ChannelRegistry::default();
+        registry.put("tick", 1i32).unwrap();
+
+        // The counter writing "tick" feeds the others; nothing reads "c".
+        let packages = vec![
+            counter::package("a", 1),
+            counter::package("tick", 1),
+            counter::package("c", 1),
+        ];
+        let woven = weave_nodes(&packages, &mut registry).unwrap();
+
+        let new_packages = vec![counter::package("a", 1)];
+        incremental_weave(&woven, &new_packages, &mut registry).unwrap();
+
+        assert!(registry.has("a"));
+        assert!(registry.has("tick"));
+        assert!(!registry.has("c"));
+    }
+
+    #[test]
+    fn incremental_weave_keeps_user_registered_output_channels() {
+        use weave::{incremental_weave, weave_nodes};
+
+        let mut registry = ::channels::ChannelRegistry::default();
+        registry.put("tick", 1i32).unwrap();
+        // Registered up front, e.g. to be serialized or read outside the weave.
+        registry.ensure_serializable::<i32>("c").unwrap();
+
+        let packages = vec![counter::package("a", 1), counter::package("c", 1)];
+        let woven = weave_nodes(&packages, &mut registry).unwrap();
+        assert!(registry.is_woven_output("a"));
+        assert!(!registry.is_woven_output("c"));
+
+        incremental_weave(&woven, &[counter::package("a", 1)], &mut registry).unwrap();
+
+        assert!(registry.has("c"));
+    }
+
+    #[test]
+    fn incremental_weave_can_be_retried_after_a_failure() {
+        use weave::{incremental_weave, weave_nodes};
+
+        let mut registry = ::channels::ChannelRegistry::default();
+        registry.put("tick", 1i32).unwrap();
+        registry.put("label", "not a number".to_string()).unwrap();
+        registry.ensure::<i32>("manual").unwrap();
+        registry.get_output::<i32>("manual").unwrap();
+
+        let woven = weave_nodes(&[counter::package("a", 1)], &mut registry).unwrap();
+
+        // "c" gets a writer before the block reading a string as its tick fails.
+        let mut bad = counter::package("b", 1);
+        bad.input_keys.tick = "label".to_string();
+        let failing = vec![counter::package("c", 1), bad];
+        assert!(incremental_weave(&woven, &failing, &mut registry).is_err());
+
+        let retry = vec![counter::package("c", 1), counter::package("b", 1)];
+        assert!(incremental_weave(&woven, &retry, &mut registry).is_ok());
+        // Writers of channels the old blocks did not write are kept.
+        assert_eq!(
+            registry.get_output::<i32>("manual").err(),
+            Some(RegistryError::DuplicateWriter("manual".to_string()))
+        );
+    }
+
+    #[test]
+    fn fork_copies_woven_output_channels() {
+        use weave::weave_nodes;
+
+        let mut registry = ::channels::ChannelRegistry::default();
+        registry.put_cloneable("tick", 1i32).unwrap();
+        let packages = vec![counter::package("a", 1)];
+        weave_nodes(&packages, &mut registry).unwrap();
+        *registry.get::<i32>("a").unwrap().borrow_mut() = 3;
+
+        let fork = registry.fork();
+        assert!(fork.skipped_on_fork().is_empty());
+        *fork.get::<i32>("a").unwrap().borrow_mut() = 5;
+        assert_eq!(*registry.get::<i32>("a").unwrap().borrow(), 3);
+    }
+
+    #[test]
+    fn dump_includes_woven_outputs_registered_as_serializable() {
+        use crate::{Effect, ExecuteTrait, Intent};
+        use weave::weave_nodes;
+
+        let mut registry = ::channels::ChannelRegistry::default();
+        registry.put("tick", 2i32).unwrap();
+        registry.ensure_serializable::<i32>("a").unwrap();
+        let packages = vec![counter::package("a", 1), counter::package("b", 1)];
+        let woven = weave_nodes(&packages, &mut registry).unwrap();
+
+        let context = crate::execution_context::MockExecutionContext::new();
+        for block in woven.iter() {
+            block
+                .execute(&context, &mut |_: &Intent| {}, &mut |_: Effect| {})
+                .unwrap();
+        }
+
+        // Only the pre-registered output is part of the snapshot.
+        let dump = registry.dump_serializable();
+        let dump = dump.as_object().unwrap();
+        assert_eq!(dump.keys().collect::<Vec<_>>(), vec!["a"]);
+        assert_eq!(dump["a"].as_i64(), Some(2));
+    }
 }
@@ -1,8 +1,14 @@
+use trade_types::Contract;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Effect {
     Suspend,
     Terminate,
     Timer(u64),
+    /// Start receiving ticks for a contract in addition to the block's contract dependencies.
+    Subscribe(Contract),
+    /// Stop receiving ticks for a contract.
+    Unsubscribe(Contract),
 }
 
 impl Effect {
@@ -17,4 +23,12 @@ impl Effect {
     pub fn timer(duration: u64) -> Self {
         Effect::Timer(duration)
     }
+
+    pub fn subscribe(contract: Contract) -> Self {
+        Effect::Subscribe(contract)
+    }
+
+    pub fn unsubscribe(contract: Contract) -> Self {
+        Effect::Unsubscribe(contract)
+    }
 }
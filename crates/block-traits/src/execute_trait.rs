@@ -25,6 +25,18 @@ pub trait EffectConsumerTrait {
     ) -> Result<(), execute_status::FailureStatus> {
         self.schedule_effect(Effect::Timer(new_time))
     }
+    fn schedule_subscribe_effect(
+        &mut self,
+        contract: trade_types::Contract,
+    ) -> Result<(), execute_status::FailureStatus> {
+        self.schedule_effect(Effect::Subscribe(contract))
+    }
+    fn schedule_unsubscribe_effect(
+        &mut self,
+        contract: trade_types::Contract,
+    ) -> Result<(), execute_status::FailureStatus> {
+        self.schedule_effect(Effect::Unsubscribe(contract))
+    }
 }
 
 /// An IntentConsumerTrait implementation that wraps a closure which may fail.
@@ -83,11 +95,28 @@ pub mod execute_status {
     }
     pub use SuccessStatus::Success;
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
     pub enum FailureStatus {
         Failure,
+        /// A failure with a short description of its cause, e.g. `"deadline"`.
+        Reason(String),
     }
     pub use FailureStatus::Failure;
+
+    impl FailureStatus {
+        /// Create a failure carrying the given reason.
+        pub fn with_reason(reason: impl Into<String>) -> Self {
+            FailureStatus::Reason(reason.into())
+        }
+
+        /// The reason for the failure, if one was given.
+        pub fn reason(&self) -> Option<&str> {
+            match self {
+                FailureStatus::Failure => None,
+                FailureStatus::Reason(reason) => Some(reason),
+            }
+        }
+    }
     pub type ExecuteResult = std::result::Result<SuccessStatus, FailureStatus>;
 }
 
@@ -103,7 +132,12 @@ where
     /// Number of intents produced by the execution.
     /// This should be a constant once the execution trait is instantiated, but since we can build
     /// algorithms dynamically it is not possible to enforce this at compile time.
-    fn num_intents(&self) -> usize;
+    ///
+    /// Defaults to zero, so side-effect-only algorithms need not implement it.
+    /// Algorithms that emit intents must override it.
+    fn num_intents(&self) -> usize {
+        0
+    }
     /// Execute the block in the given execution context, producing intents consumed by the intent consumer.
     fn execute(
         &self,
@@ -111,4 +145,18 @@ where
         intent_consumer: &mut IntentConsumer,
         effect_consumer: &mut EffectConsumer,
     ) -> execute_status::ExecuteResult;
+
+    /// Execute like `execute`, describing the block's input and output in `trace`.
+    ///
+    /// Used by execution plans with tracing enabled. The default executes
+    /// without describing anything.
+    fn execute_traced(
+        &self,
+        context: &ExeContext,
+        intent_consumer: &mut IntentConsumer,
+        effect_consumer: &mut EffectConsumer,
+        _trace: &mut crate::execution_plan::BlockTrace,
+    ) -> execute_status::ExecuteResult {
+        self.execute(context, intent_consumer, effect_consumer)
+    }
 }
@@ -0,0 +1,39 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Untyped values a block keeps between ticks outside its typed `State`.
+pub type ScratchMap = HashMap<String, Box<dyn Any>>;
+
+/// Shared handle to the scratch space of one block.
+pub type Scratch = Rc<RefCell<ScratchMap>>;
+
+/// Per-block scratch spaces, keyed by block id.
+///
+/// The blackboard is separate from the channel registry: blocks cannot see
+/// each other's scratch space unless they share a block id, and nothing in it
+/// is part of the woven graph or its serialized state. Cloning the blackboard
+/// gives another handle to the same storage, so an owner that outlives single
+/// ticks (e.g. a controller) can hand it to each new execution context.
+///
+/// Block ids are only unique within a plan, so independent plans, such as
+/// the algorithms of different actors, should each get their own blackboard.
+#[derive(Clone, Default)]
+pub struct Blackboard(Rc<RefCell<HashMap<u32, Scratch>>>);
+
+impl Blackboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The scratch space of the block with the given id, created empty on first use.
+    pub fn scratch(&self, block_id: u32) -> Scratch {
+        self.0.borrow_mut().entry(block_id).or_default().clone()
+    }
+
+    /// Drop the scratch space of a block, e.g. when the block is removed.
+    pub fn clear(&self, block_id: u32) {
+        self.0.borrow_mut().remove(&block_id);
+    }
+}
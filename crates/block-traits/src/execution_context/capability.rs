@@ -0,0 +1,58 @@
+use super::ExecutionContextTrait;
+
+/// Data an execution context can provide to blocks.
+///
+/// Contexts advertise what they provide with `ExecutionContextTrait::capabilities`,
+/// and blocks declare what they need with `BlockSpec::required_capabilities`.
+/// A context without a capability typically answers the corresponding calls
+/// with `None`, so checking the two against each other up front catches
+/// blocks that would otherwise silently misbehave.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// `get_order_book` returns the contract's order book.
+    OrderBook,
+    /// `get_position` returns the block's position in the contract.
+    Position,
+    /// `scratch` keeps the block's scratch space across ticks.
+    Scratch,
+}
+
+/// The capabilities required by an executable item.
+///
+/// Composite items report the capabilities required by all the blocks they
+/// contain, possibly with repetitions.
+pub trait RequiredCapabilities {
+    fn required_capabilities(&self) -> Vec<Capability>;
+}
+
+/// Error returned when an item requires a capability the context lacks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingCapability(pub Capability);
+
+impl std::fmt::Display for MissingCapability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Capability {:?} is required but not provided by the execution context",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for MissingCapability {}
+
+/// Check that contexts of type `C` provide all the capabilities `item` requires.
+pub fn check_capabilities<C>(item: &impl RequiredCapabilities) -> Result<(), MissingCapability>
+where
+    C: ExecutionContextTrait,
+{
+    let provided = C::capabilities();
+    match item
+        .required_capabilities()
+        .into_iter()
+        .find(|capability| !provided.contains(capability))
+    {
+        Some(capability) => Err(MissingCapability(capability)),
+        None => Ok(()),
+    }
+}
@@ -1,10 +1,78 @@
+use super::blackboard::Scratch;
+use super::capability::Capability;
 use super::order_book::OrderBookTrait;
+use super::shared::SharedResources;
+use crate::execute_trait::execute_status;
+use std::time::Instant;
 use trade_types::*;
 
+/// Reason given by `check_deadline` when the execution deadline has passed.
+pub const DEADLINE_EXCEEDED: &str = "deadline";
+
 pub trait ExecutionContextTrait {
     type OrderBook: OrderBookTrait;
 
     fn time(&self) -> u64;
     fn get_order_book(&self, contract: &Contract) -> Option<Self::OrderBook>;
     fn get_position(&self, block_id: u32, contract: &Contract) -> Option<Quantity>;
+
+    /// Time passed since `start`, or 0 if `start` lies in the future.
+    fn elapsed_since(&self, start: u64) -> u64 {
+        self.time().saturating_sub(start)
+    }
+
+    /// The capabilities contexts of this type provide.
+    ///
+    /// Checked against the blocks' `required_capabilities` when setting up
+    /// execution, see `check_capabilities`. The default advertises nothing.
+    fn capabilities() -> &'static [Capability]
+    where
+        Self: Sized,
+    {
+        &[]
+    }
+
+    /// Scratch space for the block with the given id.
+    ///
+    /// Blocks can keep untyped data here, such as cached computations, that is
+    /// not part of their `State`. Contexts backed by a `Blackboard` keep the
+    /// scratch space across ticks. The default returns a fresh, empty space on
+    /// every call, so nothing is kept.
+    fn scratch(&self, _block_id: u32) -> Scratch {
+        Scratch::default()
+    }
+
+    /// Resources shared by the blocks of a plan across ticks, if the context
+    /// provides them. See `ExecutionPlan::execute_with_shared`.
+    fn shared(&self) -> Option<&SharedResources> {
+        None
+    }
+
+    /// Optional wall-clock deadline for the current execution.
+    ///
+    /// Deadlines are cooperative: nothing preempts a block, but long-running
+    /// blocks should check the deadline (see `check_deadline`) and give up
+    /// once it has passed. Contexts without a deadline return `None`.
+    fn deadline(&self) -> Option<Instant> {
+        None
+    }
+
+    /// Whether the execution deadline, if any, has passed.
+    fn deadline_exceeded(&self) -> bool {
+        self.deadline()
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Fail with `FailureStatus::with_reason("deadline")` if the deadline has passed.
+    ///
+    /// Intended for use with `?` inside block `execute` methods.
+    fn check_deadline(&self) -> Result<(), execute_status::FailureStatus> {
+        if self.deadline_exceeded() {
+            Err(execute_status::FailureStatus::with_reason(
+                DEADLINE_EXCEEDED,
+            ))
+        } else {
+            Ok(())
+        }
+    }
 }
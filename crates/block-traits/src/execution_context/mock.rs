@@ -0,0 +1,86 @@
+use super::capability::Capability;
+use super::exe_ctx::ExecutionContextTrait;
+use super::order_book::OrderBookTrait;
+use std::collections::HashMap;
+use trade_types::*;
+
+/// A configurable `ExecutionContextTrait` implementation for tests.
+///
+/// Starts at time 0 with no order books and no positions; use the `with_*`
+/// methods to set up what a test needs.
+///
+/// ```rust
+/// use block_traits::execution_context::{MockExecutionContext, OrderBookTrait};
+/// use block_traits::ExecutionContextTrait;
+/// use trade_types::{Cents, Contract, Price, Side};
+///
+/// let contract = Contract::new("DE-BASE");
+/// let context = MockExecutionContext::new()
+///     .with_time(42)
+///     .with_order_book(contract.clone(), Price::from(Cents(100)));
+///
+/// assert_eq!(context.time(), 42);
+/// let book = context.get_order_book(&contract).unwrap();
+/// assert_eq!(book.top_of_side(Side::Buy), Some(Price::from(Cents(100))));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MockExecutionContext {
+    time: u64,
+    order_books: HashMap<Contract, Price>,
+    positions: HashMap<(u32, Contract), Quantity>,
+}
+
+impl MockExecutionContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the time reported by the context.
+    pub fn with_time(mut self, time: u64) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Give `contract` an order book whose top of both sides is `price`.
+    pub fn with_order_book(mut self, contract: Contract, price: Price) -> Self {
+        self.order_books.insert(contract, price);
+        self
+    }
+
+    /// Give the block `block_id` a position of `quantity` in `contract`.
+    pub fn with_position(mut self, block_id: u32, contract: Contract, quantity: Quantity) -> Self {
+        self.positions.insert((block_id, contract), quantity);
+        self
+    }
+}
+
+/// Order book of a `MockExecutionContext`, with the same top price on both sides.
+#[derive(Clone, Debug)]
+pub struct MockOrderBook {
+    price: Price,
+}
+
+impl OrderBookTrait for MockOrderBook {
+    fn top_of_side(&self, _side: Side) -> Option<Price> {
+        Some(self.price.clone())
+    }
+}
+
+impl ExecutionContextTrait for MockExecutionContext {
+    type OrderBook = MockOrderBook;
+
+    fn time(&self) -> u64 {
+        self.time
+    }
+    fn get_order_book(&self, contract: &Contract) -> Option<MockOrderBook> {
+        let price = self.order_books.get(contract)?.clone();
+        Some(MockOrderBook { price })
+    }
+    fn get_position(&self, block_id: u32, contract: &Contract) -> Option<Quantity> {
+        self.positions.get(&(block_id, contract.clone())).cloned()
+    }
+    fn capabilities() -> &'static [Capability] {
+        // Scratch space is the default one, which keeps nothing across ticks.
+        &[Capability::OrderBook, Capability::Position]
+    }
+}
@@ -1,5 +1,15 @@
+pub mod blackboard;
+pub mod capability;
 pub mod exe_ctx;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
 pub mod order_book;
+pub mod shared;
 
-pub use exe_ctx::ExecutionContextTrait;
+pub use blackboard::{Blackboard, Scratch, ScratchMap};
+pub use capability::{check_capabilities, Capability, MissingCapability, RequiredCapabilities};
+pub use exe_ctx::{ExecutionContextTrait, DEADLINE_EXCEEDED};
+#[cfg(any(test, feature = "test-util"))]
+pub use mock::{MockExecutionContext, MockOrderBook};
 pub use order_book::OrderBookTrait;
+pub use shared::{SharedContext, SharedResources};
@@ -0,0 +1,77 @@
+use super::blackboard::Scratch;
+use super::capability::Capability;
+use super::exe_ctx::ExecutionContextTrait;
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Instant;
+use trade_types::*;
+
+/// Resources such as a connection or a cache that the blocks of an
+/// `ExecutionPlan` share across ticks.
+///
+/// Resources are keyed by their type, so there is at most one of each type.
+/// Cloning gives another handle to the same storage, as for `Blackboard`.
+#[derive(Clone, Default)]
+pub struct SharedResources(Rc<RefCell<HashMap<TypeId, Rc<dyn Any>>>>);
+
+impl SharedResources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a resource, replacing any earlier resource of the same type.
+    pub fn insert<T: 'static>(&self, value: T) {
+        self.0
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), Rc::new(RefCell::new(value)));
+    }
+
+    /// The resource of type `T`, if one was added.
+    pub fn get<T: 'static>(&self) -> Option<Rc<RefCell<T>>> {
+        let resource = self.0.borrow().get(&TypeId::of::<T>())?.clone();
+        resource.downcast::<RefCell<T>>().ok()
+    }
+}
+
+/// Context that gives blocks access to shared resources on top of another context.
+///
+/// Everything but `shared` is answered by the wrapped context.
+/// `ExecutionPlan::execute_with_shared` runs its blocks in this context.
+pub struct SharedContext<'c, C> {
+    inner: &'c C,
+    shared: SharedResources,
+}
+
+impl<'c, C> SharedContext<'c, C> {
+    pub fn new(inner: &'c C, shared: SharedResources) -> Self {
+        Self { inner, shared }
+    }
+}
+
+impl<C: ExecutionContextTrait> ExecutionContextTrait for SharedContext<'_, C> {
+    type OrderBook = C::OrderBook;
+
+    fn time(&self) -> u64 {
+        self.inner.time()
+    }
+    fn get_order_book(&self, contract: &Contract) -> Option<Self::OrderBook> {
+        self.inner.get_order_book(contract)
+    }
+    fn get_position(&self, block_id: u32, contract: &Contract) -> Option<Quantity> {
+        self.inner.get_position(block_id, contract)
+    }
+    fn capabilities() -> &'static [Capability] {
+        C::capabilities()
+    }
+    fn scratch(&self, block_id: u32) -> Scratch {
+        self.inner.scratch(block_id)
+    }
+    fn deadline(&self) -> Option<Instant> {
+        self.inner.deadline()
+    }
+    fn shared(&self) -> Option<&SharedResources> {
+        Some(&self.shared)
+    }
+}
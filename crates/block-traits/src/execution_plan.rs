@@ -1,8 +1,15 @@
+use crate::execution_context::{
+    check_capabilities, Capability, MissingCapability, RequiredCapabilities, SharedContext,
+    SharedResources,
+};
 use crate::{
     execute_trait::execute_status, ContractDeps, EffectConsumerTrait, ExecuteTrait,
-    ExecutionContextTrait, IntentConsumerTrait,
+    ExecutionContextTrait, Intent, IntentConsumerTrait,
 };
+use ::channels::DerivedChannels;
 use ::weave::TopoOrdered;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 
 impl<CD> ContractDeps for TopoOrdered<CD>
 where
@@ -45,3 +52,469 @@ where
         Ok(execute_status::Success)
     }
 }
+
+/// An execution plan where blocks can run at a lower cadence than the plan.
+///
+/// A block with cadence `n` is executed on every `n`th tick of the plan (ticks
+/// 0, n, 2n, ...) and skipped on the ticks in between. A skipped block does not
+/// write its outputs, so its output channels keep the value from its last
+/// execution, and the intents it emitted on its last execution are emitted
+/// again so the intent slots of the following blocks do not shift.
+///
+/// Blocks without an entry in the cadence map run on every tick.
+///
+/// With `with_intent_dedup`, a `Place` intent identical to one already emitted
+/// in the same tick is replaced by `NoIntent`, so overlapping blocks do not
+/// waste reconciliation slots on duplicate orders.
+///
+/// With `with_tracing`, each tick records a `TickTrace` of what every block
+/// read, wrote, and emitted, available from `last_trace`.
+///
+/// With `with_derived_channels`, derived channels are recomputed before every
+/// block executes, so blocks reading them see their dependencies' current
+/// values, including those written earlier in the same tick.
+pub struct ExecutionPlan<X> {
+    blocks: TopoOrdered<X>,
+    cadence: Vec<u64>,
+    tick: Cell<u64>,
+    last_intents: RefCell<Vec<Vec<Intent>>>,
+    dedup_intents: bool,
+    tracing: bool,
+    last_trace: RefCell<Option<TickTrace>>,
+    shared: SharedResources,
+    derived: Option<DerivedChannels>,
+}
+
+/// What one block of an `ExecutionPlan` did in a traced tick.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BlockTrace {
+    /// Whether the block executed, or was skipped by its cadence.
+    pub executed: bool,
+    /// The block's input, as read from its input channels and completed from
+    /// the execution context (`Debug` formatted).
+    /// `None` for skipped blocks and blocks that do not describe their input.
+    pub input: Option<String>,
+    /// The output the block wrote to its output channels (`Debug` formatted).
+    /// `None` if nothing was written, e.g. because the block failed.
+    pub output: Option<String>,
+    /// The intents the block emitted, or, for skipped blocks, repeated.
+    pub intents: Vec<Intent>,
+}
+
+/// Trace of one tick of an `ExecutionPlan`, with one entry per block in
+/// topological order.
+///
+/// If a block fails, the trace ends with that block's entry.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TickTrace {
+    pub tick: u64,
+    pub blocks: Vec<BlockTrace>,
+}
+
+impl<X> ExecutionPlan<X> {
+    /// Create a plan from topologically ordered blocks and a map from block
+    /// index (in topological order) to the block's cadence. A cadence of zero
+    /// is treated as one.
+    pub fn new(blocks: TopoOrdered<X>, cadence: HashMap<usize, u64>) -> Self {
+        let cadence = (0..blocks.len())
+            .map(|idx| cadence.get(&idx).copied().unwrap_or(1).max(1))
+            .collect();
+        let last_intents = RefCell::new(vec![Vec::new(); blocks.len()]);
+        Self {
+            blocks,
+            cadence,
+            tick: Cell::new(0),
+            last_intents,
+            dedup_intents: false,
+            tracing: false,
+            last_trace: RefCell::new(None),
+            shared: SharedResources::new(),
+            derived: None,
+        }
+    }
+
+    /// Record a `TickTrace` of every tick, see `last_trace`.
+    ///
+    /// Tracing formats every block's input and output, so it is meant for
+    /// debugging rather than production runs.
+    pub fn with_tracing(mut self) -> Self {
+        self.tracing = true;
+        self
+    }
+
+    /// The trace of the last tick, if tracing is enabled and a tick has run.
+    pub fn last_trace(&self) -> Option<TickTrace> {
+        self.last_trace.borrow().clone()
+    }
+
+    /// Recompute `derived` before every block executes, see
+    /// `ChannelRegistry::derived_channels`.
+    ///
+    /// Woven blocks hold on to the channels they read, so without this they
+    /// see derived channels as they were when last refreshed.
+    pub fn with_derived_channels(mut self, derived: DerivedChannels) -> Self {
+        self.derived = Some(derived);
+        self
+    }
+
+    /// Replace duplicate `Place` intents within a tick by `NoIntent`.
+    ///
+    /// The first occurrence is kept. Duplicates become `NoIntent` rather than
+    /// being dropped, so the intent slots of later blocks do not shift.
+    pub fn with_intent_dedup(mut self) -> Self {
+        self.dedup_intents = true;
+        self
+    }
+
+    /// Share `shared` with the blocks in `execute_with_shared`, e.g. to hand
+    /// several plans the same resources.
+    pub fn with_shared(mut self, shared: SharedResources) -> Self {
+        self.shared = shared;
+        self
+    }
+
+    /// The resources the plan shares with its blocks across ticks.
+    pub fn shared(&self) -> &SharedResources {
+        &self.shared
+    }
+
+    /// The blocks of the plan in topological order.
+    pub fn blocks(&self) -> &TopoOrdered<X> {
+        &self.blocks
+    }
+
+    /// The cadence of the block at the given index.
+    pub fn cadence(&self, idx: usize) -> u64 {
+        self.cadence[idx]
+    }
+
+    /// Number of ticks executed so far.
+    pub fn tick(&self) -> u64 {
+        self.tick.get()
+    }
+}
+
+impl<X> ExecutionPlan<X>
+where
+    X: BlockIds,
+{
+    /// Like `new`, but reject plans where two blocks share a block id.
+    pub fn new_checked(
+        blocks: TopoOrdered<X>,
+        cadence: HashMap<usize, u64>,
+    ) -> Result<Self, DuplicateBlockId> {
+        let plan = Self::new(blocks, cadence);
+        plan.validate()?;
+        Ok(plan)
+    }
+
+    /// Check that no two blocks in the plan share a block id.
+    ///
+    /// Intent slots are tied to block ids, so blocks sharing an id would have
+    /// their intents collide during reconciliation.
+    ///
+    /// Woven blocks get the ids configured in their packages, see
+    /// `BlockPackage::block_id`.
+    pub fn validate(&self) -> Result<(), DuplicateBlockId> {
+        let mut seen = std::collections::HashSet::new();
+        match self.block_ids().into_iter().find(|id| !seen.insert(*id)) {
+            Some(id) => Err(DuplicateBlockId(id)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<X> From<TopoOrdered<X>> for ExecutionPlan<X> {
+    fn from(blocks: TopoOrdered<X>) -> Self {
+        Self::new(blocks, HashMap::new())
+    }
+}
+
+impl<X> ContractDeps for ExecutionPlan<X>
+where
+    X: ContractDeps,
+{
+    fn contract_deps(&self) -> Vec<::trade_types::Contract> {
+        self.blocks.contract_deps()
+    }
+}
+
+/// The ids of the blocks in an executable item, for validating plans.
+///
+/// Composite items report the ids of all the blocks they contain.
+pub trait BlockIds {
+    fn block_ids(&self) -> Vec<u32>;
+}
+
+impl<X> BlockIds for TopoOrdered<X>
+where
+    X: BlockIds,
+{
+    fn block_ids(&self) -> Vec<u32> {
+        self.iter().flat_map(|block| block.block_ids()).collect()
+    }
+}
+
+impl<X> BlockIds for ExecutionPlan<X>
+where
+    X: BlockIds,
+{
+    fn block_ids(&self) -> Vec<u32> {
+        self.blocks.block_ids()
+    }
+}
+
+impl<X> RequiredCapabilities for TopoOrdered<X>
+where
+    X: RequiredCapabilities,
+{
+    fn required_capabilities(&self) -> Vec<Capability> {
+        self.iter()
+            .flat_map(|block| block.required_capabilities())
+            .collect()
+    }
+}
+
+impl<X> RequiredCapabilities for ExecutionPlan<X>
+where
+    X: RequiredCapabilities,
+{
+    fn required_capabilities(&self) -> Vec<Capability> {
+        self.blocks.required_capabilities()
+    }
+}
+
+impl<X> ExecutionPlan<X>
+where
+    X: RequiredCapabilities,
+{
+    /// Check that contexts of type `C` provide the capabilities all blocks in
+    /// the plan require, before the plan is executed in them.
+    pub fn check_capabilities<C>(&self) -> Result<(), MissingCapability>
+    where
+        C: ExecutionContextTrait,
+    {
+        check_capabilities::<C>(self)
+    }
+}
+
+/// Error returned when two blocks in a plan share a block id.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateBlockId(pub u32);
+
+impl std::fmt::Display for DuplicateBlockId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Block id {} is used by more than one block in the plan",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for DuplicateBlockId {}
+
+/// The `Place` intents emitted so far in a tick, for deduplication.
+/// `None` when the plan does not deduplicate.
+struct Emitted(Option<Vec<Intent>>);
+
+impl Emitted {
+    /// Forward `intent`, or `NoIntent` if it duplicates an earlier one.
+    fn forward<I>(
+        &mut self,
+        inner: &mut I,
+        intent: &Intent,
+    ) -> Result<(), execute_status::FailureStatus>
+    where
+        I: IntentConsumerTrait + ?Sized,
+    {
+        match &mut self.0 {
+            Some(seen) if matches!(intent, Intent::Place { .. }) => {
+                if seen.contains(intent) {
+                    inner.consume(&Intent::NoIntent)
+                } else {
+                    seen.push(intent.clone());
+                    inner.consume(intent)
+                }
+            }
+            _ => inner.consume(intent),
+        }
+    }
+}
+
+/// Intent consumer that forwards intents while keeping a copy of them.
+///
+/// Execution plans hand this to their blocks, so blocks in a plan must be
+/// executable with it.
+pub struct RecordingConsumer<'a, I: ?Sized> {
+    inner: &'a mut I,
+    record: &'a mut Vec<Intent>,
+    emitted: &'a mut Emitted,
+}
+
+impl<I> IntentConsumerTrait for RecordingConsumer<'_, I>
+where
+    I: IntentConsumerTrait + ?Sized,
+{
+    fn consume(&mut self, intent: &Intent) -> Result<(), execute_status::FailureStatus> {
+        self.record.push(intent.clone());
+        self.emitted.forward(self.inner, intent)
+    }
+}
+
+impl<C, X, I, E> ExecuteTrait<C, I, E> for ExecutionPlan<X>
+where
+    C: ExecutionContextTrait,
+    X: for<'a> ExecuteTrait<C, RecordingConsumer<'a, I>, E>,
+    I: IntentConsumerTrait,
+    E: EffectConsumerTrait,
+{
+    fn num_intents(&self) -> usize {
+        self.blocks.iter().map(|block| block.num_intents()).sum()
+    }
+
+    fn execute(
+        &self,
+        context: &C,
+        intent_consumer: &mut I,
+        effect_consumer: &mut E,
+    ) -> execute_status::ExecuteResult {
+        let tick = self.tick.get();
+        self.tick.set(tick + 1);
+        self.run(context, intent_consumer, effect_consumer, tick, |idx| {
+            tick.is_multiple_of(self.cadence[idx])
+        })
+    }
+}
+
+impl<X> ExecutionPlan<X> {
+    /// Execute one tick like `execute`, running the blocks in a context that
+    /// also provides the plan's shared resources through
+    /// `ExecutionContextTrait::shared`.
+    pub fn execute_with_shared<'c, C, I, E>(
+        &self,
+        context: &'c C,
+        intent_consumer: &mut I,
+        effect_consumer: &mut E,
+    ) -> execute_status::ExecuteResult
+    where
+        C: ExecutionContextTrait,
+        X: for<'a> ExecuteTrait<SharedContext<'c, C>, RecordingConsumer<'a, I>, E>,
+        I: IntentConsumerTrait,
+        E: EffectConsumerTrait,
+    {
+        let context = SharedContext::new(context, self.shared.clone());
+        let tick = self.tick.get();
+        self.tick.set(tick + 1);
+        self.run(&context, intent_consumer, effect_consumer, tick, |idx| {
+            tick.is_multiple_of(self.cadence[idx])
+        })
+    }
+
+    /// Execute only the blocks from the one with block id `start_block_id`
+    /// onward in topological order, for debugging and recomputing downstream
+    /// blocks.
+    ///
+    /// The blocks before the start are not executed: their output channels keep
+    /// their values, which the executed blocks read as they are, and their last
+    /// intents are repeated as for blocks skipped by their cadence. The blocks
+    /// from the start execute regardless of cadence. The plan's tick is not
+    /// advanced.
+    ///
+    /// Fails with a reason if no block in the plan has the id.
+    pub fn execute_from<C, I, E>(
+        &self,
+        context: &C,
+        intent_consumer: &mut I,
+        effect_consumer: &mut E,
+        start_block_id: u32,
+    ) -> execute_status::ExecuteResult
+    where
+        C: ExecutionContextTrait,
+        X: for<'a> ExecuteTrait<C, RecordingConsumer<'a, I>, E> + BlockIds,
+        I: IntentConsumerTrait,
+        E: EffectConsumerTrait,
+    {
+        let start = self
+            .blocks
+            .iter()
+            .position(|block| block.block_ids().contains(&start_block_id))
+            .ok_or_else(|| {
+                execute_status::FailureStatus::with_reason(format!(
+                    "unknown block id {start_block_id}"
+                ))
+            })?;
+        let tick = self.tick.get();
+        self.run(context, intent_consumer, effect_consumer, tick, |idx| {
+            idx >= start
+        })
+    }
+
+    /// Run one tick, executing the blocks selected by `runs` and repeating the
+    /// last intents of the others.
+    fn run<C, I, E>(
+        &self,
+        context: &C,
+        intent_consumer: &mut I,
+        effect_consumer: &mut E,
+        tick: u64,
+        runs: impl Fn(usize) -> bool,
+    ) -> execute_status::ExecuteResult
+    where
+        C: ExecutionContextTrait,
+        X: for<'a> ExecuteTrait<C, RecordingConsumer<'a, I>, E>,
+        I: IntentConsumerTrait,
+        E: EffectConsumerTrait,
+    {
+        let mut last_intents = self.last_intents.borrow_mut();
+        let mut emitted = Emitted(self.dedup_intents.then(Vec::new));
+        let mut trace = self.tracing.then(|| TickTrace {
+            tick,
+            blocks: Vec::with_capacity(self.blocks.len()),
+        });
+        let mut result = Ok(execute_status::Success);
+        for (idx, block) in self.blocks.iter().enumerate() {
+            let record = &mut last_intents[idx];
+            let mut entry = BlockTrace::default();
+            if runs(idx) {
+                record.clear();
+                let mut recording = RecordingConsumer {
+                    inner: intent_consumer,
+                    record,
+                    emitted: &mut emitted,
+                };
+                entry.executed = true;
+                if let Some(derived) = &self.derived {
+                    derived.refresh();
+                }
+                result = if trace.is_some() {
+                    block.execute_traced(context, &mut recording, effect_consumer, &mut entry)
+                } else {
+                    block.execute(context, &mut recording, effect_consumer)
+                };
+            } else {
+                // Off-tick: repeat the last intents, padding with `NoIntent` in
+                // case the block has not completed an execution yet.
+                result = record
+                    .iter()
+                    .try_for_each(|intent| emitted.forward(intent_consumer, intent))
+                    .and_then(|_| {
+                        (record.len()..block.num_intents())
+                            .try_for_each(|_| intent_consumer.consume(&Intent::NoIntent))
+                    })
+                    .map(|_| execute_status::Success);
+            }
+            if let Some(trace) = trace.as_mut() {
+                entry.intents = record.clone();
+                trace.blocks.push(entry);
+            }
+            if result.is_err() {
+                break;
+            }
+        }
+        if self.tracing {
+            *self.last_trace.borrow_mut() = trace;
+        }
+        result
+    }
+}
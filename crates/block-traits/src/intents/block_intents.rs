@@ -14,6 +14,40 @@ pub trait BlockIntents: sealed::Sealed {
     fn as_slice(&self) -> &[Intent];
 }
 
+/// Error returned when an intent is emitted for a slot name that the block
+/// did not declare.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownSlot(pub String);
+
+impl std::fmt::Display for UnknownSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown intent slot '{}'", self.0)
+    }
+}
+
+impl std::error::Error for UnknownSlot {}
+
+/// Place named intents into the slots with the same names.
+///
+/// The slot index of an intent is the position of its name in `slots`, so the
+/// order the intents are emitted in does not matter. Slots that receive no
+/// intent hold `NoIntent`, and a later intent for the same slot replaces an
+/// earlier one.
+fn place_named<'a, const N: usize>(
+    slots: [&str; N],
+    intents: impl IntoIterator<Item = (&'a str, Intent)>,
+) -> Result<[Intent; N], UnknownSlot> {
+    let mut placed: [Intent; N] = std::array::from_fn(|_| Intent::NoIntent);
+    for (name, intent) in intents {
+        let idx = slots
+            .iter()
+            .position(|slot| *slot == name)
+            .ok_or_else(|| UnknownSlot(name.to_string()))?;
+        placed[idx] = intent;
+    }
+    Ok(placed)
+}
+
 /// Macro defining a set of BlockIntents implementations for
 /// fixed-size arrays of Intent. Call like:
 /// ```text
@@ -23,6 +57,10 @@ pub trait BlockIntents: sealed::Sealed {
 /// You can then use `ThreeIntents::new([intent1, intent2, intent3])`
 /// to create an instance. The number of intents must match the
 /// compiler-time constant given to the macro.
+///
+/// Blocks that emit their intents in a varying order can instead use
+/// `ThreeIntents::from_named(["bid", "ask", "hedge"], intents)`, which keeps
+/// every named intent in the same slot from tick to tick.
 macro_rules! declare_intents {
     ($name:ident, 0) => {
         #[derive(Clone, Debug)]
@@ -35,6 +73,12 @@ macro_rules! declare_intents {
             pub fn from_array(_: [Intent; 0]) -> Self {
                 Self
             }
+            pub fn from_named<'a>(
+                slots: [&str; 0],
+                intents: impl IntoIterator<Item = (&'a str, Intent)>,
+            ) -> Result<Self, UnknownSlot> {
+                place_named(slots, intents).map(|_| Self)
+            }
         }
 
         impl Default for $name {
@@ -64,6 +108,12 @@ macro_rules! declare_intents {
             pub fn from_array(intents: [Intent; $n]) -> Self {
                 Self::new(intents)
             }
+            pub fn from_named<'a>(
+                slots: [&str; $n],
+                intents: impl IntoIterator<Item = (&'a str, Intent)>,
+            ) -> Result<Self, UnknownSlot> {
+                place_named(slots, intents).map(Self)
+            }
         }
 
         impl Default for $name {
@@ -1,6 +1,6 @@
 use trade_types::{Contract, Price, Quantity, Side};
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum Intent {
     #[default]
     NoIntent,
@@ -9,5 +9,9 @@ pub enum Intent {
         side: Side,
         price: Price,
         quantity: Quantity,
+        /// Precedence over other places for the same contract and side in a
+        /// tick; reconciliation withdraws the lower-priority orders. Places of
+        /// equal priority, such as the default 0, are all kept.
+        priority: u8,
     },
 }
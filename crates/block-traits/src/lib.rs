@@ -13,6 +13,8 @@ pub mod execute_trait;
 pub mod execution_context;
 pub mod execution_plan;
 pub mod intents;
+#[cfg(feature = "sync")]
+pub mod sync_block;
 
 pub use associated_types::{
     block_keys, BlockInput, BlockOutput, BlockSpecAssociatedTypes, ContractDeps,
@@ -21,8 +23,10 @@ pub use block_spec::BlockSpec;
 pub use block_weave::{BlockEmbedding, BlockPackage};
 pub use effects::*;
 pub use execute_trait::{execute_status, EffectConsumerTrait, ExecuteTrait, IntentConsumerTrait};
-pub use execution_context::ExecutionContextTrait;
+pub use execution_context::{Capability, ExecutionContextTrait};
 pub use intents::*;
+#[cfg(feature = "sync")]
+pub use sync_block::{SyncBlock, SyncBlockTrait};
 
 #[cfg(test)]
 mod test_types {
@@ -146,6 +150,7 @@ mod test_types {
         type State = TestState;
         type InitParameters = DoublerInitParams;
         type Intents = ::block_traits::intents::ZeroIntents;
+        const NAME: &'static str = "DoublerBlock";
     }
     impl ::block_traits::block_spec::EmptyContractDepsTag for DoublerBlock {}
 
@@ -227,6 +232,14 @@ mod tests {
         assert_eq!(context.time, 12345);
     }
 
+    #[test]
+    fn test_elapsed_since_saturates_for_future_start() {
+        let context = ExecutionContext { time: 100 };
+        assert_eq!(context.elapsed_since(40), 60);
+        assert_eq!(context.elapsed_since(100), 0);
+        assert_eq!(context.elapsed_since(150), 0);
+    }
+
     #[test]
     fn test_block_spec_block_id() {
         let block = DoublerBlock;
@@ -324,6 +337,7 @@ mod tests {
         type State = TestState;
         type InitParameters = AccumulatorInitParams;
         type Intents = ::block_traits::intents::ZeroIntents;
+        const NAME: &'static str = "AccumulatorBlock";
     }
     impl ::block_traits::block_spec::EmptyContractDepsTag for AccumulatorBlock {}
 
@@ -390,4 +404,502 @@ mod tests {
             state = new_state;
         }
     }
+
+    // Context with a deadline, for cooperative deadline checks.
+    struct DeadlineContext {
+        deadline: std::time::Instant,
+    }
+
+    impl ExecutionContextTrait for DeadlineContext {
+        type OrderBook = OrderBook;
+
+        fn time(&self) -> u64 {
+            0
+        }
+        fn get_order_book(&self, _contract: &Contract) -> Option<Self::OrderBook> {
+            Some(OrderBook)
+        }
+        fn get_position(
+            &self,
+            _block_id: u32,
+            _contract: &Contract,
+        ) -> Option<trade_types::Quantity> {
+            None
+        }
+        fn deadline(&self) -> Option<std::time::Instant> {
+            Some(self.deadline)
+        }
+    }
+
+    // Block that checks the deadline before doing its work.
+    struct DeadlineAwareBlock;
+
+    impl BlockSpecAssociatedTypes for DeadlineAwareBlock {
+        type Input = TestInput;
+        type Output = TestOutput;
+        type State = TestState;
+        type InitParameters = DoublerInitParams;
+        type Intents = ::block_traits::intents::ZeroIntents;
+        const NAME: &'static str = "DeadlineAwareBlock";
+    }
+    impl ::block_traits::block_spec::EmptyContractDepsTag for DeadlineAwareBlock {}
+
+    impl BlockSpec for DeadlineAwareBlock {
+        fn block_id(&self) -> u32 {
+            1
+        }
+
+        fn new_from_init_params(_params: &DoublerInitParams) -> Self {
+            DeadlineAwareBlock
+        }
+
+        fn init_state(&self) -> Self::State {
+            TestState { acc: 0 }
+        }
+
+        fn execute<C: ExecutionContextTrait, E: EffectConsumerTrait>(
+            &self,
+            context: &C,
+            input: Self::Input,
+            state: &Self::State,
+            _effect_consumer: &mut E,
+        ) -> Result<(Self::Output, Self::State, Self::Intents), execute_status::FailureStatus>
+        {
+            context.check_deadline()?;
+            let output = TestOutput {
+                result: input.value,
+            };
+            Ok((output, state.clone(), Self::Intents::new()))
+        }
+    }
+
+    #[test]
+    fn test_contexts_have_no_deadline_by_default() {
+        let context = ExecutionContext { time: 0 };
+        assert!(context.deadline().is_none());
+        assert!(!context.deadline_exceeded());
+        assert!(context.check_deadline().is_ok());
+    }
+
+    #[test]
+    fn test_block_past_deadline_fails_with_reason() {
+        use std::time::{Duration, Instant};
+
+        let past = Instant::now()
+            .checked_sub(Duration::from_millis(1))
+            .unwrap_or_else(Instant::now);
+        let context = DeadlineContext { deadline: past };
+        let mut effect_handler = |_effect: Effect| {};
+
+        let err = DeadlineAwareBlock
+            .execute(
+                &context,
+                TestInput { value: 1 },
+                &TestState { acc: 0 },
+                &mut effect_handler,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            execute_status::FailureStatus::with_reason(execution_context::DEADLINE_EXCEEDED)
+        );
+        assert_eq!(err.reason(), Some("deadline"));
+    }
+
+    #[test]
+    fn test_block_before_deadline_succeeds() {
+        use std::time::{Duration, Instant};
+
+        let context = DeadlineContext {
+            deadline: Instant::now() + Duration::from_secs(60),
+        };
+        let mut effect_handler = |_effect: Effect| {};
+
+        let (output, _, _) = DeadlineAwareBlock
+            .execute(
+                &context,
+                TestInput { value: 3 },
+                &TestState { acc: 0 },
+                &mut effect_handler,
+            )
+            .unwrap();
+        assert_eq!(output.result, 3);
+    }
+
+    fn place(price: u32) -> Intent {
+        Intent::Place {
+            contract: Contract::new("C"),
+            side: Side::Buy,
+            price: Price::from(Cents(price)),
+            quantity: trade_types::Quantity::from(trade_types::Kw(1)),
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn test_named_intents_keep_slots_when_emit_order_changes() {
+        const SLOTS: [&str; 3] = ["bid", "ask", "hedge"];
+
+        let first =
+            ThreeIntents::from_named(SLOTS, [("bid", place(10)), ("hedge", place(30))]).unwrap();
+        let second =
+            ThreeIntents::from_named(SLOTS, [("hedge", place(30)), ("bid", place(10))]).unwrap();
+
+        assert_eq!(first.as_slice(), second.as_slice());
+        assert_eq!(first.as_slice()[0], place(10));
+        assert_eq!(first.as_slice()[1], Intent::NoIntent);
+        assert_eq!(first.as_slice()[2], place(30));
+    }
+
+    #[test]
+    fn test_named_intents_reject_unknown_slot() {
+        let err = TwoIntents::from_named(["bid", "ask"], [("hedge", place(1))]).unwrap_err();
+        assert_eq!(err, UnknownSlot("hedge".to_string()));
+        assert!(ZeroIntents::from_named([], [("bid", place(1))]).is_err());
+    }
+
+    /// Block that writes the context time to its output and emits it as a price.
+    struct TimeBlock {
+        output: std::rc::Rc<std::cell::Cell<u64>>,
+    }
+
+    impl ContractDeps for TimeBlock {}
+
+    impl<C, I, E> ExecuteTrait<C, I, E> for TimeBlock
+    where
+        C: ExecutionContextTrait,
+        I: IntentConsumerTrait + ?Sized,
+        E: EffectConsumerTrait + ?Sized,
+    {
+        fn num_intents(&self) -> usize {
+            1
+        }
+        fn execute(
+            &self,
+            context: &C,
+            intent_consumer: &mut I,
+            _effect_consumer: &mut E,
+        ) -> execute_status::ExecuteResult {
+            self.output.set(context.time());
+            intent_consumer.consume(&place(context.time() as u32))?;
+            Ok(execute_status::Success)
+        }
+    }
+
+    #[test]
+    fn test_execution_plan_cadence_skips_off_ticks() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let every_tick = Rc::new(Cell::new(u64::MAX));
+        let every_other = Rc::new(Cell::new(u64::MAX));
+        let plan = execution_plan::ExecutionPlan::new(
+            weave::TopoOrdered(vec![
+                TimeBlock {
+                    output: every_tick.clone(),
+                },
+                TimeBlock {
+                    output: every_other.clone(),
+                },
+            ]),
+            [(1, 2)].into_iter().collect(),
+        );
+        assert_eq!(plan.cadence(0), 1);
+        assert_eq!(plan.cadence(1), 2);
+
+        for time in 0..4 {
+            let context = ExecutionContext { time };
+            let mut intents = Vec::new();
+            let mut consumer = |intent: &Intent| intents.push(intent.clone());
+            let mut effects = |_effect: Effect| {};
+            plan.execute(&context, &mut consumer, &mut effects).unwrap();
+
+            let expected = time - time % 2;
+            assert_eq!(every_tick.get(), time);
+            assert_eq!(every_other.get(), expected);
+            // Skipped blocks repeat their last intents so slots stay aligned.
+            assert_eq!(intents, vec![place(time as u32), place(expected as u32)]);
+        }
+        assert_eq!(plan.tick(), 4);
+    }
+
+    #[test]
+    fn test_execution_plan_dedups_identical_place_intents() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let blocks = || {
+            weave::TopoOrdered(
+                (0..3)
+                    .map(|_| TimeBlock {
+                        output: Rc::new(Cell::new(0)),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let run = |plan: &execution_plan::ExecutionPlan<TimeBlock>| {
+            let mut intents = Vec::new();
+            let mut consumer = |intent: &Intent| intents.push(intent.clone());
+            let mut effects = |_effect: Effect| {};
+            plan.execute(&ExecutionContext { time: 5 }, &mut consumer, &mut effects)
+                .unwrap();
+            intents
+        };
+
+        let plain = execution_plan::ExecutionPlan::from(blocks());
+        assert_eq!(run(&plain), vec![place(5), place(5), place(5)]);
+
+        // Only the first place intent survives; the duplicates keep their slots.
+        let dedup = execution_plan::ExecutionPlan::from(blocks()).with_intent_dedup();
+        assert_eq!(
+            run(&dedup),
+            vec![place(5), Intent::NoIntent, Intent::NoIntent]
+        );
+    }
+
+    #[test]
+    fn test_execution_plan_traces_every_block() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let blocks = (0..3)
+            .map(|_| TimeBlock {
+                output: Rc::new(Cell::new(0)),
+            })
+            .collect::<Vec<_>>();
+        let plan = execution_plan::ExecutionPlan::new(
+            weave::TopoOrdered(blocks),
+            [(2, 2)].into_iter().collect(),
+        );
+        let mut effects = |_effect: Effect| {};
+        let mut consumer = |_intent: &Intent| {};
+        plan.execute(&ExecutionContext { time: 0 }, &mut consumer, &mut effects)
+            .unwrap();
+        assert_eq!(plan.last_trace(), None);
+
+        let plan = plan.with_tracing();
+        plan.execute(&ExecutionContext { time: 1 }, &mut consumer, &mut effects)
+            .unwrap();
+        let trace = plan.last_trace().unwrap();
+        assert_eq!(trace.tick, 1);
+        assert_eq!(trace.blocks.len(), 3);
+        let executed: Vec<bool> = trace.blocks.iter().map(|b| b.executed).collect();
+        assert_eq!(executed, vec![true, true, false]);
+        assert_eq!(trace.blocks[0].intents, vec![place(1)]);
+        // The skipped block repeats the intents from its last execution.
+        assert_eq!(trace.blocks[2].intents, vec![place(0)]);
+    }
+
+    /// Block that stamps the context time on its output and places an intent
+    /// priced by its id.
+    struct StampBlock {
+        id: u32,
+        stamp: std::rc::Rc<std::cell::Cell<u64>>,
+    }
+
+    impl ContractDeps for StampBlock {}
+
+    impl execution_plan::BlockIds for StampBlock {
+        fn block_ids(&self) -> Vec<u32> {
+            vec![self.id]
+        }
+    }
+
+    impl<C, I, E> ExecuteTrait<C, I, E> for StampBlock
+    where
+        C: ExecutionContextTrait,
+        I: IntentConsumerTrait + ?Sized,
+        E: EffectConsumerTrait + ?Sized,
+    {
+        fn num_intents(&self) -> usize {
+            1
+        }
+        fn execute(
+            &self,
+            context: &C,
+            intent_consumer: &mut I,
+            _effect_consumer: &mut E,
+        ) -> execute_status::ExecuteResult {
+            self.stamp.set(context.time());
+            intent_consumer.consume(&place(self.id))?;
+            Ok(execute_status::Success)
+        }
+    }
+
+    #[test]
+    fn test_execution_plan_execute_from_skips_upstream_blocks() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let stamps: Vec<Rc<Cell<u64>>> = (0..3).map(|_| Rc::new(Cell::new(0))).collect();
+        let blocks = (0..3)
+            .map(|i| StampBlock {
+                id: 10 + i as u32,
+                stamp: stamps[i].clone(),
+            })
+            .collect();
+        let plan = execution_plan::ExecutionPlan::from(weave::TopoOrdered(blocks));
+        let mut effects = |_effect: Effect| {};
+        let mut consumer = |_intent: &Intent| {};
+        plan.execute(&ExecutionContext { time: 1 }, &mut consumer, &mut effects)
+            .unwrap();
+
+        let mut intents = Vec::new();
+        let mut consumer = |intent: &Intent| intents.push(intent.clone());
+        plan.execute_from(
+            &ExecutionContext { time: 2 },
+            &mut consumer,
+            &mut effects,
+            11,
+        )
+        .unwrap();
+        let times: Vec<u64> = stamps.iter().map(|s| s.get()).collect();
+        // The first block is stale; the others ran again.
+        assert_eq!(times, vec![1, 2, 2]);
+        // Skipped blocks keep their intent slots.
+        assert_eq!(intents, vec![place(10), place(11), place(12)]);
+        assert_eq!(plan.tick(), 1);
+
+        let mut consumer = |_intent: &Intent| {};
+        let err = plan
+            .execute_from(
+                &ExecutionContext { time: 3 },
+                &mut consumer,
+                &mut effects,
+                99,
+            )
+            .unwrap_err();
+        assert!(err.reason().unwrap().contains("99"));
+    }
+
+    /// Block that counts the ticks it ran in a shared `TickCounter`.
+    struct CountingBlock;
+
+    struct TickCounter(u64);
+
+    impl ContractDeps for CountingBlock {}
+
+    impl<C, I, E> ExecuteTrait<C, I, E> for CountingBlock
+    where
+        C: ExecutionContextTrait,
+        I: IntentConsumerTrait + ?Sized,
+        E: EffectConsumerTrait + ?Sized,
+    {
+        fn execute(
+            &self,
+            context: &C,
+            _intent_consumer: &mut I,
+            _effect_consumer: &mut E,
+        ) -> execute_status::ExecuteResult {
+            let counter = context
+                .shared()
+                .and_then(|shared| shared.get::<TickCounter>())
+                .ok_or_else(|| execute_status::FailureStatus::with_reason("no counter"))?;
+            counter.borrow_mut().0 += 1;
+            Ok(execute_status::Success)
+        }
+    }
+
+    #[test]
+    fn test_execution_plan_shares_resources_across_ticks() {
+        let plan = execution_plan::ExecutionPlan::from(weave::TopoOrdered(vec![
+            CountingBlock,
+            CountingBlock,
+        ]));
+        plan.shared().insert(TickCounter(0));
+
+        let mut effects = |_effect: Effect| {};
+        let mut consumer = |_intent: &Intent| {};
+        for time in 0..3 {
+            plan.execute_with_shared(&ExecutionContext { time }, &mut consumer, &mut effects)
+                .unwrap();
+        }
+        let counter = plan.shared().get::<TickCounter>().unwrap();
+        assert_eq!(counter.borrow().0, 6);
+        assert_eq!(plan.tick(), 3);
+
+        // Plain contexts provide no shared resources.
+        assert!(ExecutionContext { time: 0 }.shared().is_none());
+        assert!(plan
+            .execute(&ExecutionContext { time: 3 }, &mut consumer, &mut effects)
+            .is_err());
+    }
+
+    struct BlackboardContext {
+        blackboard: execution_context::Blackboard,
+    }
+
+    impl ExecutionContextTrait for BlackboardContext {
+        type OrderBook = OrderBook;
+
+        fn time(&self) -> u64 {
+            0
+        }
+        fn get_order_book(&self, _contract: &Contract) -> Option<Self::OrderBook> {
+            None
+        }
+        fn get_position(
+            &self,
+            _block_id: u32,
+            _contract: &Contract,
+        ) -> Option<trade_types::Quantity> {
+            None
+        }
+        fn scratch(&self, block_id: u32) -> execution_context::Scratch {
+            self.blackboard.scratch(block_id)
+        }
+    }
+
+    #[test]
+    fn test_scratch_is_isolated_per_block_id() {
+        let blackboard = execution_context::Blackboard::new();
+        let tick1 = BlackboardContext {
+            blackboard: blackboard.clone(),
+        };
+        tick1
+            .scratch(1)
+            .borrow_mut()
+            .insert("cache".to_string(), Box::new(42i32));
+        assert!(tick1.scratch(2).borrow().is_empty());
+
+        // A new context on the same blackboard sees the same scratch spaces.
+        let tick2 = BlackboardContext { blackboard };
+        let scratch = tick2.scratch(1);
+        let cached = scratch.borrow()["cache"].downcast_ref::<i32>().copied();
+        assert_eq!(cached, Some(42));
+        assert!(tick2.scratch(2).borrow().is_empty());
+
+        // Without a blackboard nothing is kept.
+        let context = ExecutionContext { time: 0 };
+        context
+            .scratch(1)
+            .borrow_mut()
+            .insert("cache".to_string(), Box::new(1i32));
+        assert!(context.scratch(1).borrow().is_empty());
+    }
+
+    struct IdBlock(u32);
+
+    impl execution_plan::BlockIds for IdBlock {
+        fn block_ids(&self) -> Vec<u32> {
+            vec![self.0]
+        }
+    }
+
+    #[test]
+    fn test_execution_plan_rejects_duplicate_block_ids() {
+        use execution_plan::{DuplicateBlockId, ExecutionPlan};
+
+        let plan = |ids: &[u32]| {
+            ExecutionPlan::new_checked(
+                weave::TopoOrdered(ids.iter().map(|&id| IdBlock(id)).collect()),
+                Default::default(),
+            )
+        };
+        assert!(plan(&[1, 2, 3]).is_ok());
+
+        let err = plan(&[1, 7, 2, 7]).err().unwrap();
+        assert_eq!(err, DuplicateBlockId(7));
+        assert!(err.to_string().contains("7"));
+    }
 }
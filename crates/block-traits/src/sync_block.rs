@@ -0,0 +1,163 @@
+use super::*;
+use crate::execution_plan::BlockTrace;
+use std::sync::{Mutex, MutexGuard};
+
+type InputFn<B> = Box<dyn Fn() -> <B as BlockSpecAssociatedTypes>::Input + Send + Sync>;
+type OutputFn<B> = Box<dyn Fn(&<B as BlockSpecAssociatedTypes>::Output) + Send + Sync>;
+
+/// Thread-safe counterpart of `BlockEmbedding`.
+///
+/// A `SyncBlock` runs a `BlockSpec` like `BlockEmbedding` does, but keeps its
+/// state behind a `Mutex` and reads and writes its data through `Send + Sync`
+/// functions, typically closures over channels from a `SyncChannelRegistry`.
+/// The block can therefore be moved to, and executed on, another thread as long
+/// as the block and its state are `Send`.
+///
+/// A panic during an execution, e.g. in the output function, poisons the locks
+/// but leaves the state as it was before that execution, since the state is
+/// only replaced as a whole. Poisoned locks are therefore recovered, and the
+/// block can keep executing.
+pub struct SyncBlock<B: BlockSpec> {
+    block: B,
+    input: InputFn<B>,
+    output: OutputFn<B>,
+    state: Mutex<B::State>,
+    prev_output: Mutex<Option<B::Output>>,
+    executed: Mutex<bool>,
+}
+
+impl<B: BlockSpec> SyncBlock<B> {
+    /// Create a block from its init parameters, the function reading its input,
+    /// and the function writing its output. The state starts at `init_state`.
+    pub fn new(
+        init_params: &B::InitParameters,
+        input: impl Fn() -> B::Input + Send + Sync + 'static,
+        output: impl Fn(&B::Output) + Send + Sync + 'static,
+    ) -> Self {
+        let block = B::new_from_init_params(init_params);
+        let state = Mutex::new(block.init_state());
+        Self {
+            block,
+            input: Box::new(input),
+            output: Box::new(output),
+            state,
+            prev_output: Mutex::new(None),
+            executed: Mutex::new(false),
+        }
+    }
+
+    /// Replace the block's state, e.g. with a previously saved one.
+    ///
+    /// The block counts as having executed, so it skips `execute_first`.
+    pub fn with_state(self, state: B::State) -> Self {
+        *lock(&self.state) = state;
+        *lock(&self.executed) = true;
+        self
+    }
+
+    /// A copy of the block's current state.
+    pub fn state(&self) -> B::State {
+        lock(&self.state).clone()
+    }
+
+    fn execute_with_trace<C, I, E>(
+        &self,
+        context: &C,
+        intent_consumer: &mut I,
+        effect_consumer: &mut E,
+        trace: Option<&mut BlockTrace>,
+    ) -> execute_status::ExecuteResult
+    where
+        C: ExecutionContextTrait,
+        I: IntentConsumerTrait,
+        E: EffectConsumerTrait,
+    {
+        block_weave::embed::execute_block(
+            &self.block,
+            (self.input)(),
+            &mut lock(&self.state),
+            &mut lock(&self.prev_output),
+            &mut lock(&self.executed),
+            &self.output,
+            context,
+            intent_consumer,
+            effect_consumer,
+            trace,
+        )
+    }
+}
+
+impl<B> ContractDeps for SyncBlock<B>
+where
+    B: BlockSpec,
+{
+    fn contract_deps(&self) -> Vec<::trade_types::Contract> {
+        self.block.contract_deps()
+    }
+}
+
+impl<B> crate::execution_context::RequiredCapabilities for SyncBlock<B>
+where
+    B: BlockSpec,
+{
+    fn required_capabilities(&self) -> Vec<Capability> {
+        B::required_capabilities().to_vec()
+    }
+}
+
+impl<B, C, I, E> ExecuteTrait<C, I, E> for SyncBlock<B>
+where
+    B: BlockSpec,
+    C: ExecutionContextTrait,
+    I: IntentConsumerTrait,
+    E: EffectConsumerTrait,
+{
+    fn num_intents(&self) -> usize {
+        B::Intents::len()
+    }
+    fn execute(
+        &self,
+        context: &C,
+        intent_consumer: &mut I,
+        effect_consumer: &mut E,
+    ) -> execute_status::ExecuteResult {
+        self.execute_with_trace(context, intent_consumer, effect_consumer, None)
+    }
+    fn execute_traced(
+        &self,
+        context: &C,
+        intent_consumer: &mut I,
+        effect_consumer: &mut E,
+        trace: &mut BlockTrace,
+    ) -> execute_status::ExecuteResult {
+        self.execute_with_trace(context, intent_consumer, effect_consumer, Some(trace))
+    }
+}
+
+/// Lock `mutex`, recovering it if an earlier execution panicked; see `SyncBlock`.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Blocks that can be executed on a different thread than they were created on.
+///
+/// `ExecuteTrait` objects are not `Send`, so a multi-threaded controller holds
+/// its blocks as `Box<dyn SyncBlockTrait<C, I, E>>` rather than spelling out
+/// `Box<dyn ExecuteTrait<C, I, E> + Send>` everywhere. Every `Send` block,
+/// such as a `SyncBlock`, implements it.
+pub trait SyncBlockTrait<C, I, E>: ExecuteTrait<C, I, E> + Send
+where
+    C: ExecutionContextTrait,
+    I: IntentConsumerTrait,
+    E: EffectConsumerTrait,
+{
+}
+
+impl<T, C, I, E> SyncBlockTrait<C, I, E> for T
+where
+    T: ExecuteTrait<C, I, E> + Send,
+    C: ExecutionContextTrait,
+    I: IntentConsumerTrait,
+    E: EffectConsumerTrait,
+{
+}
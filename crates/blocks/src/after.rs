@@ -1,22 +1,49 @@
 use super::*;
 
-// Default (empty struct) Input and State
-make_defaults!(input, state,);
+// Default (empty struct) Input
+make_defaults!(input,);
 
 #[output]
 pub struct Output {
     pub is_after: bool,
 }
 
+/// How the block interprets its `time` threshold.
+#[derive(
+    PartialEq, Eq, Clone, Copy, Debug, Default, serde::Serialize, serde::Deserialize, Serializable,
+)]
+pub enum TimeMode {
+    /// `time` is an absolute context time; the block fires once the context
+    /// time is past it.
+    #[default]
+    Absolute,
+    /// `time` is a delay from the context time of the block's first execution;
+    /// the block fires once that many ticks have passed.
+    ///
+    /// The boundary is inclusive: the block fires at `start + time`, where
+    /// `start` is the time of the first execution, so a delay of 0 fires right
+    /// away. This differs from `Absolute`, which only fires after `time`.
+    Relative,
+}
+
+#[state]
+pub struct State {
+    /// Context time of the first execution; only recorded in `Relative` mode.
+    pub start_time: Option<u64>,
+}
+
 #[init_params]
 pub struct InitParams {
     pub time: u64,
+    #[serde(default)]
+    pub mode: TimeMode,
 }
 
 #[block]
 pub struct AfterBlock {
     pub block_id: u32,
     time: u64,
+    mode: TimeMode,
 }
 
 impl BlockSpec for AfterBlock {
@@ -24,84 +51,64 @@ impl BlockSpec for AfterBlock {
         self.block_id
     }
 
+    fn set_block_id(&mut self, block_id: u32) {
+        self.block_id = block_id;
+    }
+
     fn new_from_init_params(params: &InitParams) -> Self {
         AfterBlock {
             block_id: 0,
             time: params.time,
+            mode: params.mode,
         }
     }
 
     fn init_state(&self) -> State {
-        State
+        State { start_time: None }
     }
 
     #[execute]
-    fn execute<C: ExecutionContextTrait>(&self, context: &C) -> Output {
-        let is_after = context.time() > self.time;
-        Output { is_after }
+    fn execute<C: ExecutionContextTrait>(&self, context: &C, state: &State) -> (Output, State) {
+        let now = context.time();
+        let (is_after, start_time) = match self.mode {
+            TimeMode::Absolute => (now > self.time, None),
+            TimeMode::Relative => {
+                let start = state.start_time.unwrap_or(now);
+                (now >= start.saturating_add(self.time), Some(start))
+            }
+        };
+        (Output { is_after }, State { start_time })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use block_traits::execution_context::MockExecutionContext;
     use block_traits::Effect;
-    use trade_types::{Cents, Contract, Price, Side};
-
-    pub struct OrderBook;
-
-    impl block_traits::execution_context::OrderBookTrait for OrderBook {
-        fn top_of_side(&self, _side: Side) -> Option<Price> {
-            // Dummy implementation
-            Some(Price::from(Cents(100)))
-        }
-    }
-
-    pub struct ExecutionContext {
-        pub time: u64,
-    }
-
-    impl ExecutionContextTrait for ExecutionContext {
-        type OrderBook = OrderBook;
-        fn time(&self) -> u64 {
-            self.time
-        }
-        fn get_order_book(&self, _contract: &Contract) -> Option<OrderBook> {
-            // Mock implementation
-            Some(OrderBook {})
-        }
-        fn get_position(
-            &self,
-            _block_id: u32,
-            _contract: &Contract,
-        ) -> Option<trade_types::Quantity> {
-            // mock position
-            None
-        }
-    }
 
-    fn ctx(time: u64) -> ExecutionContext {
-        ExecutionContext { time }
+    fn ctx(time: u64) -> MockExecutionContext {
+        MockExecutionContext::new().with_time(time)
     }
 
     #[test]
-    fn make_defaults_creates_unit_input_and_state() {
-        // These compile only if the macro generated them.
+    fn make_defaults_creates_unit_input() {
+        // This compiles only if the macro generated it.
         let _input = Input;
-        let _state = State;
 
-        // And they should be Default if you’re relying on defaults elsewhere.
+        // And it should be Default if you’re relying on defaults elsewhere.
         let _input2: Input = Default::default();
-        let _state2: State = Default::default();
 
         // Optional sanity check: unit structs are zero-sized.
         assert_eq!(core::mem::size_of::<Input>(), 0);
-        assert_eq!(core::mem::size_of::<State>(), 0);
     }
 
     #[test]
     fn new_from_init_params_sets_time_and_block_id_default() {
-        let params = InitParams { time: 10 };
+        let params = InitParams {
+            time: 10,
+            mode: TimeMode::Absolute,
+        };
         let block = AfterBlock::new_from_init_params(&params);
 
         // Your impl sets block_id = 0 in new_from_init_params
@@ -110,7 +117,10 @@ mod tests {
 
     #[test]
     fn execute_returns_output_and_defaults_for_missing_parts_before_time() {
-        let params = InitParams { time: 10 };
+        let params = InitParams {
+            time: 10,
+            mode: TimeMode::Absolute,
+        };
         let block = AfterBlock::new_from_init_params(&params);
 
         let context = ctx(9);
@@ -118,41 +128,77 @@ mod tests {
         // Call the *full* signature (what #[execute] generates).
         let mut effect_handler = |_effect: Effect| {};
         let (out, state_out, _intents) = block
-            .execute(&context, Input, &State, &mut effect_handler)
+            .execute(&context, Input, &block.init_state(), &mut effect_handler)
             .unwrap();
 
         assert!(!out.is_after);
-        assert!(matches!(state_out, State)); // default-filled (unit)
+        assert_eq!(state_out.start_time, None); // absolute mode keeps no start time
     }
 
     #[test]
     fn execute_returns_output_and_defaults_for_missing_parts_after_time() {
-        let params = InitParams { time: 10 };
+        let params = InitParams {
+            time: 10,
+            mode: TimeMode::Absolute,
+        };
         let block = AfterBlock::new_from_init_params(&params);
 
         let context = ctx(11);
         let mut effect_handler = |_effect: Effect| {};
 
         let (out, state_out, _intents) = block
-            .execute(&context, Input, &State, &mut effect_handler)
+            .execute(&context, Input, &block.init_state(), &mut effect_handler)
             .unwrap();
 
         assert!(out.is_after);
-        assert!(matches!(state_out, State));
+        assert_eq!(state_out.start_time, None);
     }
 
     #[test]
     fn execute_is_false_when_equal() {
-        let params = InitParams { time: 10 };
+        let params = InitParams {
+            time: 10,
+            mode: TimeMode::Absolute,
+        };
         let block = AfterBlock::new_from_init_params(&params);
 
         let context = ctx(10);
         let mut effect_handler = |_effect: Effect| {};
 
         let (out, _state_out, _intents) = block
-            .execute(&context, Input, &State, &mut effect_handler)
+            .execute(&context, Input, &block.init_state(), &mut effect_handler)
             .unwrap();
 
         assert!(!out.is_after);
     }
+
+    #[test]
+    fn relative_mode_fires_delay_after_first_execution() {
+        let params = InitParams {
+            time: 5,
+            mode: TimeMode::Relative,
+        };
+        let block = AfterBlock::new_from_init_params(&params);
+        let mut effect_handler = |_effect: Effect| {};
+
+        let mut state = block.init_state();
+        let mut fired = Vec::new();
+        for time in 100..=106 {
+            let (out, next_state, _intents) = block
+                .execute(&ctx(time), Input, &state, &mut effect_handler)
+                .unwrap();
+            assert_eq!(next_state.start_time, Some(100));
+            fired.push(out.is_after);
+            state = next_state;
+        }
+
+        // Fires once the context time reaches start_time + 5, i.e. at tick 105.
+        assert_eq!(fired, [false, false, false, false, false, true, true]);
+    }
+
+    #[test]
+    fn mode_defaults_to_absolute_when_omitted() {
+        let params: InitParams = serde_json::from_str(r#"{ "time": 3 }"#).unwrap();
+        assert_eq!(params.mode, TimeMode::Absolute);
+    }
 }
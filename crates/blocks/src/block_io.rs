@@ -1,4 +1,5 @@
 use super::*;
+use serialization::JsonReadOptions;
 
 #[derive(Debug)]
 pub enum ReadBlocksError {
@@ -26,6 +27,89 @@ pub fn read_blocktypes_from_json_string(
     serde_json::from_str::<Vec<BlockPackages>>(json)
 }
 
+/// Reads blocks from a JSON string like `read_blocktypes_from_json_string`,
+/// with the given options, e.g. to fill in `${VAR}` patterns from the
+/// environment.
+pub fn read_blocktypes_from_json_string_with_options(
+    json: &str,
+    options: JsonReadOptions,
+) -> serialization::Result<Vec<BlockPackages>> {
+    Ok(serde_json::from_value(parse_json_with_options(
+        json, options,
+    )?)?)
+}
+
+/// Parse JSON into a value, interpolating the environment if `options` ask for it.
+fn parse_json_with_options(
+    json: &str,
+    options: JsonReadOptions,
+) -> serialization::Result<serde_json::Value> {
+    let mut value = serde_json::from_str(json)?;
+    if options.interpolate_env {
+        serialization::interpolate_env_value(&mut value)?;
+    }
+    Ok(value)
+}
+
+/// A block read by `read_blocktypes_allowing_unknown`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlockEntry {
+    Known(BlockPackages),
+    /// A block whose type this binary does not know, with its raw `data`.
+    Unknown {
+        type_name: String,
+        data: serde_json::Value,
+    },
+}
+
+impl BlockEntry {
+    /// The block, if its type is known.
+    pub fn known(self) -> Option<BlockPackages> {
+        match self {
+            BlockEntry::Known(block) => Some(block),
+            BlockEntry::Unknown { .. } => None,
+        }
+    }
+}
+
+/// Reads blocks from a JSON string like `read_blocktypes_from_json_string`,
+/// but keeps blocks of unknown types as `BlockEntry::Unknown` instead of failing.
+///
+/// This lets configurations written for newer binaries, with block types this
+/// one lacks, still load their known blocks; the caller decides whether to
+/// report or skip the rest. Errors in known blocks still fail, as do entries
+/// without a string `type`.
+pub fn read_blocktypes_allowing_unknown(json: &str) -> serialization::Result<Vec<BlockEntry>> {
+    read_blocktypes_allowing_unknown_with_options(json, JsonReadOptions::default())
+}
+
+/// Reads blocks like `read_blocktypes_allowing_unknown`, with the given options.
+/// Interpolation also covers the data of unknown blocks.
+pub fn read_blocktypes_allowing_unknown_with_options(
+    json: &str,
+    options: JsonReadOptions,
+) -> serialization::Result<Vec<BlockEntry>> {
+    let entries: Vec<serde_json::Value> =
+        serde_json::from_value(parse_json_with_options(json, options)?)?;
+    entries
+        .into_iter()
+        .map(
+            |mut entry| match entry.get("type").and_then(|t| t.as_str()) {
+                Some(name) if !BlockPackages::TYPE_NAMES.contains(&name) => {
+                    Ok(BlockEntry::Unknown {
+                        type_name: name.to_string(),
+                        data: entry
+                            .get_mut("data")
+                            .map(serde_json::Value::take)
+                            .unwrap_or_default(),
+                    })
+                }
+                _ => deserialize_block(entry).map(BlockEntry::Known),
+            },
+        )
+        .collect()
+}
+
 pub fn read_blocktypes_from_json_file<P: AsRef<Path>>(
     path: P,
 ) -> Result<Vec<BlockPackages>, ReadBlocksError> {
@@ -34,3 +118,131 @@ pub fn read_blocktypes_from_json_file<P: AsRef<Path>>(
     file.read_to_string(&mut buf)?;
     Ok(serde_json::from_str::<Vec<BlockPackages>>(&buf)?)
 }
+
+/// Reads blocks from a JSON file like `read_blocktypes_from_json_file`, with the
+/// given options.
+pub fn read_blocktypes_from_json_file_with_options<P: AsRef<Path>>(
+    path: P,
+    options: JsonReadOptions,
+) -> serialization::Result<Vec<BlockPackages>> {
+    let json = std::fs::read_to_string(path)?;
+    read_blocktypes_from_json_string_with_options(&json, options)
+}
+
+/// Reads a JSON array of records (JSON objects) from a file.
+pub fn read_records_from_json_file<P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<serde_json::Map<String, serde_json::Value>>, ReadBlocksError> {
+    let file = File::open(path)?;
+    Ok(serde_json::from_reader(BufReader::new(file))?)
+}
+
+/// Streams blocks one at a time from a reader holding a JSON array of blocks.
+///
+/// Unlike `read_blocktypes_from_json_file`, this never holds the whole file in
+/// memory, only the block currently being parsed. Iteration stops after the
+/// first error.
+pub fn stream_blocktypes_from_reader<R: Read>(reader: R) -> BlockTypeStream<R> {
+    BlockTypeStream {
+        reader: BufReader::new(reader),
+        state: StreamState::Start,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StreamState {
+    Start,
+    First,
+    Rest,
+    Done,
+}
+
+/// Iterator returned by `stream_blocktypes_from_reader`.
+pub struct BlockTypeStream<R: Read> {
+    reader: BufReader<R>,
+    state: StreamState,
+}
+
+impl<R: Read> BlockTypeStream<R> {
+    /// Skip whitespace and return the next byte without consuming it.
+    fn peek(&mut self) -> io::Result<Option<u8>> {
+        loop {
+            let buf = self.reader.fill_buf()?;
+            match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+                Some(pos) => {
+                    let byte = buf[pos];
+                    self.reader.consume(pos);
+                    return Ok(Some(byte));
+                }
+                None if buf.is_empty() => return Ok(None),
+                None => {
+                    let len = buf.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+
+    /// Consume the next non-whitespace byte, which must be one of `expected`.
+    fn expect(&mut self, expected: &[u8]) -> Result<u8, ReadBlocksError> {
+        match self.peek()? {
+            Some(byte) if expected.contains(&byte) => {
+                self.reader.consume(1);
+                Ok(byte)
+            }
+            found => {
+                let expected: Vec<String> = expected
+                    .iter()
+                    .map(|b| format!("`{}`", *b as char))
+                    .collect();
+                let found =
+                    found.map_or("end of input".to_string(), |b| format!("`{}`", b as char));
+                Err(ReadBlocksError::Json(serde::de::Error::custom(format!(
+                    "expected {} in block array, found {}",
+                    expected.join(" or "),
+                    found
+                ))))
+            }
+        }
+    }
+
+    fn next_block(&mut self) -> Result<Option<BlockPackages>, ReadBlocksError> {
+        if self.state == StreamState::Start {
+            self.expect(b"[")?;
+            self.state = StreamState::First;
+        }
+        if self.state == StreamState::First {
+            if self.peek()? == Some(b']') {
+                self.reader.consume(1);
+                return Ok(None);
+            }
+        } else if self.expect(b",]")? == b']' {
+            return Ok(None);
+        }
+        self.state = StreamState::Rest;
+
+        let mut de = serde_json::Deserializer::from_reader(&mut self.reader);
+        Ok(Some(serde::Deserialize::deserialize(&mut de)?))
+    }
+}
+
+impl<R: Read> Iterator for BlockTypeStream<R> {
+    type Item = Result<BlockPackages, ReadBlocksError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.state == StreamState::Done {
+            return None;
+        }
+        match self.next_block() {
+            Ok(Some(block)) => Some(Ok(block)),
+            Ok(None) => {
+                self.state = StreamState::Done;
+                None
+            }
+            Err(e) => {
+                self.state = StreamState::Done;
+                Some(Err(e))
+            }
+        }
+    }
+}
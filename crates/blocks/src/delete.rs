@@ -2,7 +2,7 @@ use super::*;
 
 make_defaults!(output, state, init_params);
 
-#[input]
+#[input(defaults)]
 pub struct Input {
     pub should_delete: bool,
 }
@@ -17,6 +17,10 @@ impl BlockSpec for DeleteBlock {
         self.block_id
     }
 
+    fn set_block_id(&mut self, block_id: u32) {
+        self.block_id = block_id;
+    }
+
     fn new_from_init_params(_params: &InitParams) -> Self {
         DeleteBlock { block_id: 0 }
     }
@@ -0,0 +1,400 @@
+use super::*;
+use std::cell::{OnceCell, RefCell};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use serde_json::{Map, Value};
+use trade_types::Price;
+
+make_defaults!(input,);
+
+/// One record of a JSON source: a JSON object whose fields are written to
+/// channels. `None` once the source has run out of records.
+#[derive(Clone, Debug)]
+pub struct Output(pub Option<Map<String, Value>>);
+
+/// The type a record field is converted to before it is written to its channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    /// The field's JSON value as is, in an `Option<serde_json::Value>` channel:
+    /// `None` if the record lacks the field or the source is exhausted.
+    #[default]
+    Json,
+    /// A `bool` channel.
+    Bool,
+    /// An `i64` channel.
+    I64,
+    /// An `f64` channel.
+    F64,
+    /// A `Price` channel, from a price in its JSON form, e.g. `{ "cents": 100 }`.
+    Price,
+}
+
+/// The channel a record field is written to, and the type it is written as.
+///
+/// Human-readable formats also accept a bare channel name, which binds the
+/// field as `FieldType::Json`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct Binding {
+    pub channel: String,
+    #[serde(rename = "type")]
+    pub field_type: FieldType,
+}
+
+impl Binding {
+    /// Bind a field to `channel` as `field_type`.
+    pub fn new(channel: impl Into<String>, field_type: FieldType) -> Self {
+        Binding {
+            channel: channel.into(),
+            field_type,
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Binding {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct Typed {
+            channel: String,
+            #[serde(rename = "type")]
+            field_type: FieldType,
+        }
+
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Readable {
+            Typed(Typed),
+            Bare(String),
+        }
+
+        // Binary formats carry exactly the serialized binding.
+        let typed = if deserializer.is_human_readable() {
+            match Readable::deserialize(deserializer)? {
+                Readable::Typed(typed) => typed,
+                Readable::Bare(channel) => Typed {
+                    channel,
+                    field_type: FieldType::Json,
+                },
+            }
+        } else {
+            Typed::deserialize(deserializer)?
+        };
+        Ok(Binding::new(typed.channel, typed.field_type))
+    }
+}
+
+/// Binds record fields to channels, mapping each field name to the channel it
+/// is written to. Serializes as a plain object, like the keys generated by
+/// `#[output]`.
+///
+/// Typed channels (all but `FieldType::Json`) keep their value when a record
+/// lacks the field or its value does not fit the type, and once the source is
+/// exhausted, so a signal is turned off by a record setting it to `false`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, Serializable)]
+#[serde(transparent)]
+pub struct OutputKeys(pub BTreeMap<String, Binding>);
+
+enum FieldCell {
+    Json(Rc<RefCell<Option<Value>>>),
+    Bool(Rc<RefCell<bool>>),
+    I64(Rc<RefCell<i64>>),
+    F64(Rc<RefCell<f64>>),
+    Price(Rc<RefCell<Price>>),
+}
+
+impl FieldCell {
+    fn new(
+        registry: &channels::ChannelRegistry,
+        binding: &Binding,
+    ) -> Result<Self, channels::RegistryError> {
+        let channel = binding.channel.as_str();
+        let cell = match binding.field_type {
+            FieldType::Json => registry.get_output(channel).map(FieldCell::Json),
+            FieldType::Bool => registry.get_output(channel).map(FieldCell::Bool),
+            FieldType::I64 => registry.get_output(channel).map(FieldCell::I64),
+            FieldType::F64 => registry.get_output(channel).map(FieldCell::F64),
+            FieldType::Price => registry.get_output(channel).map(FieldCell::Price),
+        };
+        cell.map_err(|e| e.in_writer(channel))
+    }
+
+    fn write(&self, value: Option<&Value>) {
+        match self {
+            FieldCell::Json(cell) => *cell.borrow_mut() = value.cloned(),
+            FieldCell::Bool(cell) => write_typed(cell, value.and_then(Value::as_bool)),
+            FieldCell::I64(cell) => write_typed(cell, value.and_then(Value::as_i64)),
+            FieldCell::F64(cell) => write_typed(cell, value.and_then(Value::as_f64)),
+            FieldCell::Price(cell) => write_typed(
+                cell,
+                value.and_then(|v| serde_json::from_value(v.clone()).ok()),
+            ),
+        }
+    }
+}
+
+fn write_typed<T>(cell: &RefCell<T>, value: Option<T>) {
+    if let Some(value) = value {
+        *cell.borrow_mut() = value;
+    }
+}
+
+pub struct OutputWriter {
+    fields: Vec<(String, FieldCell)>,
+}
+
+impl channels::Writer<Output> for OutputWriter {
+    fn write(&self, output: &Output) {
+        for (field, cell) in &self.fields {
+            cell.write(output.0.as_ref().and_then(|r| r.get(field)));
+        }
+    }
+}
+
+impl channels::ChannelKeys for OutputKeys {
+    fn channel_names(&self) -> Vec<String> {
+        self.0.values().map(|b| b.channel.clone()).collect()
+    }
+}
+
+impl channels::OutputKeys<Output> for OutputKeys {
+    type WriterType = OutputWriter;
+
+    fn writer(
+        &self,
+        registry: &channels::ChannelRegistry,
+    ) -> Result<Self::WriterType, channels::RegistryError> {
+        let fields = self
+            .0
+            .iter()
+            .map(|(field, binding)| Ok((field.clone(), FieldCell::new(registry, binding)?)))
+            .collect::<Result<_, channels::RegistryError>>()?;
+        Ok(OutputWriter { fields })
+    }
+
+    fn register(
+        &self,
+        registry: &mut channels::ChannelRegistry,
+    ) -> Result<(), channels::RegistryError> {
+        for Binding {
+            channel,
+            field_type,
+        } in self.0.values()
+        {
+            match field_type {
+                FieldType::Json => registry.register_output::<Option<Value>>(channel)?,
+                FieldType::Bool => registry.register_output::<bool>(channel)?,
+                FieldType::I64 => registry.register_output::<i64>(channel)?,
+                FieldType::F64 => registry.register_output::<f64>(channel)?,
+                FieldType::Price => registry.register_output::<Price>(channel)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl channels::OutputKeysErased for OutputKeys {
+    fn register_erased(
+        &self,
+        registry: &mut channels::ChannelRegistry,
+    ) -> Result<(), channels::RegistryError> {
+        <Self as channels::OutputKeys<Output>>::register(self, registry)
+    }
+}
+
+impl block_traits::BlockOutput for Output {
+    type Keys = OutputKeys;
+}
+
+#[state]
+pub struct State {
+    /// Index of the record emitted on the next execution.
+    pub next: usize,
+}
+
+#[init_params]
+pub struct InitParams {
+    /// Path to a file holding a JSON array of records.
+    pub path: String,
+}
+
+/// Replays records from a JSON file, one per tick, for backtests that feed
+/// several input signals at once.
+///
+/// The file is read once, on the block's first execution.
+#[block(contract_deps = false)]
+pub struct JsonSourceBlock {
+    pub block_id: u32,
+    path: String,
+    records: OnceCell<Result<Vec<Map<String, Value>>, String>>,
+}
+
+impl BlockSpec for JsonSourceBlock {
+    fn block_id(&self) -> u32 {
+        self.block_id
+    }
+
+    fn set_block_id(&mut self, block_id: u32) {
+        self.block_id = block_id;
+    }
+
+    fn new_from_init_params(params: &InitParams) -> Self {
+        JsonSourceBlock {
+            block_id: 0,
+            path: params.path.clone(),
+            records: OnceCell::new(),
+        }
+    }
+
+    /// Checks that the file exists; its records are only parsed when the
+    /// block first executes.
+    fn validate_init_params(params: &InitParams) -> Result<(), String> {
+        match std::fs::metadata(&params.path) {
+            Ok(metadata) if metadata.is_file() => Ok(()),
+            Ok(_) => Err(format!("'{}' is not a file", params.path)),
+            Err(e) => Err(format!("cannot read records from '{}': {e}", params.path)),
+        }
+    }
+
+    fn init_state(&self) -> State {
+        State { next: 0 }
+    }
+
+    #[execute]
+    fn execute(&self, state: &State) -> Result<(Output, State), execute_status::FailureStatus> {
+        // A file that cannot be read fails every execution.
+        let records = self
+            .records
+            .get_or_init(|| read_records_from_json_file(&self.path).map_err(|e| format!("{e:?}")))
+            .as_ref()
+            .map_err(execute_status::FailureStatus::with_reason)?;
+        let output = Output(records.get(state.next).cloned());
+        let next = (state.next + 1).min(records.len());
+        Ok((output, State { next }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use block_traits::execution_context::MockExecutionContext;
+    use block_traits::{BlockPackage, Effect, ExecuteTrait, Intent};
+    use channels::{ChannelRegistry, Writer};
+    use std::fs;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("json_source_{}_{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn drives_channels_from_records_one_per_tick() {
+        let path = temp_path("two_records");
+        fs::write(
+            &path,
+            r#"[
+                { "signal": true, "level": 1.5 },
+                { "signal": false, "level": 2.5 }
+            ]"#,
+        )
+        .unwrap();
+
+        let keys = OutputKeys(BTreeMap::from([
+            ("signal".to_string(), Binding::new("sig", FieldType::Json)),
+            ("level".to_string(), Binding::new("lvl", FieldType::Json)),
+        ]));
+        let params = InitParams {
+            path: path.to_string_lossy().into_owned(),
+        };
+        let package =
+            BlockPackage::<JsonSourceBlock>::new_checked(InputKeys {}, keys, params, None).unwrap();
+        let mut registry = ChannelRegistry::new();
+        package.register_channels(&mut registry).unwrap();
+        let block = package.weave(&mut registry).unwrap();
+        let sig = registry.get::<Option<Value>>("sig").unwrap();
+        let lvl = registry.get::<Option<Value>>("lvl").unwrap();
+
+        let tick = || {
+            let mut intents = |_intent: &Intent| {};
+            let mut effects = |_effect: Effect| {};
+            block
+                .execute(&MockExecutionContext::new(), &mut intents, &mut effects)
+                .unwrap();
+        };
+
+        tick();
+        assert_eq!(*sig.borrow(), Some(Value::Bool(true)));
+        assert_eq!(*lvl.borrow(), Some(serde_json::json!(1.5)));
+
+        // The records were read on the first execution.
+        fs::remove_file(&path).unwrap();
+        tick();
+        assert_eq!(*sig.borrow(), Some(Value::Bool(false)));
+        assert_eq!(*lvl.borrow(), Some(serde_json::json!(2.5)));
+
+        // End of data.
+        tick();
+        assert_eq!(*sig.borrow(), None);
+        assert_eq!(*lvl.borrow(), None);
+    }
+
+    #[test]
+    fn bindings_read_bare_channel_names_as_json() {
+        let keys: OutputKeys =
+            serde_json::from_str(r#"{ "a": "x", "b": { "channel": "y", "type": "f64" } }"#)
+                .unwrap();
+        assert_eq!(
+            keys,
+            OutputKeys(BTreeMap::from([
+                ("a".to_string(), Binding::new("x", FieldType::Json)),
+                ("b".to_string(), Binding::new("y", FieldType::F64)),
+            ]))
+        );
+        let json = serde_json::to_string(&keys).unwrap();
+        assert_eq!(serde_json::from_str::<OutputKeys>(&json).unwrap(), keys);
+    }
+
+    #[test]
+    fn typed_channels_keep_their_value_without_a_fitting_field() {
+        let keys = OutputKeys(BTreeMap::from([
+            ("n".to_string(), Binding::new("n", FieldType::I64)),
+            ("px".to_string(), Binding::new("px", FieldType::Price)),
+        ]));
+        let mut registry = ChannelRegistry::new();
+        channels::OutputKeys::<Output>::register(&keys, &mut registry).unwrap();
+        let writer = channels::OutputKeys::<Output>::writer(&keys, &registry).unwrap();
+        let n = registry.get::<i64>("n").unwrap();
+        let px = registry.get::<Price>("px").unwrap();
+
+        let record = |json: Value| Output(json.as_object().cloned());
+        writer.write(&record(
+            serde_json::json!({ "n": 3, "px": { "cents": 250 } }),
+        ));
+        writer.write(&record(serde_json::json!({ "n": "three" })));
+        assert_eq!(*n.borrow(), 3);
+        assert_eq!(px.borrow().in_cents().0, 250);
+
+        writer.write(&Output(None));
+        assert_eq!(*n.borrow(), 3);
+    }
+
+    #[test]
+    fn missing_file_fails_validation_and_execution() {
+        let params = InitParams {
+            path: temp_path("missing").to_string_lossy().into_owned(),
+        };
+        assert!(JsonSourceBlock::validate_init_params(&params).is_err());
+
+        let block = JsonSourceBlock::new_from_init_params(&params);
+        let mut effect_handler = |_effect: Effect| {};
+        let result = block.execute(
+            &MockExecutionContext::new(),
+            Input,
+            &block.init_state(),
+            &mut effect_handler,
+        );
+        assert!(result.is_err());
+    }
+}
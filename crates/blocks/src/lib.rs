@@ -1,5 +1,5 @@
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::{self, BufRead, BufReader, Read};
 use std::path::Path;
 
 use block_macros::*;
@@ -14,14 +14,18 @@ use weave::{EmbeddedNode, NodePackage};
 pub mod after;
 pub mod block_io;
 pub mod delete;
+pub mod json_source;
+pub mod plan_config;
+pub mod signal_order;
 pub mod simple_order;
 pub mod sniper;
 
 pub use block_io::*;
+pub use plan_config::PlanConfig;
 
 macro_rules! define_block_type {
     ( $( $variant:ident => $block_ty:path ),+ $(,)? ) => {
-        #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, Serializable)]
+        #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, Serializable)]
         #[serde(tag = "type", content = "data")]
         pub enum BlockPackages {
             $(
@@ -76,6 +80,30 @@ macro_rules! define_block_type {
                 }
                 Ok(())
             }
+            fn register_input_defaults(
+                &self,
+                channels: &mut ::channels::ChannelRegistry,
+            ) -> Result<(), channels::RegistryError> {
+                match self {
+                    $(
+                        BlockPackages::$variant(pkg) => NodePackage::register_input_defaults(pkg, channels),
+                    )+
+                }
+            }
+            fn emits_intents(&self) -> bool {
+                match self {
+                    $(
+                        BlockPackages::$variant(pkg) => NodePackage::emits_intents(pkg),
+                    )+
+                }
+            }
+            fn weave_priority(&self) -> i32 {
+                match self {
+                    $(
+                        BlockPackages::$variant(pkg) => NodePackage::weave_priority(pkg),
+                    )+
+                }
+            }
             fn weave(
                 &self,
                 channels: &mut ::channels::ChannelRegistry,
@@ -86,6 +114,13 @@ macro_rules! define_block_type {
                     )+
                 }
             }
+            fn node_id(&self) -> Option<u32> {
+                match self {
+                    $(
+                        BlockPackages::$variant(pkg) => NodePackage::node_id(pkg),
+                    )+
+                }
+            }
         }
 
         impl EmbeddedNode<BlockPackages> for BlockEmbeddings {
@@ -98,6 +133,107 @@ macro_rules! define_block_type {
             }
         }
 
+        impl ::block_traits::execution_plan::BlockIds for BlockEmbeddings {
+            fn block_ids(&self) -> Vec<u32> {
+                match self {
+                    $(
+                        BlockEmbeddings::$variant(embedded) => embedded.block_ids(),
+                    )+
+                }
+            }
+        }
+
+        impl BlockPackages {
+            /// The `type` names of all block types, as used in the JSON form.
+            pub const TYPE_NAMES: &'static [&'static str] = &[$( stringify!($variant) ),+];
+        }
+
+        /// Deserialize one block from its JSON form, `{ "type": ..., "data": ... }`.
+        ///
+        /// Unlike plain serde deserialization, errors in a known block type name
+        /// the type and the path of the failing field within the block's data.
+        pub fn deserialize_block(mut value: serde_json::Value) -> serialization::Result<BlockPackages> {
+            let block_type = value.get("type").and_then(|t| t.as_str()).map(str::to_string);
+            let data = value.get_mut("data").map(serde_json::Value::take);
+            match (block_type.as_deref(), data) {
+                $(
+                    (Some(stringify!($variant)), Some(data)) => {
+                        serde_path_to_error::deserialize::<_, BlockPackage<$block_ty>>(data)
+                            .map(BlockPackages::$variant)
+                            .map_err(|e| {
+                                let path = e.path().to_string();
+                                serialization::SerializationError::Block {
+                                    block_type: stringify!($variant).to_string(),
+                                    path: (path != ".").then_some(path),
+                                    source: e.into_inner(),
+                                }
+                            })
+                    }
+                )+
+                // Unknown type or missing data; let serde report what is wrong.
+                _ => Ok(serde_json::from_value(value)?),
+            }
+        }
+
+        impl ::block_traits::execution_context::RequiredCapabilities for BlockEmbeddings {
+            fn required_capabilities(&self) -> Vec<::block_traits::Capability> {
+                match self {
+                    $(
+                        BlockEmbeddings::$variant(embedded) => embedded.required_capabilities(),
+                    )+
+                }
+            }
+        }
+
+        // Binary encoding. Bincode cannot read the adjacently tagged JSON
+        // layout of `BlockPackages`, so the binary form goes through a plain
+        // enum with the same variants.
+        #[cfg(feature = "binary")]
+        #[derive(serde::Serialize)]
+        enum BinaryPackageRef<'a> {
+            $(
+                $variant(&'a BlockPackage<$block_ty>),
+            )+
+        }
+
+        #[cfg(feature = "binary")]
+        #[derive(serde::Deserialize)]
+        enum BinaryPackage {
+            $(
+                $variant(BlockPackage<$block_ty>),
+            )+
+        }
+
+        /// Compact binary encoding of block collections, for configurations that
+        /// are loaded often enough for JSON parsing to matter. Encode the parsed
+        /// JSON once with `to_binary` and load the artifact with `from_binary`.
+        #[cfg(feature = "binary")]
+        impl BlockPackages {
+            pub fn to_binary(blocks: &[BlockPackages]) -> Result<Vec<u8>, bincode::Error> {
+                let blocks: Vec<BinaryPackageRef<'_>> = blocks
+                    .iter()
+                    .map(|block| match block {
+                        $(
+                            BlockPackages::$variant(pkg) => BinaryPackageRef::$variant(pkg),
+                        )+
+                    })
+                    .collect();
+                bincode::serialize(&blocks)
+            }
+
+            pub fn from_binary(bytes: &[u8]) -> Result<Vec<BlockPackages>, bincode::Error> {
+                let blocks: Vec<BinaryPackage> = bincode::deserialize(bytes)?;
+                Ok(blocks
+                    .into_iter()
+                    .map(|block| match block {
+                        $(
+                            BinaryPackage::$variant(pkg) => BlockPackages::$variant(pkg),
+                        )+
+                    })
+                    .collect())
+            }
+        }
+
         // Embedded blocks have dependencies
         impl ContractDeps for BlockEmbeddings {
             fn contract_deps(&self) -> Vec<::trade_types::Contract> {
@@ -125,6 +261,19 @@ macro_rules! define_block_type {
                     )+
                 }
             }
+            fn execute_traced(
+                &self,
+                ctx: &C,
+                intent_consumer: &mut I,
+                effect_consumer: &mut E,
+                trace: &mut ::block_traits::execution_plan::BlockTrace,
+            ) -> execute_status::ExecuteResult {
+                match self {
+                    $(
+                        BlockEmbeddings::$variant(embedded) => embedded.execute_traced(ctx, intent_consumer, effect_consumer, trace),
+                    )+
+                }
+            }
         }
     };
 }
@@ -132,7 +281,9 @@ macro_rules! define_block_type {
 define_block_type!(
     After => after::AfterBlock,
     Delete => delete::DeleteBlock,
+    JsonSource => json_source::JsonSourceBlock,
     SimpleOrder => simple_order::SimpleOrderBlock,
+    SignalOrder => signal_order::SignalOrderBlock,
 );
 
 #[cfg(test)]
@@ -200,7 +351,10 @@ mod test {
             AfterOutKey {
                 is_after: "output_is_after".to_string(),
             },
-            AfterInit { time: 123 },
+            AfterInit {
+                time: 123,
+                mode: after::TimeMode::Absolute,
+            },
             None,
         );
 
@@ -227,12 +381,13 @@ mod test {
         let simple_pkg = BlockPackage::<simple_order::SimpleOrderBlock>::new(
             SimpleInKey {
                 should_execute: "should_execute".to_string(),
+                price: None,
             },
             SimpleOutKey {},
             SimpleInit {
                 contract: contract.clone(),
                 side: Side::Buy,
-                price: Price::from(Cents(100)),
+                price: Price::from(Cents(100)).into(),
                 quantity: Quantity::from(trade_types::Kw(1)),
             },
             None,
@@ -367,7 +522,7 @@ mod test {
                     "init_params": {
                         "contract": "TEST",
                         "side": "Buy",
-                        "price": { "cents": 100 },
+                        "price": { "Fixed": { "cents": 100 } },
                         "quantity": { "kw": 10 }
                     }
                 }
@@ -434,6 +589,216 @@ mod test {
         assert!(*cell.borrow());
     }
 
+    #[test]
+    fn woven_blocks_get_their_configured_ids_and_execute_from_a_block() {
+        use block_traits::execution_plan::{BlockIds, ExecutionPlan};
+
+        let json = r#"
+        [
+            {
+                "type": "After",
+                "data": {
+                    "block_id": 7,
+                    "input_keys": {},
+                    "output_keys": { "is_after": "is_after" },
+                    "init_params": { "time": 10 }
+                }
+            },
+            {
+                "type": "Delete",
+                "data": {
+                    "block_id": 3,
+                    "input_keys": { "should_delete": "is_after" },
+                    "output_keys": {},
+                    "init_params": null
+                }
+            }
+        ]
+        "#;
+        let nodes = read_blocktypes_from_json_string(json).unwrap();
+        let mut registry = channels::ChannelRegistry::default();
+        let woven = weave_nodes(&nodes, &mut registry).unwrap();
+        let plan = ExecutionPlan::from(woven);
+        assert_eq!(plan.block_ids(), vec![7, 3]);
+
+        // After would write false at time 5, but only Delete runs, reading the
+        // value already in the channel.
+        *registry.get::<bool>("is_after").unwrap().borrow_mut() = true;
+        let mut effects = vec![];
+        let mut effect_consumer = |effect: Effect| effects.push(effect);
+        let mut intent_consumer = |_: &Intent| {};
+        plan.execute_from(
+            &ExecutionContext { time: 5 },
+            &mut intent_consumer,
+            &mut effect_consumer,
+            3,
+        )
+        .unwrap();
+        assert_eq!(effects, vec![Effect::Terminate]);
+        assert!(*registry.get::<bool>("is_after").unwrap().borrow());
+    }
+
+    #[test]
+    fn block_ids_are_kept_when_nodes_are_added_and_blocks_extracted() {
+        use block_traits::execution_plan::BlockIds;
+
+        let json = r#"
+        [
+            {
+                "type": "After",
+                "data": {
+                    "block_id": 7,
+                    "input_keys": {},
+                    "output_keys": { "is_after": "is_after" },
+                    "init_params": { "time": 10 }
+                }
+            },
+            {
+                "type": "Delete",
+                "data": {
+                    "block_id": 3,
+                    "input_keys": { "should_delete": "is_after" },
+                    "output_keys": {},
+                    "init_params": null
+                }
+            }
+        ]
+        "#;
+        let nodes = read_blocktypes_from_json_string(json).unwrap();
+        let mut registry = channels::ChannelRegistry::default();
+        let woven = weave_nodes(&nodes, &mut registry).unwrap();
+
+        // A new block in front of the others does not renumber them.
+        let mut new_nodes = vec![BlockPackages::After(
+            BlockPackage::new(
+                after::InputKeys {},
+                after::OutputKeys {
+                    is_after: "other".to_string(),
+                },
+                after::InitParams {
+                    time: 1,
+                    mode: after::TimeMode::Absolute,
+                },
+                None,
+            )
+            .with_block_id(12),
+        )];
+        new_nodes.extend(nodes);
+        let rewoven = weave::incremental_weave(&woven, &new_nodes, &mut registry).unwrap();
+        assert_eq!(rewoven.block_ids(), vec![12, 7, 3]);
+
+        let extracted: Vec<BlockPackages> = rewoven.iter().map(|b| b.extract_package()).collect();
+        let reloaded =
+            read_blocktypes_from_json_string(&serde_json::to_string(&extracted).unwrap()).unwrap();
+        let mut fresh = channels::ChannelRegistry::default();
+        assert_eq!(
+            weave_nodes(&reloaded, &mut fresh).unwrap().block_ids(),
+            vec![12, 7, 3]
+        );
+    }
+
+    #[test]
+    fn plans_of_woven_blocks_are_validated_by_their_configured_ids() {
+        use block_traits::execution_plan::{DuplicateBlockId, ExecutionPlan};
+
+        let json = |after_id: u32, delete_id: u32| {
+            format!(
+                r#"
+                [
+                    {{
+                        "type": "After",
+                        "data": {{
+                            "block_id": {after_id},
+                            "input_keys": {{}},
+                            "output_keys": {{ "is_after": "is_after" }},
+                            "init_params": {{ "time": 10 }}
+                        }}
+                    }},
+                    {{
+                        "type": "Delete",
+                        "data": {{
+                            "block_id": {delete_id},
+                            "input_keys": {{ "should_delete": "is_after" }},
+                            "output_keys": {{}},
+                            "init_params": null
+                        }}
+                    }}
+                ]
+                "#
+            )
+        };
+        let weave = |after_id, delete_id| {
+            let nodes = read_blocktypes_from_json_string(&json(after_id, delete_id)).unwrap();
+            weave_nodes(&nodes, &mut channels::ChannelRegistry::default()).unwrap()
+        };
+
+        assert!(ExecutionPlan::new_checked(weave(1, 2), Default::default()).is_ok());
+        assert_eq!(
+            ExecutionPlan::new_checked(weave(2, 2), Default::default()).err(),
+            Some(DuplicateBlockId(2))
+        );
+    }
+
+    #[test]
+    fn woven_blocks_read_derived_channels_refreshed_by_the_plan() {
+        use block_traits::execution_plan::ExecutionPlan;
+
+        let json = r#"
+        [
+            {
+                "type": "After",
+                "data": {
+                    "input_keys": {},
+                    "output_keys": { "is_after": "is_after" },
+                    "init_params": { "time": 10 }
+                }
+            },
+            {
+                "type": "Delete",
+                "data": {
+                    "input_keys": { "should_delete": "after_and_armed" },
+                    "output_keys": {},
+                    "init_params": null
+                }
+            }
+        ]
+        "#;
+        let nodes = read_blocktypes_from_json_string(json).unwrap();
+        let mut registry = channels::ChannelRegistry::default();
+        registry.put("is_after", false).unwrap();
+        registry.put("armed", true).unwrap();
+        registry
+            .put_derived(
+                "after_and_armed",
+                vec!["is_after".to_string(), "armed".to_string()],
+                |r: &channels::ChannelRegistry| {
+                    *r.get::<bool>("is_after").unwrap().borrow()
+                        && *r.get::<bool>("armed").unwrap().borrow()
+                },
+            )
+            .unwrap();
+        let woven = weave_nodes(&nodes, &mut registry).unwrap();
+        let plan = ExecutionPlan::from(woven).with_derived_channels(registry.derived_channels());
+
+        let run = |time: u64| {
+            let mut effects = vec![];
+            let mut effect_consumer = |effect: Effect| effects.push(effect);
+            let mut intent_consumer = |_: &Intent| {};
+            plan.execute(
+                &ExecutionContext { time },
+                &mut intent_consumer,
+                &mut effect_consumer,
+            )
+            .unwrap();
+            effects
+        };
+        assert!(run(5).is_empty());
+        // Delete sees `is_after` as After wrote it earlier in the same tick.
+        assert_eq!(run(11), vec![Effect::Terminate]);
+        *registry.get::<bool>("armed").unwrap().borrow_mut() = false;
+        assert!(run(12).is_empty());
+    }
+
     #[test]
     fn deserialize_invalid_block_type_fails() {
         let json = r#"
@@ -446,6 +811,79 @@ mod test {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn unknown_block_types_are_kept_when_allowed() {
+        let json = r#"
+        [
+            {
+                "type": "After",
+                "data": {
+                    "input_keys": {},
+                    "output_keys": { "is_after": "is_after" },
+                    "init_params": { "time": 1 }
+                }
+            },
+            { "type": "FromTheFuture", "data": { "speed": 88 } }
+        ]
+        "#;
+        assert!(read_blocktypes_from_json_string(json).is_err());
+
+        let entries = read_blocktypes_allowing_unknown(json).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(
+            entries[0].clone().known(),
+            Some(BlockPackages::After(_))
+        ));
+        assert_eq!(
+            entries[1],
+            BlockEntry::Unknown {
+                type_name: "FromTheFuture".to_string(),
+                data: serde_json::json!({ "speed": 88 }),
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_block_reads_valid_block() {
+        let json = serde_json::json!({
+            "type": "After",
+            "data": {
+                "input_keys": {},
+                "output_keys": { "is_after": "is_after" },
+                "init_params": { "time": 1 }
+            }
+        });
+        assert!(matches!(
+            deserialize_block(json).unwrap(),
+            BlockPackages::After(_)
+        ));
+    }
+
+    #[test]
+    fn deserialize_block_error_names_block_type_and_field() {
+        let json = serde_json::json!({
+            "type": "After",
+            "data": {
+                "input_keys": {},
+                "output_keys": { "is_after": "is_after" },
+                "init_params": { "time": "soon" }
+            }
+        });
+        let err = deserialize_block(json).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("After"), "{msg}");
+        assert!(msg.contains("init_params.time"), "{msg}");
+    }
+
+    #[test]
+    fn deserialize_block_unknown_type_fails() {
+        let json = serde_json::json!({ "type": "DoesNotExist", "data": {} });
+        assert!(matches!(
+            deserialize_block(json),
+            Err(serialization::SerializationError::Json(_))
+        ));
+    }
+
     #[test]
     fn read_block_packages_from_json_string_invalid_json_fails() {
         // Malformed JSON to cover serde_json error path from from_str.
@@ -480,6 +918,55 @@ mod test {
         let _ = fs::remove_file(&path);
     }
 
+    #[test]
+    fn block_readers_with_options_interpolate_channel_names() {
+        use serialization::JsonReadOptions;
+
+        std::env::set_var("BLOCK_FLOW_TEST_BLOCKS_CHANNEL", "is_after_env");
+        let after = r#"
+            {
+                "type": "After",
+                "data": {
+                    "input_keys": {},
+                    "output_keys": { "is_after": "${BLOCK_FLOW_TEST_BLOCKS_CHANNEL}" },
+                    "init_params": { "time": 1 }
+                }
+            }
+        "#;
+        let future =
+            r#"{ "type": "Future", "data": { "channel": "${BLOCK_FLOW_TEST_BLOCKS_CHANNEL}" } }"#;
+        let json = format!("[{after}]");
+        let options = JsonReadOptions {
+            interpolate_env: true,
+        };
+        let channels_of = |blocks: &[BlockPackages]| -> Vec<String> {
+            blocks.iter().flat_map(|b| b.output_channels()).collect()
+        };
+
+        let blocks = read_blocktypes_from_json_string_with_options(&json, options).unwrap();
+        assert_eq!(channels_of(&blocks), ["is_after_env"]);
+
+        let path = tmp_path("interpolated");
+        fs::write(&path, &json).unwrap();
+        let blocks = read_blocktypes_from_json_file_with_options(&path, options).unwrap();
+        assert_eq!(channels_of(&blocks), ["is_after_env"]);
+        let _ = fs::remove_file(&path);
+
+        let entries =
+            read_blocktypes_allowing_unknown_with_options(&format!("[{after}, {future}]"), options)
+                .unwrap();
+        match &entries[1] {
+            BlockEntry::Unknown { data, .. } => assert_eq!(data["channel"], "is_after_env"),
+            other => panic!("expected an unknown block, got {other:?}"),
+        }
+        let known: Vec<BlockPackages> = entries.into_iter().filter_map(BlockEntry::known).collect();
+        assert_eq!(channels_of(&known), ["is_after_env"]);
+
+        // Without the option, the pattern is read as is.
+        let blocks = read_blocktypes_from_json_string(&json).unwrap();
+        assert_eq!(channels_of(&blocks), ["${BLOCK_FLOW_TEST_BLOCKS_CHANNEL}"]);
+    }
+
     #[test]
     fn read_block_packages_from_json_file_missing_file_is_io_error() {
         let path = tmp_path("missing");
@@ -507,6 +994,85 @@ mod test {
         let _ = fs::remove_file(&path);
     }
 
+    #[test]
+    fn stream_blocktypes_from_reader_yields_each_block() {
+        let json = r#"
+        [
+            {
+                "type": "After",
+                "data": {
+                    "input_keys": {},
+                    "output_keys": { "is_after": "is_after" },
+                    "init_params": { "time": 1 }
+                }
+            },
+            {
+                "type": "Delete",
+                "data": {
+                    "input_keys": { "should_delete": "is_after" },
+                    "output_keys": {},
+                    "init_params": null
+                }
+            },
+            {
+                "type": "After",
+                "data": {
+                    "input_keys": {},
+                    "output_keys": { "is_after": "later" },
+                    "init_params": { "time": 2 }
+                }
+            }
+        ]
+        "#;
+
+        let path = tmp_path("stream");
+        fs::write(&path, json).unwrap();
+
+        let file = File::open(&path).unwrap();
+        let blocks: Vec<BlockPackages> = stream_blocktypes_from_reader(file)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert!(matches!(blocks[0], BlockPackages::After(_)));
+        assert!(matches!(blocks[1], BlockPackages::Delete(_)));
+        match &blocks[2] {
+            BlockPackages::After(pkg) => assert_eq!(pkg.init_params.time, 2),
+            _ => panic!("Expected After"),
+        }
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn stream_blocktypes_from_reader_handles_empty_and_bad_input() {
+        assert_eq!(stream_blocktypes_from_reader(" [ ] ".as_bytes()).count(), 0);
+
+        let mut not_array = stream_blocktypes_from_reader("{}".as_bytes());
+        assert!(matches!(
+            not_array.next(),
+            Some(Err(ReadBlocksError::Json(_)))
+        ));
+        assert!(not_array.next().is_none());
+
+        // A valid block followed by garbage yields the block, then an error.
+        let json = r#"[{ "type": "After", "data": { "input_keys": {},
+            "output_keys": { "is_after": "x" }, "init_params": { "time": 1 } } } x"#;
+        let mut stream = stream_blocktypes_from_reader(json.as_bytes());
+        assert!(matches!(stream.next(), Some(Ok(BlockPackages::After(_)))));
+        assert!(matches!(stream.next(), Some(Err(ReadBlocksError::Json(_)))));
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn block_types_are_named_after_their_structs() {
+        use block_traits::BlockSpecAssociatedTypes;
+
+        assert_eq!(simple_order::SimpleOrderBlock::NAME, "SimpleOrderBlock");
+        assert_eq!(after::AfterBlock::NAME, "AfterBlock");
+        assert_eq!(delete::DeleteBlock::NAME, "DeleteBlock");
+        assert_eq!(signal_order::SignalOrderBlock::NAME, "SignalOrderBlock");
+    }
+
     #[test]
     fn read_blocks_error_from_conversions_are_covered() {
         // Explicitly cover both From impls without relying on IO/serde formatting.
@@ -525,4 +1091,295 @@ mod test {
             _ => panic!("expected Json"),
         }
     }
+
+    #[test]
+    fn signal_order_writes_output_and_places_intent_in_one_tick() {
+        let after_pkg = BlockPackage::<after::AfterBlock>::new(
+            InKeys::<after::AfterBlock> {},
+            OutKeys::<after::AfterBlock> {
+                is_after: "is_after".to_string(),
+            },
+            Init::<after::AfterBlock> {
+                time: 0,
+                mode: after::TimeMode::Absolute,
+            },
+            None,
+        );
+        let order_pkg = BlockPackage::<signal_order::SignalOrderBlock>::new(
+            InKeys::<signal_order::SignalOrderBlock> {
+                should_execute: "is_after".to_string(),
+            },
+            OutKeys::<signal_order::SignalOrderBlock> {
+                placed: "placed".to_string(),
+            },
+            Init::<signal_order::SignalOrderBlock> {
+                contract: Contract::new("TEST"),
+                side: Side::Sell,
+                price: Price::from(Cents(250)),
+                quantity: Quantity::from(trade_types::Kw(3)),
+            },
+            None,
+        );
+
+        let nodes: Vec<BlockPackages> = vec![order_pkg.into(), after_pkg.into()];
+        let mut registry = channels::ChannelRegistry::default();
+        let weave = weave_nodes(&nodes, &mut registry).unwrap();
+        let placed = registry.get::<bool>("placed").unwrap();
+        assert!(!*placed.borrow());
+
+        let ctx = ExecutionContext { time: 1 };
+        let mut intents = vec![];
+        let mut intent_consumer = |intent: &Intent| intents.push(intent.clone());
+        let mut effect_consumer = |_: Effect| {};
+        weave
+            .execute(&ctx, &mut intent_consumer, &mut effect_consumer)
+            .unwrap();
+
+        assert!(*placed.borrow());
+        match intents.as_slice() {
+            [Intent::Place { price, side, .. }] => {
+                assert_eq!(price.in_cents().0, 250);
+                assert_eq!(*side, Side::Sell);
+            }
+            other => panic!("expected a single Place intent, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn json_source_feeds_a_simple_order_with_typed_fields() {
+        let path = tmp_path("json_source_orders");
+        fs::write(
+            &path,
+            r#"[
+                { "go": false, "px": { "cents": 100 } },
+                { "go": true, "px": { "cents": 4321 } }
+            ]"#,
+        )
+        .unwrap();
+        let json = format!(
+            r#"
+            [
+                {{
+                    "type": "SimpleOrder",
+                    "data": {{
+                        "input_keys": {{ "should_execute": "go", "price": "px" }},
+                        "output_keys": {{}},
+                        "init_params": {{
+                            "contract": "TEST",
+                            "side": "Buy",
+                            "price": {{ "Channel": "px" }},
+                            "quantity": {{ "kw": 1 }}
+                        }}
+                    }}
+                }},
+                {{
+                    "type": "JsonSource",
+                    "data": {{
+                        "input_keys": {{}},
+                        "output_keys": {{
+                            "go": {{ "channel": "go", "type": "bool" }},
+                            "px": {{ "channel": "px", "type": "price" }}
+                        }},
+                        "init_params": {{ "path": {} }}
+                    }}
+                }}
+            ]
+            "#,
+            serde_json::to_string(&path).unwrap()
+        );
+
+        let nodes = read_blocktypes_from_json_string(&json).unwrap();
+        let mut registry = channels::ChannelRegistry::default();
+        let weave = weave_nodes(&nodes, &mut registry).unwrap();
+
+        let ctx = ExecutionContext { time: 0 };
+        let mut intents = vec![];
+        let mut intent_consumer = |intent: &Intent| intents.push(intent.clone());
+        let mut effect_consumer = |_: Effect| {};
+        for _ in 0..2 {
+            weave
+                .execute(&ctx, &mut intent_consumer, &mut effect_consumer)
+                .unwrap();
+        }
+        fs::remove_file(&path).unwrap();
+
+        let places: Vec<u32> = intents
+            .iter()
+            .filter_map(|intent| match intent {
+                Intent::Place { price, .. } => Some(price.in_cents().0),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(places, vec![4321]);
+    }
+
+    #[test]
+    fn sniper_requires_a_context_with_order_books() {
+        use block_traits::execution_context::{
+            check_capabilities, Capability, MissingCapability, MockExecutionContext,
+        };
+
+        let sniper = BlockPackage::<sniper::SniperBlock>::new(
+            sniper::InputKeys {
+                should_execute: "go".to_string(),
+            },
+            sniper::OutputKeys {},
+            sniper::InitParams {
+                contract: Contract::new("C"),
+                side: Side::Buy,
+                quantity: Quantity::from(trade_types::Kw(1)),
+                threshold: Price::from(Cents(100)),
+            },
+            None,
+        );
+        let mut registry = channels::ChannelRegistry::default();
+        registry.put("go", true).unwrap();
+        let sniper = sniper.weave(&mut registry).unwrap();
+
+        // The test context does not advertise the order books it returns.
+        assert_eq!(
+            check_capabilities::<ExecutionContext>(&sniper),
+            Err(MissingCapability(Capability::OrderBook))
+        );
+        assert_eq!(check_capabilities::<MockExecutionContext>(&sniper), Ok(()));
+    }
+
+    mod price_from_channel {
+        use super::*;
+        use block_traits::intents::ZeroIntents;
+
+        pub mod price_producer {
+            use super::*;
+
+            make_defaults!(input, state);
+
+            #[output]
+            pub struct Output {
+                pub price: Price,
+            }
+
+            #[init_params]
+            pub struct InitParams {
+                pub price: Price,
+            }
+
+            #[block(intents = ZeroIntents)]
+            pub struct PriceBlock {
+                price: Price,
+            }
+
+            impl BlockSpec for PriceBlock {
+                fn block_id(&self) -> u32 {
+                    0
+                }
+
+                fn new_from_init_params(params: &InitParams) -> Self {
+                    PriceBlock {
+                        price: params.price.clone(),
+                    }
+                }
+
+                fn init_state(&self) -> State {
+                    State
+                }
+
+                #[execute]
+                fn execute(&self) -> Output {
+                    Output {
+                        price: self.price.clone(),
+                    }
+                }
+            }
+        }
+
+        define_block_type!(
+            Producer => price_producer::PriceBlock,
+            SimpleOrder => simple_order::SimpleOrderBlock,
+        );
+
+        #[test]
+        fn simple_order_places_at_price_from_channel() {
+            let producer = BlockPackage::<price_producer::PriceBlock>::new(
+                price_producer::InputKeys {},
+                price_producer::OutputKeys {
+                    price: "px".to_string(),
+                },
+                price_producer::InitParams {
+                    price: Price::from(Cents(4321)),
+                },
+                None,
+            );
+            let order = BlockPackage::<simple_order::SimpleOrderBlock>::new(
+                simple_order::InputKeys {
+                    should_execute: "go".to_string(),
+                    price: Some("px".to_string()),
+                },
+                simple_order::OutputKeys {},
+                simple_order::InitParams {
+                    contract: Contract::new("TEST"),
+                    side: Side::Buy,
+                    price: simple_order::PriceSource::Channel("px".to_string()),
+                    quantity: Quantity::from(trade_types::Kw(1)),
+                },
+                None,
+            );
+
+            // Order block first, to check that it is sorted after its price producer.
+            let nodes: Vec<BlockPackages> = vec![order.into(), producer.into()];
+            let mut registry = channels::ChannelRegistry::default();
+            registry.put("go", true).unwrap();
+            let weave = weave_nodes(&nodes, &mut registry).unwrap();
+
+            let ctx = ExecutionContext { time: 0 };
+            let mut intents = vec![];
+            let mut intent_consumer = |intent: &Intent| intents.push(intent.clone());
+            let mut effect_consumer = |_: Effect| {};
+            weave
+                .execute(&ctx, &mut intent_consumer, &mut effect_consumer)
+                .unwrap();
+
+            match intents.as_slice() {
+                [Intent::Place { price, .. }] => assert_eq!(price.in_cents().0, 4321),
+                other => panic!("expected a single Place intent, got {other:?}"),
+            }
+        }
+
+        #[test]
+        fn custom_block_sets_read_their_own_type_names() {
+            assert_eq!(BlockPackages::TYPE_NAMES, ["Producer", "SimpleOrder"]);
+
+            let block = deserialize_block(serde_json::json!({
+                "type": "Producer",
+                "data": {
+                    "input_keys": {},
+                    "output_keys": { "price": "px" },
+                    "init_params": { "price": { "cents": 12 } }
+                }
+            }))
+            .unwrap();
+            assert!(matches!(block, BlockPackages::Producer(_)));
+
+            let err = deserialize_block(serde_json::json!({ "type": "After", "data": {} }));
+            assert!(err.is_err());
+        }
+
+        #[cfg(feature = "binary")]
+        #[test]
+        fn plans_of_custom_block_sets_roundtrip_through_binary() {
+            let producer = BlockPackage::<price_producer::PriceBlock>::new(
+                price_producer::InputKeys {},
+                price_producer::OutputKeys {
+                    price: "px".to_string(),
+                },
+                price_producer::InitParams {
+                    price: Price::from(Cents(4321)),
+                },
+                None,
+            );
+            let nodes: Vec<BlockPackages> = vec![producer.into()];
+
+            let bytes = BlockPackages::to_binary(&nodes).unwrap();
+            assert_eq!(BlockPackages::from_binary(&bytes).unwrap(), nodes);
+        }
+    }
 }
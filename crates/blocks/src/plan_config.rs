@@ -0,0 +1,119 @@
+use super::*;
+use channels::{ChannelRegistry, RegistryError};
+use weave::{weave_nodes, TopoOrdered};
+
+/// A whole execution plan as a single serializable artifact.
+///
+/// Where a `BlockPackage` describes a single block, a `PlanConfig` holds the
+/// complete set of block configurations that make up a strategy, so it can be
+/// stored in and restored from one file. The order of `blocks` is preserved
+/// but is not required to be topological; weaving sorts the blocks.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize, Serializable)]
+pub struct PlanConfig {
+    pub blocks: Vec<BlockPackages>,
+}
+
+impl PlanConfig {
+    pub fn new(blocks: Vec<BlockPackages>) -> Self {
+        Self { blocks }
+    }
+
+    /// Serialize the plan to a JSON string.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserialize a plan from a JSON string.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Weave all blocks of the plan into the registry, returning the
+    /// embeddings in topological order.
+    pub fn weave(
+        &self,
+        registry: &mut ChannelRegistry,
+    ) -> Result<TopoOrdered<BlockEmbeddings>, RegistryError> {
+        weave_nodes(&self.blocks, registry)
+    }
+}
+
+impl From<Vec<BlockPackages>> for PlanConfig {
+    fn from(blocks: Vec<BlockPackages>) -> Self {
+        Self::new(blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use block_traits::block_keys::{InKeys, Init, OutKeys};
+
+    fn two_block_plan() -> PlanConfig {
+        let after_pkg = BlockPackage::<after::AfterBlock>::new(
+            InKeys::<after::AfterBlock> {},
+            OutKeys::<after::AfterBlock> {
+                is_after: "is_after".to_string(),
+            },
+            Init::<after::AfterBlock> {
+                time: 7,
+                mode: after::TimeMode::Absolute,
+            },
+            None,
+        );
+        let delete_pkg = BlockPackage::<delete::DeleteBlock>::new(
+            InKeys::<delete::DeleteBlock> {
+                should_delete: "is_after".to_string(),
+            },
+            OutKeys::<delete::DeleteBlock> {},
+            Init::<delete::DeleteBlock> {},
+            None,
+        );
+        PlanConfig::new(vec![after_pkg.into(), delete_pkg.into()])
+    }
+
+    #[test]
+    fn plan_config_json_roundtrip_two_blocks() {
+        let plan = two_block_plan();
+
+        let json = plan.to_json().unwrap();
+        let restored = PlanConfig::from_json(&json).unwrap();
+
+        assert_eq!(restored.blocks.len(), 2);
+        match &restored.blocks[0] {
+            BlockPackages::After(pkg) => {
+                assert_eq!(pkg.init_params.time, 7);
+                assert_eq!(pkg.output_keys.is_after, "is_after");
+            }
+            _ => panic!("Expected After"),
+        }
+        match &restored.blocks[1] {
+            BlockPackages::Delete(pkg) => {
+                assert_eq!(pkg.input_keys.should_delete, "is_after");
+            }
+            _ => panic!("Expected Delete"),
+        }
+
+        // Serializing the restored plan gives the same artifact.
+        assert_eq!(restored.to_json().unwrap(), json);
+    }
+
+    #[test]
+    fn plan_config_from_invalid_json_fails() {
+        assert!(PlanConfig::from_json("{ \"blocks\": 42 }").is_err());
+    }
+
+    #[test]
+    fn plan_config_weave_orders_blocks() {
+        // Put the consumer first to check that weaving sorts the plan.
+        let mut plan = two_block_plan();
+        plan.blocks.reverse();
+
+        let mut registry = ChannelRegistry::default();
+        let woven = plan.weave(&mut registry).unwrap();
+
+        assert_eq!(woven.len(), 2);
+        assert!(matches!(woven[0], BlockEmbeddings::After(_)));
+        assert!(matches!(woven[1], BlockEmbeddings::Delete(_)));
+    }
+}
@@ -0,0 +1,86 @@
+use super::*;
+use block_traits::intents::*;
+use block_traits::BlockSpec;
+use trade_types::*;
+
+make_defaults!(state,);
+
+#[input(defaults)]
+pub struct Input {
+    pub should_execute: bool,
+}
+
+#[output]
+pub struct Output {
+    /// Whether the block placed an order this tick.
+    pub placed: bool,
+}
+
+#[init_params]
+pub struct InitParams {
+    pub contract: Contract,
+    pub side: Side,
+    pub price: Price,
+    pub quantity: Quantity,
+}
+
+/// Places an order like `SimpleOrderBlock` and also signals on its `placed`
+/// output whether it did, so later blocks can react in the same tick.
+#[block(intents = OneIntent)]
+pub struct SignalOrderBlock {
+    pub block_id: u32,
+    contract: Contract,
+    side: Side,
+    price: Price,
+    quantity: Quantity,
+}
+
+impl BlockSpec for SignalOrderBlock {
+    fn block_id(&self) -> u32 {
+        self.block_id
+    }
+
+    fn set_block_id(&mut self, block_id: u32) {
+        self.block_id = block_id;
+    }
+
+    fn new_from_init_params(
+        InitParams {
+            contract,
+            side,
+            price,
+            quantity,
+        }: &InitParams,
+    ) -> Self {
+        SignalOrderBlock {
+            block_id: 0,
+            contract: contract.clone(),
+            side: side.clone(),
+            price: price.clone(),
+            quantity: quantity.clone(),
+        }
+    }
+
+    fn init_state(&self) -> State {
+        State
+    }
+
+    #[execute]
+    fn execute(&self, Input { should_execute }: Input) -> (Output, Self::Intents) {
+        let intent = if should_execute {
+            Intent::Place {
+                contract: self.contract.clone(),
+                side: self.side.clone(),
+                price: self.price.clone(),
+                quantity: self.quantity.clone(),
+                priority: 0,
+            }
+        } else {
+            Intent::NoIntent
+        };
+        let output = Output {
+            placed: should_execute,
+        };
+        (output, OneIntent::new([intent]))
+    }
+}
@@ -5,16 +5,73 @@ use trade_types::*;
 
 make_defaults!(state, output);
 
-#[input]
+#[input(defaults)]
 pub struct Input {
     pub should_execute: bool,
+    /// Price read from the optional `price` channel; used with `PriceSource::Channel`.
+    pub price: Option<Price>,
+}
+
+/// Where the order block gets its price from.
+///
+/// Human-readable formats also accept a bare price, e.g. `{ "cents": 100 }`,
+/// as `Fixed`, the format used before the price could come from a channel,
+/// so existing configurations still load.
+#[derive(PartialEq, Eq, Clone, Debug, serde::Serialize, Serializable)]
+pub enum PriceSource {
+    /// Always use the given price.
+    Fixed(Price),
+    /// Use the price read from the named channel. The channel must also be set as
+    /// the block's `price` input key so it is read and ordered after its producer,
+    /// which is checked when the block is packaged with `new_checked` or woven.
+    Channel(String),
+}
+
+impl<'de> serde::Deserialize<'de> for PriceSource {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        enum Tagged {
+            Fixed(Price),
+            Channel(String),
+        }
+
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Readable {
+            Tagged(Tagged),
+            Bare(Price),
+        }
+
+        // Binary formats carry exactly the serialized variant.
+        let tagged = if deserializer.is_human_readable() {
+            match Readable::deserialize(deserializer)? {
+                Readable::Tagged(tagged) => tagged,
+                Readable::Bare(price) => Tagged::Fixed(price),
+            }
+        } else {
+            Tagged::deserialize(deserializer)?
+        };
+        Ok(match tagged {
+            Tagged::Fixed(price) => PriceSource::Fixed(price),
+            Tagged::Channel(name) => PriceSource::Channel(name),
+        })
+    }
+}
+
+impl From<Price> for PriceSource {
+    fn from(price: Price) -> Self {
+        PriceSource::Fixed(price)
+    }
 }
 
 #[init_params]
 pub struct InitParams {
     pub contract: Contract,
     pub side: Side,
-    pub price: Price,
+    pub price: PriceSource,
     pub quantity: Quantity,
 }
 
@@ -23,25 +80,37 @@ pub struct SimpleOrderBlock {
     pub block_id: u32,
     contract: Contract,
     side: Side,
-    price: Price,
+    price: PriceSource,
     quantity: Quantity,
 }
 
 impl SimpleOrderBlock {
-    fn place_intent(&self) -> Intent {
+    fn place_intent(&self, price: Price) -> Intent {
         Intent::Place {
             contract: self.contract.clone(),
             side: self.side.clone(),
-            price: self.price.clone(),
+            price,
             quantity: self.quantity.clone(),
+            priority: 0,
+        }
+    }
+
+    /// The price to place at: the fixed price, or the value read from the price channel.
+    /// `None` if the block is configured to read its price but no price was read, which
+    /// fails the block's execution.
+    fn price(&self, channel_price: Option<Price>) -> Option<Price> {
+        match &self.price {
+            PriceSource::Fixed(price) => Some(price.clone()),
+            PriceSource::Channel(_) => channel_price,
         }
     }
 
-    fn intents(&self, execute: bool) -> OneIntent {
+    fn intents(&self, execute: bool, channel_price: Option<Price>) -> Option<OneIntent> {
         if execute {
-            OneIntent::new([self.place_intent()])
+            let price = self.price(channel_price)?;
+            Some(OneIntent::new([self.place_intent(price)]))
         } else {
-            OneIntent::new([Intent::NoIntent])
+            Some(OneIntent::new([Intent::NoIntent]))
         }
     }
 }
@@ -51,6 +120,10 @@ impl BlockSpec for SimpleOrderBlock {
         self.block_id
     }
 
+    fn set_block_id(&mut self, block_id: u32) {
+        self.block_id = block_id;
+    }
+
     fn new_from_init_params(
         InitParams {
             contract,
@@ -72,9 +145,29 @@ impl BlockSpec for SimpleOrderBlock {
         State
     }
 
+    fn validate_init_params(params: &InitParams) -> Result<(), String> {
+        match &params.price {
+            PriceSource::Channel(name) if name.is_empty() => {
+                Err("price channel name must not be empty".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_input_keys(params: &InitParams, input_keys: &InputKeys) -> Result<(), String> {
+        match &params.price {
+            PriceSource::Channel(name) if input_keys.price.as_deref() != Some(name.as_str()) => {
+                Err(format!(
+                    "price is read from channel '{name}', but the price input is not wired to it"
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
     #[execute]
-    fn execute(&self, input: Input) -> Self::Intents {
-        self.intents(input.should_execute)
+    fn execute(&self, input: Input) -> Option<Self::Intents> {
+        self.intents(input.should_execute, input.price)
     }
 }
 
@@ -137,7 +230,7 @@ mod tests {
         let params = InitParams {
             contract: contract.clone(),
             side: side.clone(),
-            price: price.clone(),
+            price: price.clone().into(),
             quantity: quantity.clone(),
         };
 
@@ -151,7 +244,7 @@ mod tests {
             block_id: 1,
             contract: contract.clone(),
             side: side.clone(),
-            price: price.clone(),
+            price: price.clone().into(),
             quantity: quantity.clone(),
         };
 
@@ -175,7 +268,7 @@ mod tests {
             block_id: 42,
             contract,
             side,
-            price,
+            price: price.into(),
             quantity,
         };
 
@@ -196,6 +289,7 @@ mod tests {
                 &ctx,
                 Input {
                     should_execute: true,
+                    price: None,
                 },
                 &state,
                 &mut effect_handler,
@@ -211,6 +305,7 @@ mod tests {
                 side: intent_side,
                 price: intent_price,
                 quantity: intent_quantity,
+                ..
             } => {
                 assert_eq!(&contract, intent_contract);
                 assert_eq!(&side, intent_side);
@@ -236,6 +331,7 @@ mod tests {
                 &ctx,
                 Input {
                     should_execute: false,
+                    price: None,
                 },
                 &state,
                 &mut effect_handler,
@@ -257,8 +353,8 @@ mod tests {
     fn intents_helper_matches_execute_behavior() {
         let (_contract, _side, _price, _quantity, block) = test_block();
 
-        let t = block.intents(true).as_slice().to_vec();
-        let f = block.intents(false).as_slice().to_vec();
+        let t = block.intents(true, None).unwrap().as_slice().to_vec();
+        let f = block.intents(false, None).unwrap().as_slice().to_vec();
 
         assert_eq!(t.len(), 1);
         assert_eq!(f.len(), 1);
@@ -266,4 +362,107 @@ mod tests {
         assert!(matches!(t[0], Intent::Place { .. }));
         assert!(matches!(f[0], Intent::NoIntent));
     }
+
+    #[test]
+    fn channel_price_source_uses_input_price() {
+        let (contract, side, _price, quantity, _params) = test_params();
+        let block = SimpleOrderBlock {
+            block_id: 1,
+            contract,
+            side,
+            price: PriceSource::Channel("px".to_string()),
+            quantity,
+        };
+
+        let t = block
+            .intents(true, Some(Price::from(Cents(5))))
+            .unwrap()
+            .as_slice()
+            .to_vec();
+        match &t[0] {
+            Intent::Place { price, .. } => assert_eq!(price.in_cents().0, 5),
+            Intent::NoIntent => panic!("Expected Place intent, got NoIntent"),
+        }
+
+        // Without a price to read, the block fails rather than guessing a price.
+        assert!(block.intents(true, None).is_none());
+        // Not executing does not need a price.
+        assert!(block.intents(false, None).is_some());
+    }
+
+    #[test]
+    fn validate_init_params_rejects_empty_price_channel() {
+        let (contract, side, _price, quantity, _params) = test_params();
+        let mut params = InitParams {
+            contract,
+            side,
+            price: PriceSource::Channel(String::new()),
+            quantity,
+        };
+        assert!(SimpleOrderBlock::validate_init_params(&params).is_err());
+
+        params.price = PriceSource::Channel("px".to_string());
+        assert!(SimpleOrderBlock::validate_init_params(&params).is_ok());
+    }
+
+    #[test]
+    fn price_source_reads_bare_prices_as_fixed() {
+        let read = |json: &str| serde_json::from_str::<PriceSource>(json).unwrap();
+        let fixed = PriceSource::Fixed(Price::from(Cents(5)));
+
+        assert_eq!(read(r#"{ "cents": 5 }"#), fixed);
+        assert_eq!(read(r#"{ "Fixed": { "cents": 5 } }"#), fixed);
+        assert_eq!(
+            read(r#"{ "Channel": "px" }"#),
+            PriceSource::Channel("px".to_string())
+        );
+        assert_eq!(
+            serde_json::to_string(&fixed).unwrap(),
+            r#"{"Fixed":{"cents":5}}"#
+        );
+        assert!(serde_json::from_str::<PriceSource>(r#"{ "Other": 5 }"#).is_err());
+    }
+
+    #[test]
+    fn channel_price_source_must_be_wired_to_the_price_input() {
+        use block_traits::block_weave::BlockPackage;
+        use channels::RegistryError;
+
+        let (contract, side, _price, quantity, _params) = test_params();
+        let params = InitParams {
+            contract,
+            side,
+            price: PriceSource::Channel("px".to_string()),
+            quantity,
+        };
+        let keys = |price: Option<&str>| InputKeys {
+            should_execute: "go".to_string(),
+            price: price.map(str::to_string),
+        };
+        let checked = |price| {
+            BlockPackage::<SimpleOrderBlock>::new_checked(
+                keys(price),
+                OutputKeys {},
+                params.clone(),
+                None,
+            )
+        };
+
+        assert!(matches!(checked(None), Err(RegistryError::InvalidKeys(_))));
+        assert!(matches!(
+            checked(Some("other")),
+            Err(RegistryError::InvalidKeys(_))
+        ));
+        assert!(checked(Some("px")).is_ok());
+
+        // Packages that skipped the check are rejected when woven.
+        let miswired =
+            BlockPackage::<SimpleOrderBlock>::new(keys(None), OutputKeys {}, params, None);
+        let mut registry = channels::ChannelRegistry::default();
+        registry.put("go", true).unwrap();
+        assert!(matches!(
+            miswired.weave(&mut registry),
+            Err(RegistryError::InvalidKeys(_))
+        ));
+    }
 }
@@ -2,13 +2,13 @@ use super::*;
 use block_traits::{
     execution_context::OrderBookTrait,
     intents::{Intent, OneIntent},
-    BlockSpec, ExecutionContextTrait,
+    BlockSpec, Capability, ExecutionContextTrait,
 };
 use trade_types::*;
 
 make_defaults!(state, output);
 
-#[input]
+#[input(defaults)]
 pub struct Input {
     pub should_execute: bool,
 }
@@ -37,6 +37,7 @@ impl SniperBlock {
             side: self.side.clone(),
             price: price.clone(),
             quantity: self.quantity.clone(),
+            priority: 0,
         }
     }
 
@@ -74,6 +75,10 @@ impl BlockSpec for SniperBlock {
         self.block_id
     }
 
+    fn set_block_id(&mut self, block_id: u32) {
+        self.block_id = block_id;
+    }
+
     fn new_from_init_params(
         InitParams {
             contract,
@@ -95,6 +100,11 @@ impl BlockSpec for SniperBlock {
         State
     }
 
+    /// The block snipes from the top of the contract's order book.
+    fn required_capabilities() -> &'static [Capability] {
+        &[Capability::OrderBook]
+    }
+
     #[execute]
     fn execute<C: ExecutionContextTrait>(
         &self,
@@ -104,3 +114,53 @@ impl BlockSpec for SniperBlock {
         self.intents(ctx, should_execute)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use block_traits::execution_context::MockExecutionContext;
+    use block_traits::{BlockIntents, Effect};
+
+    fn sniper(side: Side, threshold: u32) -> SniperBlock {
+        SniperBlock::new_from_init_params(&InitParams {
+            contract: Contract::new("DE-BASE"),
+            side,
+            quantity: Quantity::from(Kw(1)),
+            threshold: Price::from(Cents(threshold)),
+        })
+    }
+
+    fn snipe(block: &SniperBlock, context: &MockExecutionContext) -> Option<Intent> {
+        let mut effects = |_effect: Effect| {};
+        let input = Input {
+            should_execute: true,
+        };
+        let (_, _, intents) = block.execute(context, input, &State, &mut effects).ok()?;
+        Some(intents.as_slice()[0].clone())
+    }
+
+    #[test]
+    fn places_at_the_order_book_price_within_threshold() {
+        let context = MockExecutionContext::new()
+            .with_order_book(Contract::new("DE-BASE"), Price::from(Cents(95)));
+
+        match snipe(&sniper(Side::Buy, 100), &context) {
+            Some(Intent::Place { price, .. }) => assert_eq!(price, Price::from(Cents(95))),
+            other => panic!("expected a place intent, got {other:?}"),
+        }
+        assert_eq!(
+            snipe(&sniper(Side::Buy, 90), &context),
+            Some(Intent::NoIntent)
+        );
+        assert!(matches!(
+            snipe(&sniper(Side::Sell, 90), &context),
+            Some(Intent::Place { .. })
+        ));
+
+        // Without an order book for the contract the block fails.
+        assert_eq!(
+            snipe(&sniper(Side::Buy, 100), &MockExecutionContext::new()),
+            None
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Binary encoding of block packages; run with `--features binary`.
+
+use blocks::{read_blocktypes_from_json_string, BlockPackages};
+
+#[test]
+fn block_packages_binary_roundtrip() {
+    let json = r#"
+    [
+        {
+            "type": "After",
+            "data": {
+                "input_keys": {},
+                "output_keys": { "is_after": "is_after" },
+                "init_params": { "time": 1 }
+            }
+        },
+        {
+            "type": "Delete",
+            "data": {
+                "input_keys": { "should_delete": "is_after" },
+                "output_keys": {},
+                "init_params": null
+            }
+        },
+        {
+            "type": "SimpleOrder",
+            "data": {
+                "input_keys": { "should_execute": "is_after" },
+                "output_keys": {},
+                "init_params": {
+                    "contract": "C",
+                    "side": "Buy",
+                    "price": { "Fixed": { "cents": 100 } },
+                    "quantity": { "kw": 1 }
+                }
+            }
+        },
+        {
+            "type": "SimpleOrder",
+            "data": {
+                "input_keys": { "should_execute": "is_after", "price": "px" },
+                "output_keys": {},
+                "init_params": {
+                    "contract": "C",
+                    "side": "Sell",
+                    "price": { "Channel": "px" },
+                    "quantity": { "kw": 2 }
+                },
+                "state": null
+            }
+        }
+    ]
+    "#;
+    let blocks = read_blocktypes_from_json_string(json).unwrap();
+
+    let bytes = BlockPackages::to_binary(&blocks).unwrap();
+    let restored = BlockPackages::from_binary(&bytes).unwrap();
+    assert_eq!(restored, blocks);
+
+    assert!(BlockPackages::from_binary(&bytes[..bytes.len() / 2]).is_err());
+}
@@ -1,15 +1,77 @@
 use super::errors;
 use super::ChannelRegistry;
+use std::cell::RefCell;
+use std::rc::Rc;
 
 /// Trait for readers that can read values of type T
 pub trait Reader<T> {
     fn read(&self) -> T;
 }
 
+/// Reader that reads several channels of the same type and reduces their
+/// values to one, e.g. their sum or maximum.
+///
+/// This lets a block treat a set of upstream signals as a single input
+/// without an intermediate aggregating block.
+pub struct ReduceReader<T> {
+    channels: Vec<Rc<RefCell<T>>>,
+    reduce: ReduceFn<T>,
+}
+
+type ReduceFn<T> = Box<dyn Fn(&[T]) -> T>;
+
+impl<T: Clone + 'static> ReduceReader<T> {
+    /// Create a reader over the channels named by `keys`. The values are
+    /// passed to `reduce` in the order of `keys`.
+    pub fn new<K: AsRef<str>>(
+        registry: &ChannelRegistry,
+        keys: &[K],
+        reduce: impl Fn(&[T]) -> T + 'static,
+    ) -> Result<Self, errors::RegistryError> {
+        let channels = keys
+            .iter()
+            .map(|key| registry.get::<T>(key))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            channels,
+            reduce: Box::new(reduce),
+        })
+    }
+}
+
+impl<T: Clone> Reader<T> for ReduceReader<T> {
+    fn read(&self) -> T {
+        let values: Vec<T> = self.channels.iter().map(|c| c.borrow().clone()).collect();
+        (self.reduce)(&values)
+    }
+}
+
 /// Trait for keys that work along channels. Used for mapping
 /// input/output keys to their channel names.
 pub trait ChannelKeys: Clone + std::fmt::Debug {
     fn channel_names(&self) -> Vec<String>;
+
+    /// Check that the keys are well-formed.
+    ///
+    /// The default implementation rejects empty channel names and channels
+    /// that are named more than once.
+    fn validate(&self) -> Result<(), errors::RegistryError> {
+        let names = self.channel_names();
+        let mut seen = std::collections::HashSet::new();
+        for name in &names {
+            if name.is_empty() {
+                return Err(errors::RegistryError::InvalidKeys(
+                    "empty channel name".to_string(),
+                ));
+            }
+            if !seen.insert(name) {
+                return Err(errors::RegistryError::InvalidKeys(format!(
+                    "channel '{name}' used more than once"
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Trait for keys that can create readers
@@ -17,6 +79,18 @@ pub trait InputKeys<T>: ChannelKeys {
     type ReaderType: Reader<T>;
     fn reader(&self, registry: &ChannelRegistry)
         -> Result<Self::ReaderType, errors::RegistryError>;
+
+    /// Create the input channels that are not yet in the registry, holding
+    /// their type's default value. Existing channels are left alone.
+    ///
+    /// Used for lenient weaving, where inputs without a producer are allowed.
+    /// The default creates nothing.
+    fn register_defaults(
+        &self,
+        _registry: &mut ChannelRegistry,
+    ) -> Result<(), errors::RegistryError> {
+        Ok(())
+    }
 }
 
 /// Trait for writers that can write values of type T
@@ -31,3 +105,25 @@ pub trait OutputKeys<T>: ChannelKeys {
         -> Result<Self::WriterType, errors::RegistryError>;
     fn register(&self, registry: &mut ChannelRegistry) -> Result<(), errors::RegistryError>;
 }
+
+/// Type-erased output keys, so keys of different output types can be
+/// registered together. Generated by `#[output]` alongside `OutputKeys`.
+pub trait OutputKeysErased {
+    /// Register the output channels, like `OutputKeys::register`.
+    fn register_erased(&self, registry: &mut ChannelRegistry) -> Result<(), errors::RegistryError>;
+}
+
+/// Register the output channels of all `keys` up front.
+///
+/// Once all outputs are registered, readers and writers can be created in any
+/// order, independent of the topological order of the blocks. Stops at the
+/// first error.
+pub fn register_all(
+    keys: &[&dyn OutputKeysErased],
+    registry: &mut ChannelRegistry,
+) -> Result<(), errors::RegistryError> {
+    for k in keys {
+        k.register_erased(registry)?;
+    }
+    Ok(())
+}
@@ -5,11 +5,60 @@ pub enum RegistryError {
     CycleDetected(String),
     DuplicateOutputKey(String),
     MissingProducer(String),
+    /// A writer was requested for an output channel that has not been registered.
+    UnregisteredOutput(String),
+    /// A writer was requested for a constant channel (see `ChannelRegistry::put_const`).
+    ConstChannel(String),
+    /// A writer was requested for a derived channel (see `ChannelRegistry::put_derived`).
+    DerivedChannel(String),
+    /// A second writer was requested for a channel that already has one.
+    DuplicateWriter(String),
+    /// Channel keys are inconsistent, e.g. a channel is both read and written by one block.
+    InvalidKeys(String),
+    /// Initialization parameters were rejected by the block.
+    InvalidInitParams(String),
+    /// A registry snapshot could not be loaded.
+    InvalidSnapshot(String),
+    /// A channel was read while it was mutably borrowed.
+    ChannelBorrowed(String),
     TypeMismatch {
         key: String,
         expected: &'static str,
         found: &'static str,
     },
+    /// Creating the channel would exceed the registry's channel limit.
+    CapacityExceeded {
+        key: String,
+        limit: usize,
+    },
+    /// A block's input reader could not be created for the channel `key`.
+    ReaderConstruction {
+        key: String,
+        reason: Box<RegistryError>,
+    },
+    /// A block's output writer could not be created for the channel `key`.
+    WriterConstruction {
+        key: String,
+        reason: Box<RegistryError>,
+    },
+}
+
+impl RegistryError {
+    /// Attribute the error to creating a reader for the channel `key`.
+    pub fn in_reader(self, key: &str) -> Self {
+        RegistryError::ReaderConstruction {
+            key: key.to_string(),
+            reason: Box::new(self),
+        }
+    }
+
+    /// Attribute the error to creating a writer for the channel `key`.
+    pub fn in_writer(self, key: &str) -> Self {
+        RegistryError::WriterConstruction {
+            key: key.to_string(),
+            reason: Box::new(self),
+        }
+    }
 }
 
 impl std::fmt::Display for RegistryError {
@@ -25,6 +74,23 @@ impl std::fmt::Display for RegistryError {
             RegistryError::MissingProducer(err) => {
                 write!(f, "Missing producer error: {err}")
             }
+            RegistryError::UnregisteredOutput(key) => {
+                write!(f, "Output channel '{key}' written before it was registered")
+            }
+            RegistryError::ConstChannel(key) => {
+                write!(f, "Channel '{key}' is constant and cannot be written")
+            }
+            RegistryError::DerivedChannel(key) => {
+                write!(f, "Channel '{key}' is derived and cannot be written")
+            }
+            RegistryError::InvalidKeys(details) => write!(f, "Invalid channel keys: {details}"),
+            RegistryError::InvalidInitParams(details) => {
+                write!(f, "Invalid init parameters: {details}")
+            }
+            RegistryError::InvalidSnapshot(details) => write!(f, "Invalid snapshot: {details}"),
+            RegistryError::ChannelBorrowed(key) => {
+                write!(f, "Channel '{key}' is mutably borrowed")
+            }
             RegistryError::TypeMismatch {
                 key,
                 expected,
@@ -34,11 +100,32 @@ impl std::fmt::Display for RegistryError {
                 "Type mismatch for key '{}': expected {}, found {}",
                 key, expected, found
             ),
+            RegistryError::CapacityExceeded { key, limit } => write!(
+                f,
+                "Cannot create channel '{key}': registry is limited to {limit} channels"
+            ),
+            RegistryError::DuplicateWriter(key) => {
+                write!(f, "Channel '{key}' already has a writer")
+            }
+            RegistryError::ReaderConstruction { key, reason } => {
+                write!(f, "Cannot create reader for channel '{key}': {reason}")
+            }
+            RegistryError::WriterConstruction { key, reason } => {
+                write!(f, "Cannot create writer for channel '{key}': {reason}")
+            }
         }
     }
 }
 
-impl std::error::Error for RegistryError {}
+impl std::error::Error for RegistryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RegistryError::ReaderConstruction { reason, .. }
+            | RegistryError::WriterConstruction { reason, .. } => Some(reason.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -50,6 +137,36 @@ mod tests {
         assert_eq!(err.to_string(), "Key 'missing' not found in registry");
     }
 
+    #[test]
+    fn display_derived_channel() {
+        let err = RegistryError::DerivedChannel("mid".to_string());
+        assert_eq!(
+            err.to_string(),
+            "Channel 'mid' is derived and cannot be written"
+        );
+    }
+
+    #[test]
+    fn display_duplicate_writer() {
+        let err = RegistryError::DuplicateWriter("out".to_string());
+        assert_eq!(err.to_string(), "Channel 'out' already has a writer");
+    }
+
+    #[test]
+    fn display_reader_and_writer_construction() {
+        let err = RegistryError::KeyNotFound("in".to_string()).in_reader("in");
+        assert_eq!(
+            err.to_string(),
+            "Cannot create reader for channel 'in': Key 'in' not found in registry"
+        );
+        assert!(std::error::Error::source(&err).is_some());
+        let err = RegistryError::ConstChannel("out".to_string()).in_writer("out");
+        assert_eq!(
+            err.to_string(),
+            "Cannot create writer for channel 'out': Channel 'out' is constant and cannot be written"
+        );
+    }
+
     #[test]
     fn display_cycle_detected() {
         let err = RegistryError::CycleDetected("A -> B -> A".to_string());
@@ -68,6 +185,44 @@ mod tests {
         assert_eq!(err.to_string(), "Missing producer error: no producer for x");
     }
 
+    #[test]
+    fn display_unregistered_output() {
+        let err = RegistryError::UnregisteredOutput("out".to_string());
+        assert_eq!(
+            err.to_string(),
+            "Output channel 'out' written before it was registered"
+        );
+    }
+
+    #[test]
+    fn display_const_channel() {
+        let err = RegistryError::ConstChannel("threshold".to_string());
+        assert_eq!(
+            err.to_string(),
+            "Channel 'threshold' is constant and cannot be written"
+        );
+    }
+
+    #[test]
+    fn display_invalid_keys_and_init_params() {
+        let err = RegistryError::InvalidKeys("dup".to_string());
+        assert_eq!(err.to_string(), "Invalid channel keys: dup");
+        let err = RegistryError::InvalidInitParams("bad".to_string());
+        assert_eq!(err.to_string(), "Invalid init parameters: bad");
+    }
+
+    #[test]
+    fn display_invalid_snapshot() {
+        let err = RegistryError::InvalidSnapshot("not an object".to_string());
+        assert_eq!(err.to_string(), "Invalid snapshot: not an object");
+    }
+
+    #[test]
+    fn display_channel_borrowed() {
+        let err = RegistryError::ChannelBorrowed("out".to_string());
+        assert_eq!(err.to_string(), "Channel 'out' is mutably borrowed");
+    }
+
     #[test]
     fn display_type_mismatch() {
         let err = RegistryError::TypeMismatch {
@@ -1,21 +1,28 @@
 pub mod channel_keys;
 pub mod errors;
 pub mod registry;
+pub mod stable_channel;
+#[cfg(feature = "sync")]
+pub mod sync_registry;
 
 pub use channel_keys::*;
 pub use errors::*;
 pub use registry::*;
+pub use stable_channel::StableChannel;
+#[cfg(feature = "sync")]
+pub use sync_registry::*;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::rc::Rc;
 
     #[test]
     fn test_put_and_get() {
         let mut registry = ChannelRegistry::new();
 
         // Put a value
-        registry.put("test_key", 42i32);
+        registry.put("test_key", 42i32).unwrap();
 
         // Get it back
         let value = registry.get::<i32>("test_key").unwrap();
@@ -26,7 +33,9 @@ mod tests {
     fn test_put_and_get_string() {
         let mut registry = ChannelRegistry::new();
 
-        registry.put("message", "Hello, World!".to_string());
+        registry
+            .put("message", "Hello, World!".to_string())
+            .unwrap();
 
         let value = registry.get::<String>("message").unwrap();
         assert_eq!(*value.borrow(), "Hello, World!");
@@ -47,7 +56,7 @@ mod tests {
     fn test_get_wrong_type() {
         let mut registry = ChannelRegistry::new();
 
-        registry.put("number", 42i32);
+        registry.put("number", 42i32).unwrap();
 
         let result = registry.get::<String>("number");
         match result {
@@ -74,7 +83,7 @@ mod tests {
     fn test_ensure_existing_key() {
         let mut registry = ChannelRegistry::new();
 
-        registry.put("existing", 42i32);
+        registry.put("existing", 42i32).unwrap();
 
         let value = registry.ensure::<i32>("existing").unwrap();
         assert_eq!(*value.borrow(), 42); // Should get existing value
@@ -87,6 +96,202 @@ mod tests {
         assert_eq!(*retrieved.borrow(), 100);
     }
 
+    #[test]
+    fn test_ensure_reporting_flags_creation_only_once() {
+        let mut registry = ChannelRegistry::new();
+
+        let (first, created) = registry.ensure_reporting::<i32>("out").unwrap();
+        assert!(created);
+        *first.borrow_mut() = 7;
+
+        let (second, created) = registry.ensure_reporting::<i32>("out").unwrap();
+        assert!(!created);
+        assert!(Rc::ptr_eq(&first, &second));
+
+        // Channels created by other means are not reported as created either.
+        registry.put("put", 1_i32).unwrap();
+        assert!(!registry.ensure_reporting::<i32>("put").unwrap().1);
+    }
+
+    #[test]
+    fn test_peek_borrows_without_cloning() {
+        // Not `Clone`, so the value cannot be read through a clone.
+        #[derive(Debug)]
+        struct Sample(#[allow(dead_code)] u32);
+
+        let mut registry = ChannelRegistry::new();
+        registry
+            .put("samples", vec![Sample(1), Sample(2), Sample(3)])
+            .unwrap();
+
+        assert_eq!(registry.peek("samples", Vec::<Sample>::len), Ok(3));
+        assert_eq!(
+            registry.peek("missing", Vec::<Sample>::len),
+            Err(RegistryError::KeyNotFound("missing".to_string()))
+        );
+        assert!(matches!(
+            registry.peek("samples", |v: &u32| *v),
+            Err(RegistryError::TypeMismatch { .. })
+        ));
+
+        let channel = registry.get::<Vec<Sample>>("samples").unwrap();
+        let _writing = channel.borrow_mut();
+        assert_eq!(
+            registry.peek("samples", Vec::<Sample>::len),
+            Err(RegistryError::ChannelBorrowed("samples".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_stable_channel_follows_replaced_channel() {
+        let mut registry = ChannelRegistry::new();
+        registry.put("level", 1_i32).unwrap();
+        let held = registry.get::<i32>("level").unwrap();
+        let stable = StableChannel::<i32>::new("level");
+
+        // Re-weaving replaces the channel's storage.
+        registry.put("level", 0_i32).unwrap();
+        *registry.get::<i32>("level").unwrap().borrow_mut() = 5;
+
+        assert_eq!(*held.borrow(), 1);
+        assert_eq!(stable.read(&registry), Ok(5));
+
+        stable.write(&registry, 7).unwrap();
+        assert_eq!(*registry.get::<i32>("level").unwrap().borrow(), 7);
+        assert_eq!(
+            StableChannel::<i32>::new("missing").read(&registry),
+            Err(RegistryError::KeyNotFound("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_for_each_of_type_visits_only_that_type() {
+        let mut registry = ChannelRegistry::new();
+        registry.put("buy", true).unwrap();
+        registry.put("sell", true).unwrap();
+        registry.put("level", 3_i32).unwrap();
+        registry.alias("signal", "buy").unwrap();
+
+        let mut visited = Vec::new();
+        registry.for_each_of_type::<bool>(|key, value| {
+            visited.push(key.to_string());
+            *value.borrow_mut() = false;
+        });
+        assert_eq!(visited, ["buy", "sell"]);
+        assert!(!*registry.get::<bool>("buy").unwrap().borrow());
+        assert!(!*registry.get::<bool>("sell").unwrap().borrow());
+        assert_eq!(*registry.get::<i32>("level").unwrap().borrow(), 3);
+    }
+
+    #[test]
+    fn test_derived_channel_recomputes_from_its_dependencies() {
+        let mut registry = ChannelRegistry::new();
+        registry.put("bid", 100.0_f64).unwrap();
+        registry.put("ask", 102.0_f64).unwrap();
+        registry
+            .put_derived(
+                "midpoint",
+                vec!["bid".to_string(), "ask".to_string()],
+                |r: &ChannelRegistry| {
+                    let bid = *r.get::<f64>("bid").unwrap().borrow();
+                    let ask = *r.get::<f64>("ask").unwrap().borrow();
+                    (bid + ask) / 2.0
+                },
+            )
+            .unwrap();
+        assert_eq!(*registry.get::<f64>("midpoint").unwrap().borrow(), 101.0);
+
+        *registry.get::<f64>("ask").unwrap().borrow_mut() = 110.0;
+        assert_eq!(*registry.get::<f64>("midpoint").unwrap().borrow(), 105.0);
+    }
+
+    #[test]
+    fn test_derived_channel_cycles_are_rejected() {
+        let mut registry = ChannelRegistry::new();
+        registry.put("a", 1_i32).unwrap();
+        registry
+            .put_derived("b", vec!["a".to_string()], |r: &ChannelRegistry| {
+                *r.get::<i32>("a").unwrap().borrow() + 1
+            })
+            .unwrap();
+
+        let err = registry
+            .put_derived("a", vec!["b".to_string()], |r: &ChannelRegistry| {
+                *r.get::<i32>("b").unwrap().borrow() + 1
+            })
+            .unwrap_err();
+        assert!(matches!(err, RegistryError::CycleDetected(_)));
+        assert!(registry
+            .put_derived("c", vec!["c".to_string()], |_: &ChannelRegistry| 0_i32)
+            .is_err());
+    }
+
+    #[test]
+    fn test_derived_channels_cannot_be_written_and_refresh_through_a_handle() {
+        let mut registry = ChannelRegistry::new();
+        registry.put("a", 1_i32).unwrap();
+        registry
+            .put_derived("b", vec!["a".to_string()], |r: &ChannelRegistry| {
+                *r.get::<i32>("a").unwrap().borrow() * 10
+            })
+            .unwrap();
+        assert_eq!(
+            registry.get_output::<i32>("b").unwrap_err(),
+            RegistryError::DerivedChannel("b".to_string())
+        );
+
+        // A reader holding on to the channel only sees refreshed values.
+        let held = registry.get::<i32>("b").unwrap();
+        let derived = registry.derived_channels();
+        *registry.get::<i32>("a").unwrap().borrow_mut() = 2;
+        assert_eq!(*held.borrow(), 10);
+        derived.refresh();
+        assert_eq!(*held.borrow(), 20);
+    }
+
+    #[test]
+    fn test_reduce_reader_sums_channels() {
+        let mut registry = ChannelRegistry::new();
+        registry.put("a", 1_i32).unwrap();
+        registry.put("b", 2_i32).unwrap();
+        registry.put("c", 3_i32).unwrap();
+
+        let sum =
+            ReduceReader::new(&registry, &["a", "b", "c"], |v: &[i32]| v.iter().sum()).unwrap();
+        assert_eq!(sum.read(), 6);
+
+        // The reader sees later writes to the channels.
+        *registry.get::<i32>("b").unwrap().borrow_mut() = 10;
+        assert_eq!(sum.read(), 14);
+
+        assert!(matches!(
+            ReduceReader::new(&registry, &["a", "missing"], |v: &[i32]| v[0]),
+            Err(errors::RegistryError::KeyNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_or_insert_with_builds_only_missing_channels() {
+        use trade_types::Contract;
+
+        let mut registry = ChannelRegistry::new();
+        let contract = registry
+            .get_or_insert_with("contract", || Contract::new("DE-BASE"))
+            .unwrap();
+        assert_eq!(*contract.borrow(), Contract::new("DE-BASE"));
+
+        // An existing channel is returned as is, without calling the closure.
+        let again = registry
+            .get_or_insert_with::<Contract>("contract", || panic!("channel exists"))
+            .unwrap();
+        assert!(Rc::ptr_eq(&contract, &again));
+
+        assert!(matches!(
+            registry.get_or_insert_with("contract", || 0_i32),
+            Err(errors::RegistryError::TypeMismatch { .. })
+        ));
+    }
+
     #[test]
     fn test_ensure_with_custom_default() {
         let mut registry = ChannelRegistry::new();
@@ -104,7 +309,7 @@ mod tests {
     fn test_mutable_access() {
         let mut registry = ChannelRegistry::new();
 
-        registry.put("counter", 0i32);
+        registry.put("counter", 0i32).unwrap();
 
         let counter = registry.get::<i32>("counter").unwrap();
         *counter.borrow_mut() += 1;
@@ -121,7 +326,7 @@ mod tests {
     fn test_multiple_references() {
         let mut registry = ChannelRegistry::new();
 
-        registry.put("shared", vec![1, 2, 3]);
+        registry.put("shared", vec![1, 2, 3]).unwrap();
 
         let ref1 = registry.get::<Vec<i32>>("shared").unwrap();
         let ref2 = registry.get::<Vec<i32>>("shared").unwrap();
@@ -136,4 +341,354 @@ mod tests {
         // Other reference should see the change
         assert_eq!(*ref2.borrow(), vec![1, 2, 3, 4]);
     }
+
+    #[test]
+    fn test_alias_reads_target_value() {
+        let mut registry = ChannelRegistry::new();
+        registry.put("price", 42i32).unwrap();
+
+        registry.alias("px", "price").unwrap();
+        assert!(registry.has("px"));
+
+        let through_alias = registry.get::<i32>("px").unwrap();
+        assert_eq!(*through_alias.borrow(), 42);
+
+        // Writes through either name are visible through the other.
+        *through_alias.borrow_mut() = 7;
+        assert_eq!(*registry.get::<i32>("price").unwrap().borrow(), 7);
+    }
+
+    #[test]
+    fn test_alias_of_alias_resolves_to_target() {
+        let mut registry = ChannelRegistry::new();
+        registry.put("a", 1i32).unwrap();
+        registry.alias("b", "a").unwrap();
+        registry.alias("c", "b").unwrap();
+
+        let a = registry.get::<i32>("a").unwrap();
+        let c = registry.get::<i32>("c").unwrap();
+        assert!(Rc::ptr_eq(&a, &c));
+    }
+
+    #[test]
+    fn test_alias_missing_target_errors() {
+        let mut registry = ChannelRegistry::new();
+        assert_eq!(
+            registry.alias("alias", "missing"),
+            Err(RegistryError::KeyNotFound("missing".to_string()))
+        );
+        assert!(!registry.has("alias"));
+    }
+
+    #[test]
+    fn test_alias_existing_key_errors() {
+        let mut registry = ChannelRegistry::new();
+        registry.put("a", 1i32).unwrap();
+        registry.put("b", 2i32).unwrap();
+        assert_eq!(
+            registry.alias("b", "a"),
+            Err(RegistryError::DuplicateOutputKey("b".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_get_output_unregistered_errors() {
+        let registry = ChannelRegistry::new();
+        assert_eq!(
+            registry.get_output::<i32>("out"),
+            Err(RegistryError::UnregisteredOutput("out".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_get_output_registered() {
+        let mut registry = ChannelRegistry::new();
+        registry.ensure::<i32>("out").unwrap();
+        let out = registry.get_output::<i32>("out").unwrap();
+        *out.borrow_mut() = 3;
+        assert_eq!(*registry.get::<i32>("out").unwrap().borrow(), 3);
+    }
+
+    #[test]
+    fn test_second_writer_for_a_channel_errors() {
+        let mut registry = ChannelRegistry::new();
+        registry.ensure::<i32>("out").unwrap();
+        registry.alias("result", "out").unwrap();
+        registry.get_output::<i32>("out").unwrap();
+
+        for key in ["out", "result"] {
+            assert_eq!(
+                registry.get_output::<i32>(key),
+                Err(RegistryError::DuplicateWriter(key.to_string()))
+            );
+        }
+
+        // Released writers, e.g. before re-weaving, can be replaced once.
+        registry.release_writers();
+        assert!(registry.get_output::<i32>("out").is_ok());
+        assert!(registry.get_output::<i32>("out").is_err());
+
+        // Explicitly shared channels take any number of writers.
+        registry.allow_multiple_writers("result");
+        assert!(registry.get_output::<i32>("out").is_ok());
+        assert!(registry.get_output::<i32>("result").is_ok());
+    }
+
+    #[test]
+    fn test_writers_are_released_per_channel_and_rolled_back_on_error() {
+        let mut registry = ChannelRegistry::new();
+        registry.ensure::<i32>("a").unwrap();
+        registry.ensure::<i32>("b").unwrap();
+        registry.get_output::<i32>("a").unwrap();
+        registry.get_output::<i32>("b").unwrap();
+
+        let failed: Result<(), RegistryError> = registry.with_writer_rollback(|registry| {
+            registry.release_writers_of(["a"]);
+            registry.get_output::<i32>("a")?;
+            registry.get_output::<i32>("b").map(|_| ())
+        });
+        assert!(failed.is_err());
+        // The failed call's release and new writer are both undone.
+        assert!(registry.get_output::<i32>("a").is_err());
+
+        registry.release_writers_of(["a"]);
+        assert!(registry.get_output::<i32>("a").is_ok());
+        assert!(registry.get_output::<i32>("b").is_err());
+    }
+
+    #[test]
+    fn test_remove_drops_channel_and_its_aliases() {
+        let mut registry = ChannelRegistry::new();
+        registry.put_const("limit", 5i32).unwrap();
+        registry.alias("max", "limit").unwrap();
+        registry.put("other", 1i32).unwrap();
+
+        assert!(registry.remove("max"));
+        assert!(!registry.has("limit"));
+        assert!(!registry.has("max"));
+        assert!(registry.has("other"));
+        assert!(!registry.remove("limit"));
+
+        // The key is free for a fresh, writable channel.
+        registry.put("limit", 7i32).unwrap();
+        assert!(!registry.is_const("limit"));
+        assert!(registry.get_output::<i32>("limit").is_ok());
+    }
+
+    #[test]
+    fn test_register_output_records_only_channels_it_creates() {
+        let mut registry = ChannelRegistry::new();
+        registry.ensure_serializable::<i32>("user").unwrap();
+
+        registry.register_output::<i32>("user").unwrap();
+        registry.register_output::<i32>("woven").unwrap();
+        assert!(!registry.is_woven_output("user"));
+        assert!(registry.is_woven_output("woven"));
+        assert!(registry.fork().is_woven_output("woven"));
+
+        registry.remove("woven");
+        registry.put("woven", 1i32).unwrap();
+        assert!(!registry.is_woven_output("woven"));
+    }
+
+    #[test]
+    fn test_const_channel_reads_but_rejects_writers() {
+        let mut registry = ChannelRegistry::new();
+        registry.put_const("threshold", 42_i32).unwrap();
+        registry.alias("limit", "threshold").unwrap();
+
+        assert!(registry.is_const("threshold"));
+        assert_eq!(*registry.get::<i32>("threshold").unwrap().borrow(), 42);
+        for key in ["threshold", "limit"] {
+            assert_eq!(
+                registry.get_output::<i32>(key),
+                Err(RegistryError::ConstChannel(key.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_put_replaces_const_serializable_and_cloneable_channels() {
+        let mut registry = ChannelRegistry::new();
+        registry.put_const("limit", 5i32).unwrap();
+        registry.put_serializable("price", 10i32).unwrap();
+        registry.put_cloneable("name", "a".to_string()).unwrap();
+
+        registry.put("limit", 6i32).unwrap();
+        registry.put("price", "not a number".to_string()).unwrap();
+        registry.put("name", 1u8).unwrap();
+
+        assert!(!registry.is_const("limit"));
+        assert!(registry.get_output::<i32>("limit").is_ok());
+        assert_eq!(registry.dump_serializable(), serde_json::json!({}));
+        assert_eq!(
+            registry.fork().skipped_on_fork(),
+            ["limit", "name", "price"]
+        );
+    }
+
+    #[derive(Clone, Debug)]
+    struct NamedKeys(Vec<String>);
+
+    impl ChannelKeys for NamedKeys {
+        fn channel_names(&self) -> Vec<String> {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn test_default_validate_accepts_distinct_names() {
+        let keys = NamedKeys(vec!["a".to_string(), "b".to_string()]);
+        assert!(keys.validate().is_ok());
+    }
+
+    #[test]
+    fn test_default_validate_rejects_duplicates_and_empty_names() {
+        let dup = NamedKeys(vec!["a".to_string(), "a".to_string()]);
+        assert!(matches!(dup.validate(), Err(RegistryError::InvalidKeys(_))));
+
+        let empty = NamedKeys(vec![String::new()]);
+        assert!(matches!(
+            empty.validate(),
+            Err(RegistryError::InvalidKeys(_))
+        ));
+    }
+
+    #[test]
+    fn test_dump_and_load_serializable_into_fresh_registry() {
+        let mut registry = ChannelRegistry::new();
+        registry.put_serializable("count", 42i32).unwrap();
+        *registry
+            .ensure_serializable::<String>("name")
+            .unwrap()
+            .borrow_mut() = "block".to_string();
+        registry.put("opaque", std::cell::Cell::new(1u8)).unwrap();
+
+        let snapshot = registry.dump_serializable();
+        assert_eq!(
+            snapshot,
+            serde_json::json!({ "count": 42, "name": "block" })
+        );
+
+        let mut fresh = ChannelRegistry::new();
+        fresh.ensure_serializable::<i32>("count").unwrap();
+        fresh.ensure_serializable::<String>("name").unwrap();
+        fresh.load_serializable(&snapshot).unwrap();
+
+        assert_eq!(*fresh.get::<i32>("count").unwrap().borrow(), 42);
+        assert_eq!(*fresh.get::<String>("name").unwrap().borrow(), "block");
+    }
+
+    #[test]
+    fn test_load_serializable_skips_unknown_and_rejects_bad_values() {
+        let mut registry = ChannelRegistry::new();
+        registry.ensure_serializable::<i32>("count").unwrap();
+
+        // Unknown channels are skipped.
+        registry
+            .load_serializable(&serde_json::json!({ "other": 1 }))
+            .unwrap();
+        assert!(!registry.has("other"));
+
+        assert!(matches!(
+            registry.load_serializable(&serde_json::json!({ "count": "x" })),
+            Err(RegistryError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            registry.load_serializable(&serde_json::json!([1, 2])),
+            Err(RegistryError::InvalidSnapshot(_))
+        ));
+    }
+
+    #[test]
+    fn test_fork_is_independent_of_original() {
+        let mut registry = ChannelRegistry::new();
+        registry.put_cloneable("prices", vec![1, 2, 3]).unwrap();
+        registry.put_serializable("count", 7i32).unwrap();
+        registry.put("opaque", Rc::new(5)).unwrap();
+        registry.alias("quotes", "prices").unwrap();
+
+        let fork = registry.fork();
+        assert_eq!(fork.skipped_on_fork(), ["opaque".to_string()]);
+        assert!(!fork.has("opaque"));
+        assert!(registry.skipped_on_fork().is_empty());
+
+        fork.get::<Vec<i32>>("quotes").unwrap().borrow_mut().push(4);
+        *fork.get::<i32>("count").unwrap().borrow_mut() = 8;
+
+        assert_eq!(
+            *registry.get::<Vec<i32>>("prices").unwrap().borrow(),
+            [1, 2, 3]
+        );
+        assert_eq!(*registry.get::<i32>("count").unwrap().borrow(), 7);
+        assert_eq!(
+            *fork.get::<Vec<i32>>("prices").unwrap().borrow(),
+            [1, 2, 3, 4]
+        );
+        assert_eq!(*fork.get::<i32>("count").unwrap().borrow(), 8);
+
+        // Forked channels keep their kind, so a fork can be forked again.
+        let again = fork.fork();
+        assert!(again.skipped_on_fork().is_empty());
+        assert_eq!(again.dump_serializable(), serde_json::json!({ "count": 8 }));
+    }
+
+    #[test]
+    fn test_fork_recomputes_derived_channels_and_keeps_shared_writers() {
+        let mut registry = ChannelRegistry::new();
+        registry.put_cloneable("bid", 100i32).unwrap();
+        registry.alias("best_bid", "bid").unwrap();
+        registry
+            .put_derived(
+                "double",
+                vec!["best_bid".to_string()],
+                |r: &ChannelRegistry| *r.get::<i32>("best_bid").unwrap().borrow() * 2,
+            )
+            .unwrap();
+        registry
+            .put_derived("quad", vec!["double".to_string()], |r: &ChannelRegistry| {
+                *r.get::<i32>("double").unwrap().borrow() * 2
+            })
+            .unwrap();
+        registry.allow_multiple_writers("bid");
+
+        let fork = registry.fork();
+        assert!(fork.skipped_on_fork().is_empty());
+        *fork.get::<i32>("bid").unwrap().borrow_mut() = 1;
+        assert_eq!(*fork.get::<i32>("quad").unwrap().borrow(), 4);
+        assert_eq!(*registry.get::<i32>("quad").unwrap().borrow(), 400);
+
+        assert!(fork.get_output::<i32>("bid").is_ok());
+        assert!(fork.get_output::<i32>("bid").is_ok());
+    }
+
+    #[test]
+    fn test_max_channels_limits_channel_creation() {
+        let mut registry = ChannelRegistry::with_max_channels(2);
+        registry.put_cloneable("a", 1i32).unwrap();
+        registry.ensure_cloneable::<i32>("b").unwrap();
+
+        let exceeded = || RegistryError::CapacityExceeded {
+            key: "c".to_string(),
+            limit: 2,
+        };
+        assert_eq!(registry.put("c", 3i32), Err(exceeded()));
+        assert_eq!(registry.ensure::<i32>("c").unwrap_err(), exceeded());
+        assert!(registry
+            .ensure::<i32>("c")
+            .unwrap_err()
+            .to_string()
+            .contains("limited to 2"));
+        assert!(!registry.has("c"));
+
+        // Replacing or looking up existing channels and aliasing are still allowed.
+        registry.put_cloneable("a", 10i32).unwrap();
+        registry.ensure::<i32>("b").unwrap();
+        registry.alias("c", "a").unwrap();
+        assert_eq!(*registry.get::<i32>("c").unwrap().borrow(), 10);
+
+        // Forks keep the limit.
+        let mut fork = registry.fork();
+        assert!(fork.put("d", 1i32).is_err());
+    }
 }
@@ -1,13 +1,115 @@
 use std::any::Any;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
+use serde::{de::DeserializeOwned, Serialize};
+
 use super::errors;
 
+type DumpFn = fn(&Rc<dyn Any>) -> Option<serde_json::Value>;
+type LoadFn = fn(&str, &Rc<dyn Any>, &serde_json::Value) -> Result<(), errors::RegistryError>;
+type ForkFn = fn(&Rc<dyn Any>) -> Option<Rc<dyn Any>>;
+type RefreshFn = Rc<dyn Fn(&ChannelRegistry, &Rc<dyn Any>)>;
+type CreateFn = Rc<dyn Fn(&ChannelRegistry) -> Rc<dyn Any>>;
+
+/// Type-erased JSON conversion for a channel, recorded when the channel is
+/// created through one of the `*_serializable` methods.
+#[derive(Clone, Copy)]
+struct Codec {
+    dump: DumpFn,
+    load: LoadFn,
+    fork: ForkFn,
+}
+
+impl Codec {
+    fn of<T: Serialize + DeserializeOwned + 'static>() -> Self {
+        Codec {
+            dump: dump_value::<T>,
+            load: load_value::<T>,
+            fork: fork_serialized::<T>,
+        }
+    }
+}
+
+fn dump_value<T: Serialize + 'static>(value: &Rc<dyn Any>) -> Option<serde_json::Value> {
+    let cell = value.downcast_ref::<RefCell<T>>()?;
+    serde_json::to_value(&*cell.borrow()).ok()
+}
+
+fn load_value<T: DeserializeOwned + 'static>(
+    key: &str,
+    value: &Rc<dyn Any>,
+    json: &serde_json::Value,
+) -> Result<(), errors::RegistryError> {
+    let mismatch = |found| errors::RegistryError::TypeMismatch {
+        key: key.to_string(),
+        expected: std::any::type_name::<T>(),
+        found,
+    };
+    let cell = value
+        .downcast_ref::<RefCell<T>>()
+        .ok_or_else(|| mismatch("unknown"))?;
+    *cell.borrow_mut() = T::deserialize(json).map_err(|_| mismatch("incompatible JSON value"))?;
+    Ok(())
+}
+
+/// Copy a channel value through a JSON round trip.
+fn fork_serialized<T: Serialize + DeserializeOwned + 'static>(
+    value: &Rc<dyn Any>,
+) -> Option<Rc<dyn Any>> {
+    let cell = value.downcast_ref::<RefCell<T>>()?;
+    let json = serde_json::to_value(&*cell.borrow()).ok()?;
+    let copy = serde_json::from_value::<T>(json).ok()?;
+    Some(Rc::new(RefCell::new(copy)))
+}
+
+/// Copy a channel value with `Clone`.
+fn fork_cloned<T: Clone + 'static>(value: &Rc<dyn Any>) -> Option<Rc<dyn Any>> {
+    let cell = value.downcast_ref::<RefCell<T>>()?;
+    let copy = cell.borrow().clone();
+    Some(Rc::new(RefCell::new(copy)))
+}
+
+/// A channel computed from other channels, see `ChannelRegistry::put_derived`.
+#[derive(Clone)]
+struct Derived {
+    deps: Vec<String>,
+    refresh: RefreshFn,
+    /// Computes a fresh value for the channel, used when forking.
+    create: CreateFn,
+}
+
+/// Recomputes the derived channels of a registry, see
+/// `ChannelRegistry::derived_channels`.
+pub struct DerivedChannels {
+    /// Shares the channels of the registry the handle was taken from.
+    registry: ChannelRegistry,
+}
+
+impl DerivedChannels {
+    /// Recompute every derived channel from its dependencies' current values.
+    pub fn refresh(&self) {
+        self.registry.refresh_derived();
+    }
+}
+
 /// The registry for storing typed values
 pub struct ChannelRegistry {
     store: HashMap<String, Rc<dyn Any>>,
+    aliases: HashMap<String, String>,
+    codecs: HashMap<String, Codec>,
+    cloners: HashMap<String, ForkFn>,
+    consts: HashSet<String>,
+    derived: HashMap<String, Derived>,
+    skipped_on_fork: Vec<String>,
+    max_channels: Option<usize>,
+    /// Channels a writer has been created for with `get_output`.
+    writers: RefCell<HashSet<String>>,
+    /// Channels allowed to have more than one writer.
+    shared_writers: HashSet<String>,
+    /// Channels created by `register_output`.
+    woven_outputs: HashSet<String>,
 }
 
 impl ChannelRegistry {
@@ -15,18 +117,223 @@ impl ChannelRegistry {
     pub fn new() -> Self {
         Self {
             store: HashMap::new(),
+            aliases: HashMap::new(),
+            codecs: HashMap::new(),
+            cloners: HashMap::new(),
+            consts: HashSet::new(),
+            derived: HashMap::new(),
+            skipped_on_fork: Vec::new(),
+            max_channels: None,
+            writers: RefCell::new(HashSet::new()),
+            shared_writers: HashSet::new(),
+            woven_outputs: HashSet::new(),
         }
     }
 
+    /// Create a new empty registry that holds at most `max_channels` channels.
+    ///
+    /// Creating channels past the limit, through `put`, `ensure`, or the
+    /// `register` of output keys, fails with `RegistryError::CapacityExceeded`.
+    /// This guards long-running processes against runaway configurations.
+    /// Aliases do not count towards the limit.
+    pub fn with_max_channels(max_channels: usize) -> Self {
+        Self {
+            max_channels: Some(max_channels),
+            ..Self::new()
+        }
+    }
+
+    /// Fail if creating the (resolved) channel `key` would exceed the channel limit.
+    /// Existing channels can always be replaced.
+    fn check_capacity(&self, key: &str) -> Result<(), errors::RegistryError> {
+        match self.max_channels {
+            Some(limit) if self.store.len() >= limit && !self.store.contains_key(key) => {
+                Err(errors::RegistryError::CapacityExceeded {
+                    key: key.to_string(),
+                    limit,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolve a key through the alias table. Keys that are not aliases
+    /// resolve to themselves.
+    fn resolve<'a>(&'a self, key: &'a str) -> &'a str {
+        self.aliases.get(key).map(String::as_str).unwrap_or(key)
+    }
+
     pub fn has(&self, key: impl Into<String>) -> bool {
         let key = key.into();
-        self.store.contains_key(&key)
+        self.store.contains_key(self.resolve(&key))
     }
 
     /// Put a value into the registry
-    pub fn put<T: 'static>(&mut self, key: impl Into<String>, value: T) {
+    ///
+    /// Replacing an existing channel makes it a plain channel: it is no longer
+    /// constant, derived, serializable or cloneable, whatever the old value was.
+    pub fn put<T: 'static>(
+        &mut self,
+        key: impl Into<String>,
+        value: T,
+    ) -> Result<(), errors::RegistryError> {
         let key = key.into();
+        let key = self.resolve(&key).to_string();
+        self.check_capacity(&key)?;
+        self.consts.remove(&key);
+        self.derived.remove(&key);
+        self.woven_outputs.remove(&key);
+        // The codec and cloner of the old value may not fit the new type.
+        self.codecs.remove(&key);
+        self.cloners.remove(&key);
+        // Writers of the replaced channel no longer write to the new one.
+        self.writers.borrow_mut().remove(&key);
         self.store.insert(key, Rc::new(RefCell::new(value)));
+        Ok(())
+    }
+
+    /// Put a channel whose value is computed from other channels.
+    ///
+    /// `compute` reads the channels listed in `deps` from the registry. It runs
+    /// once here, so the dependencies must exist, and again whenever the channel
+    /// is read with `get`, so the value follows its dependencies without a block
+    /// to update it. Readers that hold on to the channel, such as those of woven
+    /// blocks, instead see the value as of the last `get` or `refresh_derived`;
+    /// see `derived_channels` for refreshing them while the blocks run. Derived
+    /// channels cannot be written, see `get_output`.
+    ///
+    /// Fails with `RegistryError::CycleDetected` if the channel would depend on
+    /// itself through other derived channels. Putting a plain value under the
+    /// key makes it an ordinary channel again.
+    pub fn put_derived<T: 'static>(
+        &mut self,
+        key: impl Into<String>,
+        deps: Vec<String>,
+        compute: impl Fn(&ChannelRegistry) -> T + 'static,
+    ) -> Result<(), errors::RegistryError> {
+        let key = key.into();
+        let key = self.resolve(&key).to_string();
+        if self.depends_on(&deps, &key) {
+            return Err(errors::RegistryError::CycleDetected(format!(
+                "derived channel '{key}' depends on itself"
+            )));
+        }
+        let value = compute(self);
+        self.put(key.clone(), value)?;
+        let compute = Rc::new(compute);
+        let refresh: RefreshFn = Rc::new({
+            let compute = compute.clone();
+            move |registry, value| {
+                let Some(cell) = value.downcast_ref::<RefCell<T>>() else {
+                    return;
+                };
+                let value = compute(registry);
+                // A value borrowed by the caller is left as it is.
+                if let Ok(mut current) = cell.try_borrow_mut() {
+                    *current = value;
+                }
+            }
+        });
+        let create: CreateFn = Rc::new(move |registry| Rc::new(RefCell::new(compute(registry))));
+        self.derived.insert(
+            key,
+            Derived {
+                deps,
+                refresh,
+                create,
+            },
+        );
+        Ok(())
+    }
+
+    /// Whether any channel in `deps` is `key` or, through derived channels,
+    /// depends on it.
+    fn depends_on(&self, deps: &[String], key: &str) -> bool {
+        let mut pending: Vec<&str> = deps.iter().map(|dep| self.resolve(dep)).collect();
+        let mut seen = HashSet::new();
+        while let Some(dep) = pending.pop() {
+            if dep == key {
+                return true;
+            }
+            if let Some(derived) = self.derived.get(dep).filter(|_| seen.insert(dep)) {
+                pending.extend(derived.deps.iter().map(|dep| self.resolve(dep)));
+            }
+        }
+        false
+    }
+
+    /// Recompute the derived channel `key`, if it is one.
+    fn refresh(&self, key: &str) {
+        if let (Some(derived), Some(value)) = (self.derived.get(key), self.store.get(key)) {
+            (derived.refresh)(self, value);
+        }
+    }
+
+    /// Recompute all derived channels, see `put_derived`.
+    ///
+    /// Useful once per tick when readers hold on to derived channels.
+    pub fn refresh_derived(&self) {
+        for key in self.derived.keys() {
+            self.refresh(key);
+        }
+    }
+
+    /// A handle that recomputes the derived channels of the registry, for
+    /// refreshing them once the registry is no longer at hand, e.g. from an
+    /// execution plan between the blocks it runs.
+    ///
+    /// The handle shares the registry's channels as they are now: channels
+    /// replaced or derived after this call are not seen by it, so take the
+    /// handle once the registry is set up.
+    pub fn derived_channels(&self) -> DerivedChannels {
+        DerivedChannels {
+            registry: ChannelRegistry {
+                store: self.store.clone(),
+                aliases: self.aliases.clone(),
+                derived: self.derived.clone(),
+                ..ChannelRegistry::new()
+            },
+        }
+    }
+
+    /// Put a constant value into the registry.
+    ///
+    /// Constant channels can be read like any other channel, but creating a
+    /// writer for one (see `get_output`) fails with `RegistryError::ConstChannel`,
+    /// so no block can overwrite it.
+    pub fn put_const<T: 'static>(
+        &mut self,
+        key: impl Into<String>,
+        value: T,
+    ) -> Result<(), errors::RegistryError> {
+        let key = key.into();
+        let key = self.resolve(&key).to_string();
+        self.put(key.clone(), value)?;
+        self.consts.insert(key);
+        Ok(())
+    }
+
+    /// Whether `key` names a channel created with `put_const`.
+    pub fn is_const(&self, key: impl AsRef<str>) -> bool {
+        self.consts.contains(self.resolve(key.as_ref()))
+    }
+
+    /// Make `alias` another name for the channel `target`.
+    ///
+    /// Lookups through the alias resolve to the target's storage, so readers and
+    /// writers of either name share the same value. The target must already exist,
+    /// and the alias must not name an existing channel. Aliasing an alias resolves
+    /// to the original target.
+    pub fn alias(&mut self, alias: &str, target: &str) -> Result<(), errors::RegistryError> {
+        let target = self.resolve(target).to_string();
+        if !self.store.contains_key(&target) {
+            return Err(errors::RegistryError::KeyNotFound(target));
+        }
+        if self.store.contains_key(alias) || self.aliases.contains_key(alias) {
+            return Err(errors::RegistryError::DuplicateOutputKey(alias.to_string()));
+        }
+        self.aliases.insert(alias.to_string(), target);
+        Ok(())
     }
 
     /// Get a value from the registry
@@ -35,8 +342,9 @@ impl ChannelRegistry {
         key: impl AsRef<str>,
     ) -> Result<Rc<RefCell<T>>, errors::RegistryError> {
         let key = key.as_ref();
+        self.refresh(self.resolve(key));
 
-        match self.store.get(key) {
+        match self.store.get(self.resolve(key)) {
             Some(value) => {
                 // The value is stored as Rc<dyn Any>, but actually contains Rc<RefCell<T>>
                 // We need to downcast the Rc itself
@@ -52,6 +360,81 @@ impl ChannelRegistry {
         }
     }
 
+    /// Borrow the value of a channel and return what `f` computes from it.
+    ///
+    /// Unlike reading through `get`, this never clones the value, so it suits
+    /// inspecting part of a large payload, e.g. the length of a vector.
+    /// Fails with `RegistryError::ChannelBorrowed` if the channel is mutably
+    /// borrowed at the time of the call.
+    pub fn peek<T: 'static, R>(
+        &self,
+        key: impl AsRef<str>,
+        f: impl FnOnce(&T) -> R,
+    ) -> Result<R, errors::RegistryError> {
+        let key = key.as_ref();
+        self.refresh(self.resolve(key));
+
+        let value = self
+            .store
+            .get(self.resolve(key))
+            .ok_or_else(|| errors::RegistryError::KeyNotFound(key.to_string()))?;
+        let cell = value.downcast_ref::<RefCell<T>>().ok_or_else(|| {
+            errors::RegistryError::TypeMismatch {
+                key: key.to_string(),
+                expected: std::any::type_name::<T>(),
+                found: "unknown",
+            }
+        })?;
+        let value = cell
+            .try_borrow()
+            .map_err(|_| errors::RegistryError::ChannelBorrowed(key.to_string()))?;
+        Ok(f(&value))
+    }
+
+    /// Get the value of an output channel for writing.
+    ///
+    /// Output channels must be registered (see `OutputKeys::register`) before a writer
+    /// is created for them. Unlike `get`, a missing key is reported as
+    /// `RegistryError::UnregisteredOutput` so a skipped registration is easy to spot.
+    /// Constant channels cannot be written and give `RegistryError::ConstChannel`,
+    /// and derived channels give `RegistryError::DerivedChannel`.
+    ///
+    /// Each channel gets one writer: asking again for the same channel, also
+    /// through an alias, gives `RegistryError::DuplicateWriter`, unless the
+    /// channel is marked with `allow_multiple_writers`. Re-weaving a graph into
+    /// the same registry should call `release_writers` first.
+    pub fn get_output<T: 'static>(
+        &self,
+        key: impl AsRef<str>,
+    ) -> Result<Rc<RefCell<T>>, errors::RegistryError> {
+        let key = key.as_ref();
+        let value = self.output_cell::<T>(key)?;
+        let target = self.resolve(key);
+        if !self.shared_writers.contains(target)
+            && !self.writers.borrow_mut().insert(target.to_string())
+        {
+            return Err(errors::RegistryError::DuplicateWriter(key.to_string()));
+        }
+        Ok(value)
+    }
+
+    /// Get an output channel like `get_output`, without recording a writer.
+    pub(crate) fn output_cell<T: 'static>(
+        &self,
+        key: &str,
+    ) -> Result<Rc<RefCell<T>>, errors::RegistryError> {
+        if !self.has(key) {
+            return Err(errors::RegistryError::UnregisteredOutput(key.to_string()));
+        }
+        if self.is_const(key) {
+            return Err(errors::RegistryError::ConstChannel(key.to_string()));
+        }
+        if self.derived.contains_key(self.resolve(key)) {
+            return Err(errors::RegistryError::DerivedChannel(key.to_string()));
+        }
+        self.get::<T>(key)
+    }
+
     /// Ensure a key exists in the registry, creating it with Default if it doesn't.
     /// Returns the Rc<RefCell<T>> for the key. If the key exists but has the wrong type,
     /// an error is returned.
@@ -59,11 +442,23 @@ impl ChannelRegistry {
         &mut self,
         key: impl Into<String>,
     ) -> Result<Rc<RefCell<T>>, errors::RegistryError> {
+        self.ensure_reporting(key).map(|(value, _created)| value)
+    }
+
+    /// Like `ensure`, but also report whether the channel was created by this call.
+    ///
+    /// The flag is `false` if the channel already existed, which lets callers
+    /// registering outputs detect that another block registered it first.
+    pub fn ensure_reporting<T: Default + 'static>(
+        &mut self,
+        key: impl Into<String>,
+    ) -> Result<(Rc<RefCell<T>>, bool), errors::RegistryError> {
         let key = key.into();
+        let key = self.resolve(&key).to_string();
 
         // Check if key already exists and try to get it
         if let Ok(existing) = self.get::<T>(&key) {
-            return Ok(existing);
+            return Ok((existing, false));
         }
 
         if self.store.contains_key(&key) {
@@ -76,10 +471,323 @@ impl ChannelRegistry {
         }
 
         // Key doesn't exist create new entry
+        self.check_capacity(&key)?;
         let value = Rc::new(RefCell::new(T::default()));
         self.store.insert(key, value.clone());
+        Ok((value, true))
+    }
+
+    /// Get a channel, inserting the value built by `f` if it doesn't exist.
+    ///
+    /// Like `ensure`, but without requiring `Default`: `f` is only called when
+    /// the channel is created. If the key exists with a different type, an error
+    /// is returned.
+    pub fn get_or_insert_with<T: 'static>(
+        &mut self,
+        key: impl Into<String>,
+        f: impl FnOnce() -> T,
+    ) -> Result<Rc<RefCell<T>>, errors::RegistryError> {
+        let key = key.into();
+        let key = self.resolve(&key).to_string();
+        if self.store.contains_key(&key) {
+            return self.get::<T>(&key);
+        }
+        self.check_capacity(&key)?;
+        let value = Rc::new(RefCell::new(f()));
+        self.store.insert(key, value.clone());
+        Ok(value)
+    }
+
+    /// Remove the channel `key` and every alias of it.
+    ///
+    /// Returns whether the channel existed. Readers and writers already created
+    /// for the channel keep working on the removed value, but the registry no
+    /// longer hands it out.
+    pub fn remove(&mut self, key: &str) -> bool {
+        let key = self.resolve(key).to_string();
+        if self.store.remove(&key).is_none() {
+            return false;
+        }
+        self.aliases.retain(|_, target| *target != key);
+        self.codecs.remove(&key);
+        self.cloners.remove(&key);
+        self.consts.remove(&key);
+        self.derived.remove(&key);
+        self.writers.borrow_mut().remove(&key);
+        self.shared_writers.remove(&key);
+        self.woven_outputs.remove(&key);
+        true
+    }
+
+    /// Let the channel `key` have any number of writers, see `get_output`.
+    pub fn allow_multiple_writers(&mut self, key: &str) {
+        let key = self.resolve(key).to_string();
+        self.shared_writers.insert(key);
+    }
+
+    /// Forget the writers created so far, so every channel can get a new one.
+    ///
+    /// For re-weaving a graph into the registry, where the new blocks' writers
+    /// replace those of the old blocks.
+    pub fn release_writers(&self) {
+        self.writers.borrow_mut().clear();
+    }
+
+    /// Forget the writers of the channels `keys`, so each can get a new one.
+    ///
+    /// Like `release_writers`, but the writers of other channels, e.g. those
+    /// of blocks that are not re-woven, stay in place.
+    pub fn release_writers_of<K: AsRef<str>>(&self, keys: impl IntoIterator<Item = K>) {
+        let mut writers = self.writers.borrow_mut();
+        for key in keys {
+            writers.remove(self.resolve(key.as_ref()));
+        }
+    }
+
+    /// Run `f` on the registry, restoring the writers recorded before the call
+    /// if it fails.
+    ///
+    /// Writers created or released by a failed `f`, e.g. a weave that fails
+    /// halfway, are thus forgotten, and the operation can be retried without
+    /// `RegistryError::DuplicateWriter`.
+    pub fn with_writer_rollback<R, E>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<R, E>,
+    ) -> Result<R, E> {
+        let writers = self.writers.borrow().clone();
+        let result = f(self);
+        if result.is_err() {
+            *self.writers.borrow_mut() = writers;
+        }
+        result
+    }
+
+    /// Call `f` with the name and value of every channel holding a `T`, in
+    /// name order.
+    ///
+    /// Channels of other types are skipped, and aliases are not visited
+    /// separately from their targets. Useful for bulk updates such as clearing
+    /// all `bool` signals at the end of a tick.
+    pub fn for_each_of_type<T: 'static>(&self, mut f: impl FnMut(&str, &Rc<RefCell<T>>)) {
+        let mut channels: Vec<(&String, Rc<RefCell<T>>)> = self
+            .store
+            .iter()
+            .filter_map(|(key, value)| Some((key, value.clone().downcast::<RefCell<T>>().ok()?)))
+            .collect();
+        channels.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, value) in &channels {
+            f(key, value);
+        }
+    }
+
+    /// Put a serializable value into the registry.
+    ///
+    /// Like `put`, but the channel is included in `dump_serializable` snapshots
+    /// and can be restored with `load_serializable`.
+    pub fn put_serializable<T: Serialize + DeserializeOwned + 'static>(
+        &mut self,
+        key: impl Into<String>,
+        value: T,
+    ) -> Result<(), errors::RegistryError> {
+        let key = key.into();
+        let key = self.resolve(&key).to_string();
+        self.put(key.clone(), value)?;
+        self.codecs.insert(key, Codec::of::<T>());
+        Ok(())
+    }
+
+    /// Ensure a serializable channel exists, like `ensure`.
+    ///
+    /// The channel is included in `dump_serializable` snapshots and can be
+    /// restored with `load_serializable`.
+    pub fn ensure_serializable<T: Default + Serialize + DeserializeOwned + 'static>(
+        &mut self,
+        key: impl Into<String>,
+    ) -> Result<Rc<RefCell<T>>, errors::RegistryError> {
+        let key = key.into();
+        let key = self.resolve(&key).to_string();
+        let value = self.ensure::<T>(key.clone())?;
+        self.codecs.insert(key, Codec::of::<T>());
         Ok(value)
     }
+
+    /// Put a cloneable value into the registry.
+    ///
+    /// Like `put`, but the channel is copied into registries created with `fork`.
+    pub fn put_cloneable<T: Clone + 'static>(
+        &mut self,
+        key: impl Into<String>,
+        value: T,
+    ) -> Result<(), errors::RegistryError> {
+        let key = key.into();
+        let key = self.resolve(&key).to_string();
+        self.put(key.clone(), value)?;
+        self.cloners.insert(key, fork_cloned::<T>);
+        Ok(())
+    }
+
+    /// Ensure a cloneable channel exists, like `ensure`.
+    ///
+    /// The channel is copied into registries created with `fork`.
+    pub fn ensure_cloneable<T: Default + Clone + 'static>(
+        &mut self,
+        key: impl Into<String>,
+    ) -> Result<Rc<RefCell<T>>, errors::RegistryError> {
+        let key = key.into();
+        let key = self.resolve(&key).to_string();
+        let value = self.ensure::<T>(key.clone())?;
+        self.cloners.insert(key, fork_cloned::<T>);
+        Ok(value)
+    }
+
+    /// Register the output channel `key` of a woven block, creating it like
+    /// `ensure_cloneable` if it does not exist.
+    ///
+    /// Channels created here are remembered as woven outputs, see
+    /// `is_woven_output`. Channels that already existed, such as ones created
+    /// up front with `ensure_serializable`, are not.
+    pub fn register_output<T: Default + Clone + 'static>(
+        &mut self,
+        key: impl Into<String>,
+    ) -> Result<(), errors::RegistryError> {
+        let key = key.into();
+        let key = self.resolve(&key).to_string();
+        let (_, created) = self.ensure_reporting::<T>(key.clone())?;
+        self.cloners.insert(key.clone(), fork_cloned::<T>);
+        if created {
+            self.woven_outputs.insert(key);
+        }
+        Ok(())
+    }
+
+    /// Whether `key` names a channel created by `register_output`, i.e. by
+    /// weaving a block that writes it, rather than registered by the user.
+    pub fn is_woven_output(&self, key: impl AsRef<str>) -> bool {
+        self.woven_outputs.contains(self.resolve(key.as_ref()))
+    }
+
+    /// Create an independent copy of the registry.
+    ///
+    /// Channels created as cloneable (`put_cloneable`, `ensure_cloneable`) or
+    /// serializable (`put_serializable`, `ensure_serializable`) are deep-copied, so
+    /// writes to the fork do not affect this registry and vice versa. This
+    /// includes the output channels of woven blocks, which are registered as
+    /// cloneable. Derived channels are recomputed in the fork from its copies of
+    /// their dependencies. Other channels cannot be copied; they are left out of
+    /// the fork and listed in its `skipped_on_fork`. Aliases of copied channels
+    /// and channels allowing multiple writers are preserved.
+    pub fn fork(&self) -> ChannelRegistry {
+        let mut fork = ChannelRegistry::new();
+        fork.max_channels = self.max_channels;
+        for (key, value) in &self.store {
+            let cloner = self.cloners.get(key);
+            let codec = self.codecs.get(key);
+            let copy = cloner
+                .and_then(|clone| clone(value))
+                .or_else(|| codec.and_then(|codec| (codec.fork)(value)));
+            match copy {
+                Some(copy) => {
+                    fork.store.insert(key.clone(), copy);
+                    if let Some(cloner) = cloner {
+                        fork.cloners.insert(key.clone(), *cloner);
+                    }
+                    if let Some(codec) = codec {
+                        fork.codecs.insert(key.clone(), *codec);
+                    }
+                    if self.consts.contains(key) {
+                        fork.consts.insert(key.clone());
+                    }
+                    if self.woven_outputs.contains(key) {
+                        fork.woven_outputs.insert(key.clone());
+                    }
+                }
+                None if self.derived.contains_key(key) => {}
+                None => fork.skipped_on_fork.push(key.clone()),
+            }
+        }
+        // Aliases first, as derived channels may depend on channels through them.
+        for (alias, target) in &self.aliases {
+            if fork.store.contains_key(target) || self.derived.contains_key(target) {
+                fork.aliases.insert(alias.clone(), target.clone());
+            }
+        }
+        // Derived channels can depend on each other, so create them as their
+        // dependencies become available.
+        let mut pending: Vec<&String> = self.derived.keys().collect();
+        loop {
+            let before = pending.len();
+            pending.retain(|key| {
+                let derived = &self.derived[*key];
+                if !derived.deps.iter().all(|dep| fork.has(dep.as_str())) {
+                    return true;
+                }
+                let value = (derived.create)(&fork);
+                fork.store.insert((*key).clone(), value);
+                fork.derived.insert((*key).clone(), derived.clone());
+                false
+            });
+            if pending.len() == before {
+                break;
+            }
+        }
+        fork.skipped_on_fork.extend(pending.into_iter().cloned());
+        fork.skipped_on_fork.sort();
+        fork.aliases
+            .retain(|_, target| fork.store.contains_key(target.as_str()));
+        fork.shared_writers = self
+            .shared_writers
+            .iter()
+            .filter(|key| fork.store.contains_key(key.as_str()))
+            .cloned()
+            .collect();
+        fork
+    }
+
+    /// Channels of the original registry that could not be copied when this
+    /// registry was created by `fork`, in sorted order.
+    pub fn skipped_on_fork(&self) -> &[String] {
+        &self.skipped_on_fork
+    }
+
+    /// Serialize the current values of all serializable channels into a JSON map
+    /// from channel name to value.
+    ///
+    /// Only channels created with `put_serializable` or `ensure_serializable` are
+    /// included; other channels are skipped. The output channels of woven blocks
+    /// are not serializable by themselves: to include one, register it with
+    /// `ensure_serializable` before weaving, and the block will write to it.
+    pub fn dump_serializable(&self) -> serde_json::Value {
+        let mut map = serde_json::Map::new();
+        for (key, codec) in &self.codecs {
+            if let Some(value) = self.store.get(key).and_then(|v| (codec.dump)(v)) {
+                map.insert(key.clone(), value);
+            }
+        }
+        serde_json::Value::Object(map)
+    }
+
+    /// Restore channel values from a map produced by `dump_serializable`.
+    ///
+    /// Values are only restored into channels already registered as serializable in
+    /// this registry, since the type of a channel cannot be recovered from JSON.
+    /// Entries for other channels are skipped.
+    pub fn load_serializable(
+        &mut self,
+        snapshot: &serde_json::Value,
+    ) -> Result<(), errors::RegistryError> {
+        let serde_json::Value::Object(map) = snapshot else {
+            return Err(errors::RegistryError::InvalidSnapshot(
+                "expected a JSON object".to_string(),
+            ));
+        };
+        for (key, json) in map {
+            let key = self.resolve(key);
+            if let (Some(codec), Some(value)) = (self.codecs.get(key), self.store.get(key)) {
+                (codec.load)(key, value, json)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for ChannelRegistry {
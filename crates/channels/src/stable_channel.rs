@@ -0,0 +1,64 @@
+use crate::{errors::RegistryError, registry::ChannelRegistry};
+use std::marker::PhantomData;
+
+/// A typed handle to a channel that looks the channel up by key on every access.
+///
+/// An `Rc<RefCell<T>>` obtained from `ChannelRegistry::get` keeps pointing at
+/// the storage it was created with. When the channel is replaced, e.g. because
+/// the graph was re-woven into the registry, such a handle silently goes stale
+/// while new readers and writers use the new storage. A `StableChannel` holds
+/// only the key, so it always sees the channel currently in the registry.
+#[derive(Debug)]
+pub struct StableChannel<T> {
+    key: String,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T: 'static> StableChannel<T> {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            _type: PhantomData,
+        }
+    }
+
+    /// The key of the channel.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Read the current value of the channel.
+    pub fn read(&self, registry: &ChannelRegistry) -> Result<T, RegistryError>
+    where
+        T: Clone,
+    {
+        registry.peek(&self.key, T::clone)
+    }
+
+    /// Borrow the current value of the channel, see `ChannelRegistry::peek`.
+    pub fn peek<R>(
+        &self,
+        registry: &ChannelRegistry,
+        f: impl FnOnce(&T) -> R,
+    ) -> Result<R, RegistryError> {
+        registry.peek(&self.key, f)
+    }
+
+    /// Write a new value to the channel.
+    ///
+    /// Fails like `ChannelRegistry::get_output` if the channel is missing or
+    /// constant. The handle does not count as the channel's writer.
+    pub fn write(&self, registry: &ChannelRegistry, value: T) -> Result<(), RegistryError> {
+        *registry.output_cell::<T>(&self.key)?.borrow_mut() = value;
+        Ok(())
+    }
+}
+
+impl<T> Clone for StableChannel<T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            _type: PhantomData,
+        }
+    }
+}
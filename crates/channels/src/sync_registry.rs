@@ -0,0 +1,66 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use super::errors;
+
+/// A thread-safe channel, shared between the blocks that read and write it.
+pub type SyncChannel<T> = Arc<RwLock<T>>;
+
+/// Thread-safe counterpart of `ChannelRegistry`.
+///
+/// Channels are stored as `Arc<RwLock<T>>`, so they, and blocks holding them,
+/// can be moved to and shared between threads.
+#[derive(Default)]
+pub struct SyncChannelRegistry {
+    store: HashMap<String, Arc<dyn Any + Send + Sync>>,
+}
+
+impl SyncChannelRegistry {
+    /// Create a new empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn has(&self, key: impl AsRef<str>) -> bool {
+        self.store.contains_key(key.as_ref())
+    }
+
+    /// Put a value into the registry
+    pub fn put<T: Send + Sync + 'static>(&mut self, key: impl Into<String>, value: T) {
+        self.store.insert(key.into(), Arc::new(RwLock::new(value)));
+    }
+
+    /// Get a channel from the registry
+    pub fn get<T: Send + Sync + 'static>(
+        &self,
+        key: impl AsRef<str>,
+    ) -> Result<SyncChannel<T>, errors::RegistryError> {
+        let key = key.as_ref();
+        match self.store.get(key) {
+            Some(value) => value.clone().downcast::<RwLock<T>>().map_err(|_| {
+                errors::RegistryError::TypeMismatch {
+                    key: key.to_string(),
+                    expected: std::any::type_name::<T>(),
+                    found: "unknown",
+                }
+            }),
+            None => Err(errors::RegistryError::KeyNotFound(key.to_string())),
+        }
+    }
+
+    /// Ensure a key exists in the registry, creating it with Default if it doesn't.
+    /// If the key exists but has the wrong type, an error is returned.
+    pub fn ensure<T: Default + Send + Sync + 'static>(
+        &mut self,
+        key: impl Into<String>,
+    ) -> Result<SyncChannel<T>, errors::RegistryError> {
+        let key = key.into();
+        if self.store.contains_key(&key) {
+            return self.get::<T>(&key);
+        }
+        let value = Arc::new(RwLock::new(T::default()));
+        self.store.insert(key, value.clone());
+        Ok(value)
+    }
+}
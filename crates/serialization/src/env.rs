@@ -0,0 +1,140 @@
+//! Environment-variable interpolation
+//!
+//! Lets one JSON config serve several deployments by filling in `${VAR}`
+//! patterns in its strings from the environment before it is deserialized.
+
+use crate::error::{Result, SerializationError};
+use serde_json::Value;
+
+/// Replace every `${VAR}` in the string values of `json` with the value of the
+/// environment variable `VAR`, and `$${` with a literal `${`.
+///
+/// Only string values are interpolated: object keys and other values are left
+/// as they are, so the environment cannot change the structure of the config.
+/// The interpolated JSON is returned in compact form.
+///
+/// Fails with `SerializationError::Json` if `json` is not valid JSON, with
+/// `SerializationError::MissingEnvVar` if a variable is not set, and with a
+/// custom error if a `${` is not closed.
+///
+/// # Examples
+///
+/// ```rust
+/// use serialization::interpolate_env;
+///
+/// std::env::set_var("BLOCK_FLOW_DOC_CONTRACT", "ABC-123");
+/// let json = interpolate_env(r#"{ "contract": "${BLOCK_FLOW_DOC_CONTRACT}" }"#).unwrap();
+/// assert_eq!(json, r#"{"contract":"ABC-123"}"#);
+/// ```
+pub fn interpolate_env(json: &str) -> Result<String> {
+    let mut value: Value = serde_json::from_str(json)?;
+    interpolate_env_value(&mut value)?;
+    Ok(serde_json::to_string(&value)?)
+}
+
+/// Interpolate the string values of an already parsed JSON value in place,
+/// like `interpolate_env`.
+pub fn interpolate_env_value(value: &mut Value) -> Result<()> {
+    match value {
+        Value::String(text) => *text = interpolate_str(text)?,
+        Value::Array(items) => items.iter_mut().try_for_each(interpolate_env_value)?,
+        Value::Object(map) => map.values_mut().try_for_each(interpolate_env_value)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Interpolate the patterns of a single string value.
+fn interpolate_str(text: &str) -> Result<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        if let Some(before) = rest[..start].strip_suffix('$') {
+            out.push_str(before);
+            out.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find('}').ok_or_else(|| {
+            SerializationError::Custom(format!(
+                "unterminated '${{' in config: '${{{}'",
+                after.chars().take(20).collect::<String>()
+            ))
+        })?;
+        let name = &after[..end];
+        let value =
+            std::env::var(name).map_err(|_| SerializationError::MissingEnvVar(name.to_string()))?;
+        out.push_str(&value);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Config {
+        channel: String,
+        contract: String,
+    }
+
+    #[test]
+    fn substitutes_variables_in_strings() {
+        std::env::set_var("BLOCK_FLOW_TEST_ENV_CHANNEL", "prod");
+        std::env::set_var("BLOCK_FLOW_TEST_ENV_CONTRACT", "say \"hi\"");
+        let json = interpolate_env(
+            r#"{ "channel": "signal-${BLOCK_FLOW_TEST_ENV_CHANNEL}", "contract": "${BLOCK_FLOW_TEST_ENV_CONTRACT}" }"#,
+        )
+        .unwrap();
+
+        let config: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                channel: "signal-prod".to_string(),
+                contract: "say \"hi\"".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn only_string_values_are_interpolated() {
+        let json = r#"{ "${BLOCK_FLOW_TEST_ENV_UNSET}": "$5 {braces}", "n": 1 }"#;
+        assert_eq!(
+            interpolate_env(json).unwrap(),
+            r#"{"${BLOCK_FLOW_TEST_ENV_UNSET}":"$5 {braces}","n":1}"#
+        );
+    }
+
+    #[test]
+    fn double_dollar_escapes_a_pattern() {
+        let json = r#"["$${BLOCK_FLOW_TEST_ENV_UNSET}"]"#;
+        assert_eq!(
+            interpolate_env(json).unwrap(),
+            r#"["${BLOCK_FLOW_TEST_ENV_UNSET}"]"#
+        );
+    }
+
+    #[test]
+    fn missing_variable_is_an_error_naming_it() {
+        let err = interpolate_env(r#"{ "channel": "${BLOCK_FLOW_TEST_ENV_UNSET}" }"#).unwrap_err();
+        match &err {
+            SerializationError::MissingEnvVar(name) => {
+                assert_eq!(name, "BLOCK_FLOW_TEST_ENV_UNSET")
+            }
+            other => panic!("expected MissingEnvVar, got {other:?}"),
+        }
+        assert!(err.to_string().contains("BLOCK_FLOW_TEST_ENV_UNSET"));
+    }
+
+    #[test]
+    fn unterminated_pattern_is_an_error() {
+        assert!(interpolate_env(r#"{ "channel": "${OOPS" }"#).is_err());
+    }
+}
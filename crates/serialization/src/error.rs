@@ -9,6 +9,15 @@ pub enum SerializationError {
     Io(std::io::Error),
     /// Custom error message
     Custom(String),
+    /// JSON error while deserializing a block, with the block type and, if
+    /// known, the path of the failing field within the block's data
+    Block {
+        block_type: String,
+        path: Option<String>,
+        source: serde_json::Error,
+    },
+    /// A `${VAR}` in a config named an environment variable that is not set
+    MissingEnvVar(String),
 }
 
 impl fmt::Display for SerializationError {
@@ -17,6 +26,23 @@ impl fmt::Display for SerializationError {
             SerializationError::Json(e) => write!(f, "JSON error: {}", e),
             SerializationError::Io(e) => write!(f, "IO error: {}", e),
             SerializationError::Custom(msg) => write!(f, "Serialization error: {}", msg),
+            SerializationError::Block {
+                block_type,
+                path: Some(path),
+                source,
+            } => write!(
+                f,
+                "JSON error in {} block at {}: {}",
+                block_type, path, source
+            ),
+            SerializationError::Block {
+                block_type,
+                path: None,
+                source,
+            } => write!(f, "JSON error in {} block: {}", block_type, source),
+            SerializationError::MissingEnvVar(name) => {
+                write!(f, "Environment variable '{}' is not set", name)
+            }
         }
     }
 }
@@ -26,7 +52,8 @@ impl std::error::Error for SerializationError {
         match self {
             SerializationError::Json(e) => Some(e),
             SerializationError::Io(e) => Some(e),
-            SerializationError::Custom(_) => None,
+            SerializationError::Custom(_) | SerializationError::MissingEnvVar(_) => None,
+            SerializationError::Block { source, .. } => Some(source),
         }
     }
 }
@@ -79,6 +106,20 @@ mod tests {
         assert_eq!(s, "Serialization error: hello");
     }
 
+    #[test]
+    fn display_block_error_includes_block_type_and_path() {
+        let err = serde_json::from_str::<u64>("\"soon\"").unwrap_err();
+        let se = SerializationError::Block {
+            block_type: "After".to_string(),
+            path: Some("init_params.time".to_string()),
+            source: err,
+        };
+
+        let s = se.to_string();
+        assert!(s.starts_with("JSON error in After block at init_params.time: "));
+        assert!(se.source().is_some());
+    }
+
     #[test]
     fn source_for_json_error_is_some() {
         let err = serde_json::from_str::<serde_json::Value>("{ not valid json }").unwrap_err();
@@ -8,10 +8,14 @@
 //! Future support planned for:
 //! - Protocol Buffers (efficient binary format)
 
+pub mod env;
 pub mod error;
 pub mod serializer;
 pub mod structs;
 
+pub use env::{interpolate_env, interpolate_env_value};
 pub use error::{Result, SerializationError};
-pub use structs::read_struct_from_json;
+pub use structs::{
+    canonical_bytes, read_struct_from_json, read_struct_from_json_with_options, JsonReadOptions,
+};
 pub use structs::{JsonStructSerializer, Serializable, StructSerializer};
@@ -111,6 +111,57 @@ pub fn read_struct_from_json<S: Serializable>(data: &[u8]) -> Result<S> {
     crate::serializer::Serializer::from_json(data)
 }
 
+/// Options for `read_struct_from_json_with_options` and the other JSON
+/// readers taking options, such as those reading blocks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JsonReadOptions {
+    /// Fill in `${VAR}` patterns in string values from the environment before
+    /// deserializing, see `interpolate_env`.
+    pub interpolate_env: bool,
+}
+
+/// Read a struct from JSON like `read_struct_from_json`, with the given options.
+pub fn read_struct_from_json_with_options<S: Serializable>(
+    data: &[u8],
+    options: JsonReadOptions,
+) -> Result<S> {
+    if !options.interpolate_env {
+        return read_struct_from_json(data);
+    }
+    let mut value: serde_json::Value = serde_json::from_slice(data)?;
+    crate::env::interpolate_env_value(&mut value)?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Serialize `data` to canonical JSON bytes, for hashing or comparing configs.
+///
+/// The JSON is compact, and the keys of every object are sorted, so two
+/// values that serialize to equal JSON objects give the same bytes whatever
+/// order their fields or map entries are serialized in.
+pub fn canonical_bytes<S: Serializable>(data: &S) -> Result<Vec<u8>> {
+    let value = serde_json::to_value(data)?;
+    crate::serializer::Serializer::to_json(&sort_keys(value))
+}
+
+/// Rebuild `value` with the keys of every object in sorted order.
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,6 +276,27 @@ mod tests {
         assert_eq!(config, restored);
     }
 
+    #[test]
+    fn test_read_struct_from_json_interpolates_env_when_asked() {
+        std::env::set_var("BLOCK_FLOW_TEST_STRUCTS_X", "from env");
+        let json = br#"{ "value_x": "${BLOCK_FLOW_TEST_STRUCTS_X}", "value_y": 3 }"#;
+
+        let plain: TestConfigB = read_struct_from_json(json).unwrap();
+        assert_eq!(plain.value_x, "${BLOCK_FLOW_TEST_STRUCTS_X}");
+
+        let options = JsonReadOptions {
+            interpolate_env: true,
+        };
+        let interpolated: TestConfigB = read_struct_from_json_with_options(json, options).unwrap();
+        assert_eq!(
+            interpolated,
+            TestConfigB {
+                value_x: "from env".to_string(),
+                value_y: 3,
+            }
+        );
+    }
+
     #[test]
     fn test_invalid_json_deserialize_errors() {
         // Covers error path through deserialize()
@@ -297,4 +369,43 @@ mod tests {
         assert_eq!(restored1, restored2);
         assert_eq!(restored1, config);
     }
+
+    #[test]
+    fn test_canonical_bytes_ignore_field_order() {
+        #[derive(Serialize, Deserialize)]
+        struct Forward {
+            alpha: i32,
+            nested: std::collections::HashMap<String, i32>,
+            zeta: String,
+        }
+
+        #[derive(Serialize, Deserialize)]
+        struct Backward {
+            zeta: String,
+            nested: std::collections::HashMap<String, i32>,
+            alpha: i32,
+        }
+
+        impl Serializable for Forward {}
+        impl Serializable for Backward {}
+
+        // Separate hash maps, filled in opposite orders, may iterate in different orders.
+        let nested = |keys: Vec<i32>| keys.into_iter().map(|i| (format!("key{i}"), i)).collect();
+        let forward = Forward {
+            alpha: 1,
+            nested: nested((0..16).collect()),
+            zeta: "z".to_string(),
+        };
+        let backward = Backward {
+            zeta: "z".to_string(),
+            nested: nested((0..16).rev().collect()),
+            alpha: 1,
+        };
+
+        let bytes = canonical_bytes(&forward).unwrap();
+        assert_eq!(bytes, canonical_bytes(&backward).unwrap());
+        assert!(String::from_utf8(bytes)
+            .unwrap()
+            .starts_with(r#"{"alpha":1,"nested":{"key0":0,"key1":1,"key10":10,"#));
+    }
 }
@@ -1,10 +1,27 @@
 use super::*;
 
-#[derive(PartialEq, Eq, Hash, Clone, Debug, serde::Serialize, serde::Deserialize, Serializable)]
+/// A tradable contract, identified by its name. Contracts order by name.
+#[derive(
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Clone,
+    Debug,
+    serde::Serialize,
+    serde::Deserialize,
+    Serializable,
+)]
 pub struct Contract(String);
 
 impl Contract {
     pub fn new(name: &str) -> Self {
         Contract(name.to_string())
     }
+
+    /// The contract id as a string slice, e.g. for grouping contracts by product.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
 }
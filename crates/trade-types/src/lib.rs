@@ -1,13 +1,68 @@
 use serialization_macros::Serializable;
 
+/// Arithmetic, `Display` (the value followed by `$suffix`), and `From<$inner>`
+/// for a unit newtype over `$inner`, which defaults to `u32`.
+///
+/// `+` and `-` behave like the operators on `$inner`: they panic on overflow
+/// in debug builds and wrap in release builds. Use `checked_add` and
+/// `checked_sub` where the result can leave the range, e.g. subtracting a
+/// larger amount.
+macro_rules! unit_newtype {
+    ($unit:ident, $suffix:literal) => {
+        unit_newtype!($unit, u32, $suffix);
+    };
+    ($unit:ident, $inner:ty, $suffix:literal) => {
+        impl $unit {
+            /// Add two amounts, returning `None` on overflow.
+            pub fn checked_add(self, rhs: $unit) -> Option<$unit> {
+                self.0.checked_add(rhs.0).map($unit)
+            }
+
+            /// Subtract two amounts, returning `None` if `rhs` is larger.
+            pub fn checked_sub(self, rhs: $unit) -> Option<$unit> {
+                self.0.checked_sub(rhs.0).map($unit)
+            }
+        }
+
+        impl std::ops::Add for $unit {
+            type Output = $unit;
+            fn add(self, rhs: $unit) -> $unit {
+                $unit(self.0 + rhs.0)
+            }
+        }
+
+        impl std::ops::Sub for $unit {
+            type Output = $unit;
+            fn sub(self, rhs: $unit) -> $unit {
+                $unit(self.0 - rhs.0)
+            }
+        }
+
+        impl std::fmt::Display for $unit {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{} {}", self.0, $suffix)
+            }
+        }
+
+        impl From<$inner> for $unit {
+            fn from(value: $inner) -> Self {
+                $unit(value)
+            }
+        }
+    };
+}
+
 mod contract;
 pub use contract::Contract;
 
 mod price;
-pub use price::{Cents, Euros, Price};
+pub use price::{Cents, Euros, Price, PriceError};
 
 mod quantity;
-pub use quantity::{Kw, Mw, Quantity};
+pub use quantity::{Kw, MilliKw, Mw, Quantity};
+
+mod notional;
+pub use notional::Notional;
 
 #[derive(PartialEq, Eq, Hash, Debug, Clone, serde::Serialize, serde::Deserialize, Serializable)]
 pub enum Side {
@@ -15,6 +70,32 @@ pub enum Side {
     Sell,
 }
 
+/// Error returned when parsing a `Side` from an unrecognized string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSideError(pub String);
+
+impl std::fmt::Display for ParseSideError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown side '{}', expected buy or sell", self.0)
+    }
+}
+
+impl std::error::Error for ParseSideError {}
+
+/// Parses `"buy"`/`"sell"` and the abbreviations `"b"`/`"s"`, ignoring case
+/// and surrounding whitespace.
+impl std::str::FromStr for Side {
+    type Err = ParseSideError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "buy" | "b" => Ok(Side::Buy),
+            "sell" | "s" => Ok(Side::Sell),
+            _ => Err(ParseSideError(s.to_string())),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,6 +110,52 @@ mod tests {
         assert_ne!(a, c);
     }
 
+    #[test]
+    fn side_parses_case_insensitive_names_and_abbreviations() {
+        for text in ["buy", "BUY", "Buy", "b", "B", " buy "] {
+            assert_eq!(text.parse::<Side>(), Ok(Side::Buy), "{text}");
+        }
+        for text in ["sell", "SELL", "Sell", "s", "S"] {
+            assert_eq!(text.parse::<Side>(), Ok(Side::Sell), "{text}");
+        }
+    }
+
+    #[test]
+    fn side_rejects_unknown_strings() {
+        assert_eq!(
+            "long".parse::<Side>(),
+            Err(ParseSideError("long".to_string()))
+        );
+        assert!("".parse::<Side>().is_err());
+        assert!("buyer"
+            .parse::<Side>()
+            .unwrap_err()
+            .to_string()
+            .contains("buyer"));
+    }
+
+    #[test]
+    fn contract_as_str_exposes_id() {
+        let c = Contract::new("DE-BASE-2025Q1");
+        assert_eq!(c.as_str(), "DE-BASE-2025Q1");
+        assert!(c.as_str().starts_with("DE-BASE"));
+    }
+
+    #[test]
+    fn contracts_sort_lexically() {
+        let mut contracts = [
+            Contract::new("DE-PEAK-2025Q1"),
+            Contract::new("DE-BASE-2025Q2"),
+            Contract::new("DE-BASE-2025Q1"),
+        ];
+        contracts.sort();
+        let names: Vec<&str> = contracts.iter().map(Contract::as_str).collect();
+        assert_eq!(
+            names,
+            ["DE-BASE-2025Q1", "DE-BASE-2025Q2", "DE-PEAK-2025Q1"]
+        );
+    }
+
     #[test]
     fn price_from_cents_roundtrip_in_cents_and_euros() {
         let p: Price = Cents(12345).into();
@@ -43,20 +170,244 @@ mod tests {
         assert_eq!(p.in_euros().0, 42);
     }
 
+    #[test]
+    fn price_from_euros_f64_rounds_to_nearest_cent() {
+        assert_eq!(Price::from_euros_f64(1.005).unwrap().in_cents().0, 101);
+        assert_eq!(Price::from_euros_f64(1.004).unwrap().in_cents().0, 100);
+        assert_eq!(Price::from_euros_f64(12.34).unwrap().in_cents().0, 1234);
+        assert_eq!(Price::from_euros_f64(0.0).unwrap().in_cents().0, 0);
+    }
+
+    #[test]
+    fn price_from_euros_f64_rejects_negative() {
+        assert_eq!(Price::from_euros_f64(-0.01), Err(PriceError::Negative));
+    }
+
+    #[test]
+    fn price_from_euros_f64_rejects_nan_and_out_of_range() {
+        assert_eq!(Price::from_euros_f64(f64::NAN), Err(PriceError::NotANumber));
+        assert_eq!(
+            Price::from_euros_f64(f64::INFINITY),
+            Err(PriceError::OutOfRange)
+        );
+        assert_eq!(Price::from_euros_f64(1e10), Err(PriceError::OutOfRange));
+    }
+
+    #[test]
+    fn price_checked_add_and_sub() {
+        let p: Price = Cents(150).into();
+        assert_eq!(p.checked_add(&Cents(50).into()), Some(Cents(200).into()));
+        assert_eq!(p.checked_sub(&Cents(50).into()), Some(Cents(100).into()));
+        assert_eq!(p.checked_sub(&p), Some(Cents(0).into()));
+    }
+
+    #[test]
+    fn price_checked_add_detects_overflow() {
+        let max: Price = Cents(u32::MAX).into();
+        assert_eq!(max.checked_add(&Cents(1).into()), None);
+    }
+
+    #[test]
+    fn price_checked_sub_detects_underflow() {
+        let p: Price = Cents(100).into();
+        assert_eq!(p.checked_sub(&Cents(101).into()), None);
+    }
+
+    #[test]
+    fn price_as_euros_f64() {
+        let p: Price = Cents(12345).into();
+        assert_eq!(p.as_euros_f64(), 123.45);
+        assert_eq!(
+            Price::from_euros_f64(p.as_euros_f64())
+                .unwrap()
+                .in_cents()
+                .0,
+            12345
+        );
+    }
+
+    #[test]
+    fn price_error_display() {
+        assert_eq!(PriceError::Negative.to_string(), "Price cannot be negative");
+        assert_eq!(PriceError::NotANumber.to_string(), "Price cannot be NaN");
+        assert_eq!(PriceError::OutOfRange.to_string(), "Price is out of range");
+    }
+
     #[test]
     fn quantity_from_kw_roundtrip_in_kw_and_mw() {
         let q: Quantity = Kw(2500).into();
-        assert_eq!(q.in_kw().0, 2500);
+        assert_eq!(q.in_milli_kw(), MilliKw(2_500_000));
         assert_eq!(q.in_mw().0, 2); // integer division
     }
 
     #[test]
     fn quantity_from_mw_converts_to_kw_correctly() {
         let q: Quantity = Mw(3).into();
-        assert_eq!(q.in_kw().0, 3000);
+        assert_eq!(q.as_kw_f64(), 3000.0);
         assert_eq!(q.in_mw().0, 3);
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn quantity_from_milli_kw_keeps_fractional_kw() {
+        let q: Quantity = MilliKw(500).into();
+        assert_eq!(q.in_milli_kw(), MilliKw(500));
+        assert_eq!(q.in_kw().0, 0); // integer division
+        assert_eq!(q.as_kw_f64(), 0.5);
+
+        let q: Quantity = Kw(2).into();
+        assert_eq!(q.in_milli_kw(), MilliKw(2000));
+    }
+
+    #[test]
+    fn quantity_json_roundtrips_fractional_kw() {
+        let q: Quantity = MilliKw(1500).into();
+        let json = serde_json::to_string(&q).unwrap();
+        assert_eq!(json, r#"{"milli_kw":1500}"#);
+        assert_eq!(serde_json::from_str::<Quantity>(&json).unwrap(), q);
+    }
+
+    #[test]
+    fn quantity_json_accepts_whole_kw() {
+        let q: Quantity = serde_json::from_str(r#"{ "kw": 3 }"#).unwrap();
+        assert_eq!(q, Kw(3).into());
+        assert!(serde_json::from_str::<Quantity>(r#"{ "kw": 3, "milli_kw": 1 }"#).is_err());
+        assert!(serde_json::from_str::<Quantity>("{}").is_err());
+    }
+
+    #[test]
+    fn price_json_roundtrips_edge_values() {
+        for cents in [0, 1, u32::MAX - 1, u32::MAX] {
+            let price = Price::from(Cents(cents));
+            let json = serde_json::to_string(&price).unwrap();
+            assert_eq!(serde_json::from_str::<Price>(&json).unwrap(), price);
+        }
+    }
+
+    #[test]
+    fn quantity_json_roundtrips_edge_values() {
+        let edges = [
+            Quantity::from(MilliKw(0)),
+            Quantity::from(Kw(0)),
+            Quantity::from(Kw(u32::MAX)),
+            Quantity::from(Mw(u32::MAX)),
+            Quantity::from(MilliKw(u64::MAX)),
+        ];
+        for q in edges {
+            let json = serde_json::to_string(&q).unwrap();
+            assert_eq!(serde_json::from_str::<Quantity>(&json).unwrap(), q);
+        }
+    }
+
+    /// Deterministic xorshift values, so the property tests need no extra dependencies.
+    fn pseudo_random(seed: u64) -> impl Iterator<Item = u64> {
+        std::iter::successors(Some(seed), |x| {
+            let mut x = *x;
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            Some(x)
+        })
+    }
+
+    #[test]
+    fn price_and_quantity_json_roundtrip_random_values() {
+        for value in pseudo_random(0x9E37_79B9_7F4A_7C15).take(1_000) {
+            let price = Price::from(Cents(value as u32));
+            let json = serde_json::to_string(&price).unwrap();
+            assert_eq!(serde_json::from_str::<Price>(&json).unwrap(), price);
+
+            let q = Quantity::from(MilliKw(value));
+            let json = serde_json::to_string(&q).unwrap();
+            assert_eq!(serde_json::from_str::<Quantity>(&json).unwrap(), q);
+            assert_eq!(q.in_milli_kw(), MilliKw(value));
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn conversions_saturate_at_extreme_values() {
+        let max = Quantity::from(MilliKw(u64::MAX));
+        assert_eq!(max.in_kw(), Kw(u32::MAX));
+        assert_eq!(max.in_mw(), Mw(u32::MAX));
+        assert_eq!(Quantity::from(Kw(u32::MAX)).in_kw(), Kw(u32::MAX));
+        assert_eq!(Quantity::from(Mw(u32::MAX)).in_mw(), Mw(u32::MAX));
+        assert_eq!(Price::from(Euros(u32::MAX)).in_cents(), Cents(u32::MAX));
+        assert_eq!(
+            Price::from(Cents(u32::MAX)).in_euros(),
+            Euros(u32::MAX / 100)
+        );
+    }
+
+    #[test]
+    fn price_checked_from_euros_rejects_unrepresentable_amounts() {
+        assert_eq!(Price::checked_from_euros(Euros(42)), Some(Euros(42).into()));
+        let largest = Euros(u32::MAX / 100);
+        assert_eq!(Price::checked_from_euros(largest), Some(largest.into()));
+        assert_eq!(Price::checked_from_euros(Euros(u32::MAX / 100 + 1)), None);
+        assert_eq!(Price::checked_from_euros(Euros(u32::MAX)), None);
+    }
+
+    #[test]
+    fn cents_add_and_sub() {
+        assert_eq!(Cents(150) + Cents(275), Cents(425));
+        assert_eq!(Cents(275) - Cents(150), Cents(125));
+        assert_eq!(Cents::from(7) + 3.into(), Cents(10));
+    }
+
+    #[test]
+    fn unit_checked_add_and_sub_detect_out_of_range() {
+        assert_eq!(Cents(275).checked_sub(Cents(150)), Some(Cents(125)));
+        assert_eq!(Cents(150).checked_sub(Cents(275)), None);
+        assert_eq!(Kw(u32::MAX).checked_add(Kw(1)), None);
+        assert_eq!(MilliKw(1).checked_add(MilliKw(2)), Some(MilliKw(3)));
+    }
+
+    #[test]
+    fn units_display_with_suffix() {
+        assert_eq!(Mw(12).to_string(), "12 MW");
+        assert_eq!(Kw(500).to_string(), "500 kW");
+        assert_eq!(MilliKw(500).to_string(), "500 W");
+        assert_eq!(Cents(99).to_string(), "99 ct");
+        assert_eq!(Euros(3).to_string(), "3 EUR");
+        assert_eq!((Mw(1) + Mw(2)).to_string(), "3 MW");
+    }
+
+    #[test]
+    fn notional_multiplies_price_by_quantity() {
+        let notional = Price::from(Cents(250)).notional(&Quantity::from(MilliKw(1_500)));
+        assert_eq!(notional.in_milli_cents(), 375_000);
+        assert_eq!(notional.to_string(), "375.000 ct");
+        assert_eq!(notional.as_euros_f64(), 3.75);
+
+        let half = Price::from(Cents(1)).notional(&Quantity::from(MilliKw(500)));
+        assert_eq!(half.to_string(), "0.500 ct");
+        assert_eq!((notional + half).to_string(), "375.500 ct");
+        assert_eq!(notional - half, Notional::from_milli_cents(374_500));
+        assert_eq!(half.checked_sub(&notional), None);
+        assert_eq!(
+            [notional, half].into_iter().sum::<Notional>(),
+            notional + half
+        );
+    }
+
+    #[test]
+    fn notional_of_largest_price_and_quantity_does_not_overflow() {
+        let notional = Price::from(Cents(u32::MAX)).notional(&Quantity::from(MilliKw(u64::MAX)));
+        assert_eq!(
+            notional.in_milli_cents(),
+            u32::MAX as u128 * u64::MAX as u128
+        );
+        assert!(notional > Price::from(Cents(1)).notional(&Quantity::from(MilliKw(u64::MAX))));
+        assert_eq!(
+            Notional::from_milli_cents(u128::MAX).checked_add(&Notional::from_milli_cents(1)),
+            None
+        );
+
+        let json = serde_json::to_string(&notional).unwrap();
+        assert_eq!(serde_json::from_str::<Notional>(&json).unwrap(), notional);
+    }
+
     #[test]
     fn side_enum_variants_exist_and_match() {
         let b = Side::Buy;
@@ -0,0 +1,89 @@
+use super::*;
+
+/// The value of a quantity at a price, e.g. for exposure limits.
+///
+/// With prices per kilowatt, a price in cents times a quantity in thousandths
+/// of a kilowatt is a value in thousandths of a cent, which is what a
+/// `Notional` stores. It is kept in a `u128`, wide enough for the product of
+/// any `Price` and `Quantity`, so computing a notional cannot overflow.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    Serializable,
+)]
+pub struct Notional {
+    milli_cents: u128,
+}
+
+impl Notional {
+    /// A notional of `milli_cents` thousandths of a cent.
+    pub fn from_milli_cents(milli_cents: u128) -> Self {
+        Notional { milli_cents }
+    }
+
+    /// The notional in thousandths of a cent.
+    pub fn in_milli_cents(&self) -> u128 {
+        self.milli_cents
+    }
+
+    /// The notional in euros as a floating-point number.
+    pub fn as_euros_f64(&self) -> f64 {
+        self.milli_cents as f64 / 100_000.0
+    }
+
+    /// The sum of two notionals, or `None` if it does not fit.
+    pub fn checked_add(&self, other: &Notional) -> Option<Notional> {
+        let milli_cents = self.milli_cents.checked_add(other.milli_cents)?;
+        Some(Notional { milli_cents })
+    }
+
+    /// The difference of two notionals, or `None` if it would be negative.
+    pub fn checked_sub(&self, other: &Notional) -> Option<Notional> {
+        let milli_cents = self.milli_cents.checked_sub(other.milli_cents)?;
+        Some(Notional { milli_cents })
+    }
+}
+
+impl std::ops::Add for Notional {
+    type Output = Notional;
+    fn add(self, rhs: Notional) -> Notional {
+        Notional {
+            milli_cents: self.milli_cents + rhs.milli_cents,
+        }
+    }
+}
+
+impl std::ops::Sub for Notional {
+    type Output = Notional;
+    fn sub(self, rhs: Notional) -> Notional {
+        Notional {
+            milli_cents: self.milli_cents - rhs.milli_cents,
+        }
+    }
+}
+
+impl std::iter::Sum for Notional {
+    fn sum<I: Iterator<Item = Notional>>(iter: I) -> Notional {
+        iter.fold(Notional::default(), |acc, n| acc + n)
+    }
+}
+
+/// Shown in cents with three decimals, e.g. `1234.500 ct`.
+impl std::fmt::Display for Notional {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{:03} ct",
+            self.milli_cents / 1_000,
+            self.milli_cents % 1_000
+        )
+    }
+}
@@ -1,7 +1,16 @@
 use super::*;
 
 #[derive(
-    PartialEq, Eq, PartialOrd, Ord, Clone, Debug, serde::Serialize, serde::Deserialize, Serializable,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Debug,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    Serializable,
 )]
 pub struct Price {
     cents: u32,
@@ -13,18 +22,102 @@ impl Price {
     pub fn in_euros(&self) -> Euros {
         Euros(self.cents / 100)
     }
+
+    /// Create a price from a floating-point amount of euros, rounded to the nearest cent.
+    ///
+    /// Halves round away from zero on the decimal value, so `1.005` becomes 101 cents
+    /// even though its binary representation is slightly below 1.005.
+    /// Negative, NaN, infinite and too large amounts are rejected.
+    pub fn from_euros_f64(euros: f64) -> Result<Price, PriceError> {
+        if euros.is_nan() {
+            return Err(PriceError::NotANumber);
+        }
+        if euros < 0.0 {
+            return Err(PriceError::Negative);
+        }
+        // Snap away representation error before rounding to whole cents.
+        let cents = ((euros * 100.0 * 1e6).round() / 1e6).round();
+        if !cents.is_finite() || cents > u32::MAX as f64 {
+            return Err(PriceError::OutOfRange);
+        }
+        Ok(Price {
+            cents: cents as u32,
+        })
+    }
+
+    /// Create a price from whole euros, or `None` if it does not fit in a `Price`.
+    ///
+    /// Unlike `Price::from(Euros(..))`, which saturates at the largest price,
+    /// this reports amounts that cannot be represented.
+    pub fn checked_from_euros(euros: Euros) -> Option<Price> {
+        let cents = euros.0.checked_mul(100)?;
+        Some(Price { cents })
+    }
+
+    /// The price in euros as a floating-point number.
+    pub fn as_euros_f64(&self) -> f64 {
+        self.cents as f64 / 100.0
+    }
+
+    /// The sum of two prices, or `None` if it does not fit in a `Price`.
+    pub fn checked_add(&self, other: &Price) -> Option<Price> {
+        let cents = self.cents.checked_add(other.cents)?;
+        Some(Price { cents })
+    }
+
+    /// The difference of two prices, or `None` if it would be negative.
+    pub fn checked_sub(&self, other: &Price) -> Option<Price> {
+        let cents = self.cents.checked_sub(other.cents)?;
+        Some(Price { cents })
+    }
+
+    /// The value of `quantity` at this price, taking the price to be per kilowatt.
+    pub fn notional(&self, quantity: &Quantity) -> Notional {
+        let milli_cents = self.cents as u128 * quantity.in_milli_kw().0 as u128;
+        Notional::from_milli_cents(milli_cents)
+    }
+}
+
+/// Errors converting a floating-point amount into a `Price`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PriceError {
+    Negative,
+    NotANumber,
+    OutOfRange,
+}
+
+impl std::fmt::Display for PriceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PriceError::Negative => write!(f, "Price cannot be negative"),
+            PriceError::NotANumber => write!(f, "Price cannot be NaN"),
+            PriceError::OutOfRange => write!(f, "Price is out of range"),
+        }
+    }
 }
 
+impl std::error::Error for PriceError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Cents(pub u32);
+unit_newtype!(Cents, "ct");
+
 impl From<Cents> for Price {
     fn from(c: Cents) -> Self {
         Price { cents: c.0 }
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Euros(pub u32);
+unit_newtype!(Euros, "EUR");
+
+/// Amounts beyond what a `Price` can hold saturate at the largest price; use
+/// `Price::checked_from_euros` to detect them instead.
 impl From<Euros> for Price {
     fn from(e: Euros) -> Self {
-        Price { cents: e.0 * 100 }
+        Price {
+            cents: e.0.saturating_mul(100),
+        }
     }
 }
@@ -1,28 +1,113 @@
 use super::*;
 
-#[derive(PartialEq, Eq, Clone, Debug, serde::Serialize, serde::Deserialize, Serializable)]
+/// An amount of power, stored in thousandths of a kilowatt (watts) so that
+/// fractional kilowatts such as 0.5 kW are represented exactly.
+///
+/// Serializes as `{ "milli_kw": ... }`. Human-readable formats also accept
+/// whole kilowatts as `{ "kw": ... }`, the format used before fractional
+/// quantities were supported, so existing configurations still load.
+#[derive(PartialEq, Eq, Clone, Debug, serde::Serialize, Serializable)]
 pub struct Quantity {
-    kw: u32,
+    milli_kw: u64,
 }
 impl Quantity {
+    /// The quantity in whole kilowatts, rounded down.
+    ///
+    /// Fractions of a kilowatt are lost, so comparing or adding the results
+    /// gets fractional quantities wrong. Use `in_milli_kw` for the exact
+    /// quantity, or `as_kw_f64` for a fractional number of kilowatts.
+    ///
+    /// Quantities beyond `u32::MAX` kilowatts saturate at `Kw(u32::MAX)`.
+    #[deprecated(note = "rounds down to whole kilowatts; use `in_milli_kw` or `as_kw_f64`")]
     pub fn in_kw(&self) -> Kw {
-        Kw(self.kw)
+        Kw(u32::try_from(self.milli_kw / 1_000).unwrap_or(u32::MAX))
     }
+
+    /// The quantity in whole megawatts, rounded down.
+    ///
+    /// Quantities beyond `u32::MAX` megawatts saturate at `Mw(u32::MAX)`.
     pub fn in_mw(&self) -> Mw {
-        Mw(self.kw / 1000)
+        Mw(u32::try_from(self.milli_kw / 1_000_000).unwrap_or(u32::MAX))
+    }
+    pub fn in_milli_kw(&self) -> MilliKw {
+        MilliKw(self.milli_kw)
+    }
+
+    /// The quantity in kilowatts as a floating-point number, including fractions.
+    pub fn as_kw_f64(&self) -> f64 {
+        self.milli_kw as f64 / 1_000.0
     }
 }
 
+impl<'de> serde::Deserialize<'de> for Quantity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct Compact {
+            milli_kw: u64,
+        }
+
+        #[derive(serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Readable {
+            kw: Option<u32>,
+            milli_kw: Option<u64>,
+        }
+
+        // Binary formats carry exactly the serialized field.
+        if !deserializer.is_human_readable() {
+            let Compact { milli_kw } = Compact::deserialize(deserializer)?;
+            return Ok(Quantity { milli_kw });
+        }
+        match Readable::deserialize(deserializer)? {
+            Readable {
+                kw: Some(kw),
+                milli_kw: None,
+            } => Ok(Kw(kw).into()),
+            Readable {
+                kw: None,
+                milli_kw: Some(milli_kw),
+            } => Ok(MilliKw(milli_kw).into()),
+            _ => Err(serde::de::Error::custom(
+                "expected exactly one of `kw` and `milli_kw`",
+            )),
+        }
+    }
+}
+
+/// One thousandth of a kilowatt, i.e. one watt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MilliKw(pub u64);
+unit_newtype!(MilliKw, u64, "W");
+
+impl From<MilliKw> for Quantity {
+    fn from(m: MilliKw) -> Self {
+        Quantity { milli_kw: m.0 }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Kw(pub u32);
+unit_newtype!(Kw, "kW");
+
 impl From<Kw> for Quantity {
     fn from(k: Kw) -> Self {
-        Quantity { kw: k.0 }
+        Quantity {
+            milli_kw: k.0 as u64 * 1_000,
+        }
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Mw(pub u32);
+unit_newtype!(Mw, "MW");
+
 impl From<Mw> for Quantity {
     fn from(m: Mw) -> Self {
-        Quantity { kw: m.0 * 1_000 }
+        Quantity {
+            milli_kw: m.0 as u64 * 1_000_000,
+        }
     }
 }
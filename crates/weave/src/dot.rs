@@ -0,0 +1,61 @@
+use crate::WeaveNode;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Render the channel graph of `nodes` in Graphviz DOT format.
+///
+/// Nodes are named by their index (`n0`, `n1`, ...), and each channel read by a
+/// node gets an edge from its producer, labelled with the channel name. Inputs
+/// without a producer in `nodes` are left out.
+pub fn to_dot<W, T>(nodes: &[W]) -> String
+where
+    W: WeaveNode<T>,
+{
+    write_dot(nodes, &[])
+}
+
+/// Render `nodes` like `to_dot`, drawing each named group of node indices as a
+/// cluster.
+pub(crate) fn write_dot<W, T>(nodes: &[W], clusters: &[(&str, Vec<usize>)]) -> String
+where
+    W: WeaveNode<T>,
+{
+    let mut producer_of = HashMap::new();
+    for (idx, node) in nodes.iter().enumerate() {
+        for ch in node.output_channels() {
+            producer_of.entry(ch).or_insert(idx);
+        }
+    }
+
+    let mut out = String::from("digraph weave {\n");
+    for (cluster, (name, members)) in clusters.iter().enumerate() {
+        let _ = writeln!(out, "    subgraph cluster_{cluster} {{");
+        let _ = writeln!(out, "        label=\"{}\";", escape(name));
+        for idx in members {
+            let _ = writeln!(out, "        n{idx};");
+        }
+        out.push_str("    }\n");
+    }
+    for idx in 0..nodes.len() {
+        if !clusters.iter().any(|(_, members)| members.contains(&idx)) {
+            let _ = writeln!(out, "    n{idx};");
+        }
+    }
+    for (consumer, node) in nodes.iter().enumerate() {
+        for ch in node.input_channels() {
+            if let Some(producer) = producer_of.get(&ch) {
+                let _ = writeln!(
+                    out,
+                    "    n{producer} -> n{consumer} [label=\"{}\"];",
+                    escape(&ch)
+                );
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
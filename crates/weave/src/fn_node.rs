@@ -0,0 +1,82 @@
+use crate::WeaveNode;
+use channels::{ChannelRegistry, RegistryError};
+
+/// A `WeaveNode` built from explicit channel lists and a closure.
+///
+/// This is a lightweight way to put one-off nodes (test fixtures, glue code)
+/// into `weave_nodes` without defining a full node package. The `build` closure
+/// is called when the node is weaved and produces the woven value. By default
+/// the node registers no channels; use `with_register` to register its outputs
+/// so that consumers can read from them.
+pub struct FnNode<T, F>
+where
+    F: Fn(&mut ChannelRegistry) -> Result<T, RegistryError>,
+{
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    register: Option<RegisterFn>,
+    priority: i32,
+    build: F,
+}
+
+type RegisterFn = Box<dyn Fn(&mut ChannelRegistry) -> Result<(), RegistryError>>;
+
+impl<T, F> FnNode<T, F>
+where
+    F: Fn(&mut ChannelRegistry) -> Result<T, RegistryError>,
+{
+    pub fn new<I, O>(inputs: I, outputs: O, build: F) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+        O: IntoIterator,
+        O::Item: Into<String>,
+    {
+        Self {
+            inputs: inputs.into_iter().map(Into::into).collect(),
+            outputs: outputs.into_iter().map(Into::into).collect(),
+            register: None,
+            priority: 0,
+            build,
+        }
+    }
+
+    /// Set the closure used to register the node's channels before weaving.
+    pub fn with_register(
+        mut self,
+        register: impl Fn(&mut ChannelRegistry) -> Result<(), RegistryError> + 'static,
+    ) -> Self {
+        self.register = Some(Box::new(register));
+        self
+    }
+
+    /// Set the node's `weave_priority`.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+impl<T, F> WeaveNode<T> for FnNode<T, F>
+where
+    F: Fn(&mut ChannelRegistry) -> Result<T, RegistryError>,
+{
+    fn input_channels(&self) -> Vec<String> {
+        self.inputs.clone()
+    }
+    fn output_channels(&self) -> Vec<String> {
+        self.outputs.clone()
+    }
+    fn register_channels(&self, channels: &mut ChannelRegistry) -> Result<(), RegistryError> {
+        match &self.register {
+            Some(register) => register(channels),
+            None => Ok(()),
+        }
+    }
+    fn weave_priority(&self) -> i32 {
+        self.priority
+    }
+    fn weave(&self, channels: &mut ChannelRegistry) -> Result<T, RegistryError> {
+        (self.build)(channels)
+    }
+}
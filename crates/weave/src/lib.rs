@@ -2,8 +2,16 @@ use channels::{ChannelRegistry, RegistryError};
 use serialization::Serializable;
 use std::ops::Deref;
 
+mod dot;
+mod fn_node;
+mod lint;
 mod node_weaving;
+mod subgraph;
+pub use dot::to_dot;
+pub use fn_node::FnNode;
+pub use lint::{lint_graph, GraphLint};
 pub use node_weaving::*;
+pub use subgraph::{flatten_subgraphs, FlatGraph, Subgraph};
 
 pub trait NodePackage<E>: Serializable + Sized
 where
@@ -27,8 +35,45 @@ where
     /// that can be accessed by other nodes but need not be part of the topological sorting.
     fn register_channels(&self, channels: &mut ChannelRegistry) -> Result<(), RegistryError>;
 
+    /// Whether the node emits intents, i.e., acts on the market.
+    ///
+    /// Used by `lint_graph` to find nodes that can never be triggered.
+    /// Defaults to `false`.
+    fn emits_intents(&self) -> bool {
+        false
+    }
+
+    /// Tiebreaker for the node's place in the topological order.
+    ///
+    /// Among nodes that become ready at the same time, higher priorities are
+    /// woven, and so executed, first. Nodes of equal priority keep the order
+    /// they would have without priorities. Defaults to 0.
+    fn weave_priority(&self) -> i32 {
+        0
+    }
+
+    /// Create the node's input channels that are not yet registered, with
+    /// default values.
+    ///
+    /// Called by lenient weaving for nodes reading a channel without a
+    /// producer. The default creates nothing, so such nodes still fail to weave.
+    fn register_input_defaults(
+        &self,
+        _channels: &mut ChannelRegistry,
+    ) -> Result<(), RegistryError> {
+        Ok(())
+    }
+
     /// Weave the node into the given channel registry.
     fn weave(&self, channels: &mut ChannelRegistry) -> Result<E, RegistryError>;
+
+    /// The id the node is configured with, if it has one, such as a block's
+    /// block id.
+    ///
+    /// Used by `resolve_producers` to name producers. Defaults to `None`.
+    fn node_id(&self) -> Option<u32> {
+        None
+    }
 }
 
 pub trait EmbeddedNode<P>: Sized + 'static
@@ -62,8 +107,45 @@ pub trait WeaveNode<E> {
     /// that can be accessed by other nodes but need not be part of the topological sorting.
     fn register_channels(&self, channels: &mut ChannelRegistry) -> Result<(), RegistryError>;
 
+    /// Whether the node emits intents, i.e., acts on the market.
+    ///
+    /// Used by `lint_graph` to find nodes that can never be triggered.
+    /// Defaults to `false`.
+    fn emits_intents(&self) -> bool {
+        false
+    }
+
+    /// Tiebreaker for the node's place in the topological order.
+    ///
+    /// Among nodes that become ready at the same time, higher priorities are
+    /// woven, and so executed, first. Nodes of equal priority keep the order
+    /// they would have without priorities. Defaults to 0.
+    fn weave_priority(&self) -> i32 {
+        0
+    }
+
+    /// Create the node's input channels that are not yet registered, with
+    /// default values.
+    ///
+    /// Called by lenient weaving for nodes reading a channel without a
+    /// producer. The default creates nothing, so such nodes still fail to weave.
+    fn register_input_defaults(
+        &self,
+        _channels: &mut ChannelRegistry,
+    ) -> Result<(), RegistryError> {
+        Ok(())
+    }
+
     /// Weave the node into the given channel registry.
     fn weave(&self, channels: &mut ChannelRegistry) -> Result<E, RegistryError>;
+
+    /// The id the node is configured with, if it has one, such as a block's
+    /// block id.
+    ///
+    /// Used by `resolve_producers` to name producers. Defaults to `None`.
+    fn node_id(&self) -> Option<u32> {
+        None
+    }
 }
 
 impl<P, E> WeaveNode<E> for P
@@ -80,12 +162,27 @@ where
     fn register_channels(&self, channels: &mut ChannelRegistry) -> Result<(), RegistryError> {
         NodePackage::<E>::register_channels(self, channels)
     }
+    fn emits_intents(&self) -> bool {
+        NodePackage::<E>::emits_intents(self)
+    }
+    fn weave_priority(&self) -> i32 {
+        NodePackage::<E>::weave_priority(self)
+    }
+    fn register_input_defaults(&self, channels: &mut ChannelRegistry) -> Result<(), RegistryError> {
+        NodePackage::<E>::register_input_defaults(self, channels)
+    }
     fn weave(&self, channels: &mut ChannelRegistry) -> Result<E, RegistryError> {
         NodePackage::<E>::weave(self, channels)
     }
+    fn node_id(&self) -> Option<u32> {
+        NodePackage::<E>::node_id(self)
+    }
 }
 
 /// Topologically ordered items for execution in a weave.
+///
+/// `weave_nodes` and `incremental_weave` return their woven nodes in this
+/// wrapper, so consumers such as execution plans get the ordering by type.
 pub struct TopoOrdered<T>(pub Vec<T>);
 impl<T> Deref for TopoOrdered<T> {
     type Target = Vec<T>;
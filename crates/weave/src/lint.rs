@@ -0,0 +1,52 @@
+use crate::WeaveNode;
+use std::collections::HashSet;
+
+/// A likely misconfiguration found by `lint_graph`.
+///
+/// Lints are not errors; the graph may still weave and run, but probably not
+/// as intended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GraphLint {
+    /// A node that emits intents reads a channel that no node produces and that
+    /// is not declared external, so the input will never change.
+    UnconnectedTrigger { node: usize, channel: String },
+}
+
+impl std::fmt::Display for GraphLint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphLint::UnconnectedTrigger { node, channel } => write!(
+                f,
+                "Node {node} emits intents but its input '{channel}' has no producer \
+                 and is not declared external"
+            ),
+        }
+    }
+}
+
+/// Check a graph for intent-emitting nodes with inputs that can never change.
+///
+/// A channel read by a node is connected if another node lists it as an output
+/// or it is in `external`, the channels fed from outside the graph. Inputs of
+/// intent-emitting nodes (see `WeaveNode::emits_intents`) that are not
+/// connected are reported, since the node will only ever see the channel's
+/// initial value: an order block whose trigger is never produced never trades.
+pub fn lint_graph<W, T>(nodes: &[W], external: &[&str]) -> Vec<GraphLint>
+where
+    W: WeaveNode<T>,
+{
+    let produced: HashSet<String> = nodes.iter().flat_map(|n| n.output_channels()).collect();
+
+    let mut lints = Vec::new();
+    for (node, n) in nodes.iter().enumerate() {
+        if !n.emits_intents() {
+            continue;
+        }
+        for channel in n.input_channels() {
+            if !produced.contains(&channel) && !external.contains(&channel.as_str()) {
+                lints.push(GraphLint::UnconnectedTrigger { node, channel });
+            }
+        }
+    }
+    lints
+}
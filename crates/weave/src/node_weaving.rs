@@ -1,6 +1,41 @@
-use crate::{TopoOrdered, WeaveNode};
+use crate::{EmbeddedNode, NodePackage, TopoOrdered, WeaveNode};
 use channels::{errors::RegistryError, ChannelRegistry};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Options for `weave_nodes_with_options`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WeaveOptions {
+    /// Allow input channels without a producer.
+    ///
+    /// Instead of failing the weave, each missing channel is created with its
+    /// type's default value (see `WeaveNode::register_input_defaults`) and
+    /// reported as a `WeaveWarning`. Useful while a graph is only partly wired;
+    /// production graphs should weave strictly. Blocks only create their
+    /// missing inputs if declared with `#[input(defaults)]`, and never their
+    /// optional inputs; for other inputs the weave still fails.
+    pub lenient: bool,
+}
+
+/// A problem tolerated by a lenient weave.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WeaveWarning {
+    /// The input channel of a node had no producer and was created with a
+    /// default value.
+    MissingProducer { node: usize, channel: String },
+}
+
+impl std::fmt::Display for WeaveWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeaveWarning::MissingProducer { node, channel } => write!(
+                f,
+                "Missing producer for input channel '{channel}' (node index {node}); \
+                 created with a default value"
+            ),
+        }
+    }
+}
 
 /// Topologically sort the nodes and ensuring that all input channels have a producer,
 /// that only one node produces each output channel, and that there are no cycles.
@@ -9,6 +44,23 @@ pub fn weave_nodes<W, T>(
     nodes: &[W],
     registry: &mut ChannelRegistry,
 ) -> Result<TopoOrdered<T>, RegistryError>
+where
+    W: WeaveNode<T> + 'static,
+    T: 'static,
+{
+    let (woven, _warnings) = weave_nodes_with_options(nodes, registry, WeaveOptions::default())?;
+    Ok(woven)
+}
+
+/// Weave the nodes like `weave_nodes`, with the given options.
+///
+/// Returns the woven nodes together with the warnings for the problems the
+/// options allowed. A strict weave (the default options) never has warnings.
+pub fn weave_nodes_with_options<W, T>(
+    nodes: &[W],
+    registry: &mut ChannelRegistry,
+    options: WeaveOptions,
+) -> Result<(TopoOrdered<T>, Vec<WeaveWarning>), RegistryError>
 where
     W: WeaveNode<T> + 'static,
     T: 'static,
@@ -20,15 +72,116 @@ where
 
     // Compute the topological ordering of the nodes based on their input/output channels.
     let producer_of = producer_map(nodes);
-    let edges = build_edges(nodes, registry, &producer_of)?;
-    let topo = topo_order_or_cycle(&edges)?;
+    let (edges, missing) = build_edges(nodes, registry, &producer_of);
+    let warnings = handle_missing_producers(nodes, registry, missing, options)?;
+    let priorities: Vec<i32> = nodes.iter().map(|node| node.weave_priority()).collect();
+    let topo = topo_order_or_cycle(&edges, &priorities)
+        .map_err(|cycle| cycle_error(nodes, &producer_of, &cycle))?;
+
+    // Finally, weave the nodes in topological order. Writers created before a
+    // node fails to weave are forgotten, so the weave can be retried.
+    let out = registry.with_writer_rollback(|registry| {
+        topo.into_iter()
+            .map(|idx| nodes[idx].weave(registry))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    Ok((TopoOrdered(out), warnings))
+}
+
+/// Check that `nodes` would weave strictly, without touching the registry.
+///
+/// Runs the checks of `weave_nodes`: each output channel has one producer,
+/// each input channel has a producer or already exists in `registry`, and the
+/// graph has no cycles. No channels are registered and no nodes are woven, so
+/// this is safe for checking a configuration before running it.
+///
+/// Only input and output channels are considered. Other channels a node
+/// registers while weaving, and errors from registering or weaving itself,
+/// are not detected.
+pub fn validate_weave<W, T>(nodes: &[W], registry: &ChannelRegistry) -> Result<(), RegistryError>
+where
+    W: WeaveNode<T>,
+    T: 'static,
+{
+    let producer_of = unique_producer_map(nodes)?;
+    let (edges, missing) = build_edges(nodes, registry, &producer_of);
+    if let Some((node, channel)) = missing.first() {
+        return Err(missing_producer(*node, channel));
+    }
+    let priorities: Vec<i32> = nodes.iter().map(|node| node.weave_priority()).collect();
+    topo_order_or_cycle(&edges, &priorities)
+        .map_err(|cycle| cycle_error(nodes, &producer_of, &cycle))?;
+    Ok(())
+}
 
-    // Finally, weave the nodes in topological order.
-    let mut out = Vec::with_capacity(nodes.len());
-    for idx in topo {
-        out.push(nodes[idx].weave(registry)?);
+/// Re-weave a set of node packages, reusing the nodes of a previous weave where possible.
+///
+/// Each new package that equals (by `PartialEq`) a package extracted from one of the
+/// `old` embeddings is replaced by that extracted package, so any state the old
+/// embedding carries is woven into the new node. Packages without a match are woven
+/// from scratch. Each old embedding is reused at most once.
+///
+/// The result is the same as calling `weave_nodes` on the merged packages, including
+/// the topological ordering and all validation.
+///
+/// The writers of the old embeddings' output channels are released (see
+/// `ChannelRegistry::release_writers_of`), so after a successful weave the old
+/// embeddings should not be executed again. If the weave fails, the registry's
+/// writers are left as they were, so the old embeddings stay usable and the
+/// weave can be retried.
+///
+/// After a successful weave, output channels of the old embeddings that no new
+/// package produces or reads are removed from the registry, so channels of
+/// removed nodes do not pile up across re-weaves. Only channels the weave
+/// created are removed (see `ChannelRegistry::is_woven_output`); channels the
+/// user registered before weaving are kept. Of the weave's own channels, only
+/// the new packages count as users: readers outside this weave, such as
+/// `StableChannel`s, other plans woven into the same registry, or actors
+/// holding the channel, are not considered, and keep the removed value, which
+/// is no longer written. Register such channels up front to keep them.
+pub fn incremental_weave<P, E>(
+    old: &[E],
+    new_packages: &[P],
+    registry: &mut ChannelRegistry,
+) -> Result<TopoOrdered<E>, RegistryError>
+where
+    P: NodePackage<E> + PartialEq + Clone + 'static,
+    E: EmbeddedNode<P>,
+{
+    let mut old_packages: Vec<Option<P>> = old.iter().map(|e| Some(e.extract_package())).collect();
+    let old_outputs: HashSet<String> = old_packages
+        .iter()
+        .flatten()
+        .flat_map(|pkg| pkg.output_channels())
+        .collect();
+
+    let merged: Vec<P> = new_packages
+        .iter()
+        .map(|new_pkg| {
+            old_packages
+                .iter_mut()
+                .find(|old_pkg| old_pkg.as_ref() == Some(new_pkg))
+                .and_then(Option::take)
+                .unwrap_or_else(|| new_pkg.clone())
+        })
+        .collect();
+
+    let woven = registry.with_writer_rollback(|registry| {
+        // The new nodes' writers take over from those of the old embeddings.
+        registry.release_writers_of(&old_outputs);
+        weave_nodes(&merged, registry)
+    })?;
+
+    let in_use: HashSet<String> = merged
+        .iter()
+        .flat_map(|pkg| [pkg.output_channels(), pkg.input_channels()].concat())
+        .collect();
+    for channel in old_outputs.difference(&in_use) {
+        if registry.is_woven_output(channel) {
+            registry.remove(channel);
+        }
     }
-    Ok(TopoOrdered(out))
+    Ok(woven)
 }
 
 /// Register all channels of all nodes in the registry.
@@ -50,6 +203,48 @@ where
     Ok(())
 }
 
+/// Map each output channel of `nodes` to the id of the node that produces it,
+/// the way weaving resolves producers.
+///
+/// Nodes are identified by their configured id (see `WeaveNode::node_id`), so
+/// for blocks by their block id. Nodes without an id are identified by their
+/// index in `nodes`, as in weave errors and warnings. Useful for showing where
+/// a channel's data comes from. Fails with `RegistryError::DuplicateOutputKey`
+/// if two nodes produce the same channel, which would also fail the weave.
+pub fn resolve_producers<W, T>(nodes: &[W]) -> Result<HashMap<String, u32>, RegistryError>
+where
+    W: WeaveNode<T>,
+    T: 'static,
+{
+    Ok(unique_producer_map(nodes)?
+        .into_iter()
+        .map(|(channel, idx)| {
+            let index = u32::try_from(idx).unwrap_or(u32::MAX);
+            let id = nodes[idx].node_id().unwrap_or(index);
+            (channel, id)
+        })
+        .collect())
+}
+
+/// Map each output channel to the index of the node that produces it, failing
+/// with `RegistryError::DuplicateOutputKey` if two nodes produce it.
+fn unique_producer_map<W, T>(nodes: &[W]) -> Result<HashMap<String, usize>, RegistryError>
+where
+    W: WeaveNode<T>,
+    T: 'static,
+{
+    let mut producer_of = HashMap::<String, usize>::new();
+    for (i, node) in nodes.iter().enumerate() {
+        for ch in node.output_channels() {
+            if producer_of.contains_key(&ch) {
+                return Err(RegistryError::DuplicateOutputKey(ch));
+            }
+            producer_of.insert(ch, i);
+        }
+    }
+    Ok(producer_of)
+}
+
 /// Map each output channel to the index of the node that produces it.
 /// This is used in the topological sort.
 fn producer_map<W, T>(nodes: &[W]) -> HashMap<String, usize>
@@ -73,11 +268,14 @@ where
 /// This only takes input/output channels into account. It is valid for nodes
 /// to register other channels, and for other nodes to read from them, but this
 /// will not affect the topological ordering.
+///
+/// Input channels that neither have a producer nor are in the registry are
+/// returned alongside the edges, as (node index, channel) pairs.
 fn build_edges<W, T>(
     nodes: &[W],
     registry: &ChannelRegistry,
     producer_of: &HashMap<String, usize>,
-) -> Result<Vec<HashSet<usize>>, RegistryError>
+) -> (Vec<HashSet<usize>>, Vec<(usize, String)>)
 where
     W: WeaveNode<T>,
     T: 'static,
@@ -85,6 +283,7 @@ where
     let n = nodes.len();
     let mut edges: Vec<HashSet<usize>> = vec![HashSet::new(); n];
     let mut indegree: Vec<usize> = vec![0; n];
+    let mut missing = Vec::new();
 
     for (consumer, node) in nodes.iter().enumerate() {
         for ch in node.input_channels() {
@@ -93,20 +292,62 @@ where
                     indegree[consumer] += 1;
                 }
             } else if !registry.has(&ch) {
-                return Err(RegistryError::MissingProducer(format!(
-                    "Missing producer for input channel '{ch}' (node index {consumer})"
-                )));
+                missing.push((consumer, ch));
             }
         }
     }
 
-    Ok(edges)
+    (edges, missing)
+}
+
+/// Fail on the first input channel without a producer, or, in a lenient weave,
+/// create the missing channels with default values and report them as warnings.
+fn handle_missing_producers<W, T>(
+    nodes: &[W],
+    registry: &mut ChannelRegistry,
+    missing: Vec<(usize, String)>,
+    options: WeaveOptions,
+) -> Result<Vec<WeaveWarning>, RegistryError>
+where
+    W: WeaveNode<T>,
+    T: 'static,
+{
+    if !options.lenient {
+        return match missing.first() {
+            Some((node, channel)) => Err(missing_producer(*node, channel)),
+            None => Ok(Vec::new()),
+        };
+    }
+
+    let mut warnings = Vec::with_capacity(missing.len());
+    for (node, channel) in missing {
+        nodes[node].register_input_defaults(registry)?;
+        // Nodes that cannot create default inputs still fail.
+        if !registry.has(&channel) {
+            return Err(missing_producer(node, &channel));
+        }
+        warnings.push(WeaveWarning::MissingProducer { node, channel });
+    }
+    Ok(warnings)
+}
+
+fn missing_producer(node: usize, channel: &str) -> RegistryError {
+    RegistryError::MissingProducer(format!(
+        "Missing producer for input channel '{channel}' (node index {node})"
+    ))
 }
 
 /// Perform a topological sort on the given edges. If a cycle is detected,
-/// an error is returned with the indices of the nodes involved in the cycle.
+/// the nodes of one cycle are returned as the error, in edge order, so each
+/// node feeds the next and the last feeds the first.
 /// Otherwise, we return a vector of node indices in topological order.
-fn topo_order_or_cycle(edges: &[HashSet<usize>]) -> Result<Vec<usize>, RegistryError> {
+///
+/// Among the nodes ready at any point, those with the highest priority are
+/// taken first; ties go to the node that became ready first.
+fn topo_order_or_cycle(
+    edges: &[HashSet<usize>],
+    priorities: &[i32],
+) -> Result<Vec<usize>, Vec<usize>> {
     let n = edges.len();
 
     let mut indegree = vec![0usize; n];
@@ -116,31 +357,94 @@ fn topo_order_or_cycle(edges: &[HashSet<usize>]) -> Result<Vec<usize>, RegistryE
         }
     }
 
-    let mut q: VecDeque<usize> = indegree
-        .iter()
-        .enumerate()
-        .filter_map(|(i, &d)| (d == 0).then_some(i))
-        .collect();
+    // Ready nodes keyed by priority, then by the order they became ready in.
+    let mut ready = 0usize;
+    let mut q: BinaryHeap<(i32, Reverse<usize>, usize)> = BinaryHeap::new();
+    let mut push = |q: &mut BinaryHeap<_>, v: usize| {
+        q.push((priorities[v], Reverse(ready), v));
+        ready += 1;
+    };
+    for v in (0..n).filter(|&i| indegree[i] == 0) {
+        push(&mut q, v);
+    }
 
     let mut topo = Vec::with_capacity(n);
-    while let Some(u) = q.pop_front() {
+    while let Some((_, _, u)) = q.pop() {
         topo.push(u);
         for &v in edges[u].iter() {
             indegree[v] -= 1;
             if indegree[v] == 0 {
-                q.push_back(v);
+                push(&mut q, v);
             }
         }
     }
 
     if topo.len() != n {
-        let cyclic: Vec<usize> = indegree
-            .iter()
-            .enumerate()
-            .filter_map(|(i, &d)| (d > 0).then_some(i))
-            .collect();
-        return Err(RegistryError::CycleDetected(format!("{cyclic:?}")));
+        return Err(find_cycle(edges, &indegree));
     }
 
     Ok(topo)
 }
+
+/// Extract one cycle from the nodes a topological sort could not order.
+///
+/// Every unordered node (nonzero remaining indegree) has a producer that is also
+/// unordered, so walking from producer to producer must eventually revisit a
+/// node. The revisited stretch of the walk is a cycle. Producers are picked by
+/// lowest index to keep the reported cycle deterministic.
+fn find_cycle(edges: &[HashSet<usize>], indegree: &[usize]) -> Vec<usize> {
+    let unordered = |i: usize| indegree[i] > 0;
+    let producer = |v: usize| {
+        (0..edges.len())
+            .find(|&u| unordered(u) && edges[u].contains(&v))
+            .expect("unordered nodes have an unordered producer")
+    };
+
+    let start = (0..edges.len())
+        .find(|&i| unordered(i))
+        .expect("a failed sort leaves unordered nodes");
+    let mut walk = vec![start];
+    let mut node = producer(start);
+    while !walk.contains(&node) {
+        walk.push(node);
+        node = producer(node);
+    }
+    let first = walk.iter().position(|&i| i == node).unwrap_or(0);
+    let mut cycle = walk.split_off(first);
+    // The walk followed edges backwards.
+    cycle.reverse();
+    cycle
+}
+
+/// Describe a cycle found by `topo_order_or_cycle` by the channels connecting
+/// its nodes, so the user knows which connections could be cut to break it.
+fn cycle_error<W, T>(
+    nodes: &[W],
+    producer_of: &HashMap<String, usize>,
+    cycle: &[usize],
+) -> RegistryError
+where
+    W: WeaveNode<T>,
+    T: 'static,
+{
+    let links: Vec<String> = cycle
+        .iter()
+        .zip(cycle.iter().cycle().skip(1))
+        .map(|(&producer, &consumer)| {
+            let channels: Vec<String> = nodes[consumer]
+                .input_channels()
+                .into_iter()
+                .filter(|ch| producer_of.get(ch) == Some(&producer))
+                .map(|ch| format!("'{ch}'"))
+                .collect();
+            format!(
+                "node {producer} -> node {consumer} via {}",
+                channels.join(", ")
+            )
+        })
+        .collect();
+    RegistryError::CycleDetected(format!(
+        "nodes {cycle:?}: {}; cut one of these connections to break the cycle",
+        links.join(", ")
+    ))
+}
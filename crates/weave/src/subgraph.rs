@@ -0,0 +1,87 @@
+use crate::{dot, lint_graph, GraphLint, WeaveNode};
+
+/// A named group of nodes, e.g. "signals" or "execution".
+///
+/// Subgraphs only organize a graph for visualization and reporting; they do
+/// not change how it is woven. Flatten them with `flatten_subgraphs` and pass
+/// the resulting nodes to `weave_nodes` as usual.
+pub struct Subgraph<W> {
+    pub name: String,
+    pub nodes: Vec<W>,
+}
+
+impl<W> Subgraph<W> {
+    pub fn new(name: impl Into<String>, nodes: Vec<W>) -> Self {
+        Self {
+            name: name.into(),
+            nodes,
+        }
+    }
+}
+
+/// The nodes of several subgraphs in one list, remembering which subgraph
+/// each node came from.
+pub struct FlatGraph<W> {
+    /// All nodes, subgraph by subgraph, in the order the subgraphs were given.
+    pub nodes: Vec<W>,
+    names: Vec<String>,
+    membership: Vec<usize>,
+}
+
+/// Flatten subgraphs into a single node list for weaving.
+pub fn flatten_subgraphs<W>(subgraphs: impl IntoIterator<Item = Subgraph<W>>) -> FlatGraph<W> {
+    let mut flat = FlatGraph {
+        nodes: Vec::new(),
+        names: Vec::new(),
+        membership: Vec::new(),
+    };
+    for (group, subgraph) in subgraphs.into_iter().enumerate() {
+        flat.membership
+            .extend(std::iter::repeat_n(group, subgraph.nodes.len()));
+        flat.nodes.extend(subgraph.nodes);
+        flat.names.push(subgraph.name);
+    }
+    flat
+}
+
+impl<W> FlatGraph<W> {
+    /// Name of the subgraph holding the node at `node` in `nodes`.
+    pub fn subgraph_of(&self, node: usize) -> Option<&str> {
+        let group = *self.membership.get(node)?;
+        Some(&self.names[group])
+    }
+
+    /// Lint the flattened graph with `lint_graph`, pairing each lint with the
+    /// subgraph of the node it reports on.
+    pub fn lint<T>(&self, external: &[&str]) -> Vec<(&str, GraphLint)>
+    where
+        W: WeaveNode<T>,
+    {
+        lint_graph(&self.nodes, external)
+            .into_iter()
+            .map(|lint| {
+                let GraphLint::UnconnectedTrigger { node, .. } = &lint;
+                (self.names[self.membership[*node]].as_str(), lint)
+            })
+            .collect()
+    }
+
+    /// Render the graph like `to_dot`, with one cluster per subgraph.
+    pub fn to_dot<T>(&self) -> String
+    where
+        W: WeaveNode<T>,
+    {
+        let clusters: Vec<(&str, Vec<usize>)> = self
+            .names
+            .iter()
+            .enumerate()
+            .map(|(group, name)| {
+                let members = (0..self.nodes.len())
+                    .filter(|&idx| self.membership[idx] == group)
+                    .collect();
+                (name.as_str(), members)
+            })
+            .collect();
+        dot::write_dot(&self.nodes, &clusters)
+    }
+}
@@ -0,0 +1,30 @@
+use ::block_macros::*;
+use ::block_traits::BlockSpecAssociatedTypes;
+
+mod default_name {
+    use super::*;
+
+    make_defaults!(input, output, state, init_params);
+
+    #[block(block_spec = false)]
+    pub struct NamedBlock;
+}
+
+mod custom_name {
+    use super::*;
+
+    make_defaults!(input, output, state, init_params);
+
+    #[block(block_spec = false, name = "custom-name")]
+    pub struct RenamedBlock;
+}
+
+#[test]
+fn block_name_defaults_to_struct_name() {
+    assert_eq!(default_name::NamedBlock::NAME, "NamedBlock");
+}
+
+#[test]
+fn block_name_can_be_overridden() {
+    assert_eq!(custom_name::RenamedBlock::NAME, "custom-name");
+}
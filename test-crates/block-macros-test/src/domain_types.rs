@@ -0,0 +1,60 @@
+use ::block_macros::*;
+use ::channels::{ChannelRegistry, InputKeys as _, OutputKeys as _};
+use trade_types::{Cents, Price};
+
+/// Input with a field of a non-`Copy` domain type.
+#[input]
+#[allow(dead_code)]
+struct Input {
+    limit: Price,
+    fallback: Option<Price>,
+}
+
+#[output]
+#[allow(dead_code)]
+struct Output {
+    quote: Price,
+}
+
+#[test]
+fn input_reader_reads_price_channels() {
+    let mut registry = ChannelRegistry::new();
+    registry.put("limit", Price::from(Cents(125))).unwrap();
+    registry.put("fallback", Price::from(Cents(99))).unwrap();
+
+    let keys = InputKeys {
+        limit: "limit".to_string(),
+        fallback: Some("fallback".to_string()),
+    };
+    let input = keys.reader(&registry).unwrap().read();
+    assert_eq!(input.limit, Price::from(Cents(125)));
+    assert_eq!(input.fallback, Some(Price::from(Cents(99))));
+
+    // The reader sees later writes to the channel.
+    let reader = keys.reader(&registry).unwrap();
+    *registry.get::<Price>("limit").unwrap().borrow_mut() = Price::from(Cents(130));
+    assert_eq!(reader.read().limit, Price::from(Cents(130)));
+}
+
+#[test]
+fn output_writer_feeds_price_input() {
+    let mut registry = ChannelRegistry::new();
+    let out_keys = OutputKeys {
+        quote: "quote".to_string(),
+    };
+    out_keys.register(&mut registry).unwrap();
+    let writer = out_keys.writer(&registry).unwrap();
+
+    let in_keys = InputKeys {
+        limit: "quote".to_string(),
+        fallback: None,
+    };
+    let reader = in_keys.reader(&registry).unwrap();
+
+    writer.write(&Output {
+        quote: Price::from(Cents(250)),
+    });
+    let input = reader.read();
+    assert_eq!(input.limit, Price::from(Cents(250)));
+    assert_eq!(input.fallback, None);
+}
@@ -37,7 +37,7 @@ mod self_only_no_return {
 
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -60,7 +60,7 @@ mod self_only_unit_return {
 
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -81,7 +81,7 @@ mod self_only_output {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -104,7 +104,7 @@ mod self_only_state {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -129,7 +129,7 @@ mod self_only_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -152,7 +152,7 @@ mod self_only_output_state {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -175,7 +175,7 @@ mod self_only_output_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -198,7 +198,7 @@ mod self_only_state_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -221,7 +221,7 @@ mod self_only_full_tuple {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -244,7 +244,7 @@ mod self_only_option_output_none {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -267,7 +267,7 @@ mod self_only_option_output_some {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -290,7 +290,7 @@ mod self_only_option_output_state_none {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -313,7 +313,7 @@ mod self_only_option_output_state_some {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -336,7 +336,7 @@ mod self_only_option_full_tuple_none {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -359,7 +359,7 @@ mod self_only_option_full_tuple_some {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -412,7 +412,7 @@ mod context_only_no_return {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -435,7 +435,7 @@ mod context_only_unit_return {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -458,7 +458,7 @@ mod context_only_output {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -482,7 +482,7 @@ mod context_only_state {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -506,7 +506,7 @@ mod context_only_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -530,7 +530,7 @@ mod context_only_output_state {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -554,7 +554,7 @@ mod context_only_output_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -578,7 +578,7 @@ mod context_only_state_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -602,7 +602,7 @@ mod context_only_full_tuple {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -656,7 +656,7 @@ mod input_only_no_return {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -679,7 +679,7 @@ mod input_only_unit_return {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -702,7 +702,7 @@ mod input_only_output {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -726,7 +726,7 @@ mod input_only_state {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -750,7 +750,7 @@ mod input_only_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -774,7 +774,7 @@ mod input_only_output_state {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -798,7 +798,7 @@ mod input_only_output_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -822,7 +822,7 @@ mod input_only_state_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -846,7 +846,7 @@ mod input_only_full_tuple {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -900,7 +900,7 @@ mod state_only_no_return {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -923,7 +923,7 @@ mod state_only_unit_return {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -946,7 +946,7 @@ mod state_only_output {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -970,7 +970,7 @@ mod state_only_state {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -994,7 +994,7 @@ mod state_only_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -1018,7 +1018,7 @@ mod state_only_output_state {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -1042,7 +1042,7 @@ mod state_only_output_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -1066,7 +1066,7 @@ mod state_only_state_intents {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -1090,7 +1090,7 @@ mod state_only_full_tuple {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -1144,7 +1144,7 @@ mod context_input_no_return {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -1167,7 +1167,7 @@ mod context_input_output {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -1191,7 +1191,7 @@ mod context_state_no_return {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -1214,7 +1214,7 @@ mod input_state_no_return {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -1237,7 +1237,7 @@ mod context_input_state_no_return {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -1260,7 +1260,7 @@ mod context_input_state_full_tuple {
     use super::*;
     make_defaults!(input, output, state, init_params);
 
-    #[block]
+    #[block(block_spec = false)]
     #[allow(dead_code)]
     struct DummyBlock;
     impl DummyBlock {
@@ -1284,3 +1284,66 @@ mod context_input_state_full_tuple {
         let _ = block.execute(&context, Input, &State, &mut effect_handler);
     }
 }
+
+mod input_result_with_custom_error {
+    use super::*;
+    use block_traits::execute_status::FailureStatus;
+
+    make_defaults!(state, init_params);
+
+    #[input]
+    struct Input {
+        level: u32,
+    }
+
+    #[output]
+    struct Output {
+        level: u32,
+    }
+
+    /// A block's own error type, converted into a failure by `#[execute]`.
+    #[derive(Debug)]
+    struct LevelTooHigh(u32);
+
+    impl From<LevelTooHigh> for FailureStatus {
+        fn from(LevelTooHigh(level): LevelTooHigh) -> Self {
+            FailureStatus::with_reason(format!("level {level} too high"))
+        }
+    }
+
+    fn check(level: u32) -> Result<u32, LevelTooHigh> {
+        if level > 10 {
+            Err(LevelTooHigh(level))
+        } else {
+            Ok(level)
+        }
+    }
+
+    #[block(block_spec = false)]
+    #[allow(dead_code)]
+    struct DummyBlock;
+    impl DummyBlock {
+        #[execute]
+        fn execute(&self, Input { level }: Input) -> Result<Output, LevelTooHigh> {
+            let level = check(level)?;
+            Ok(Output { level })
+        }
+    }
+
+    #[test]
+    fn test_custom_error_converts_into_failure() {
+        let block = DummyBlock;
+        let context = ExecutionContext { time: 0 };
+        let mut effect_handler = |_effect: block_traits::Effect| {};
+
+        let (output, _, _) = block
+            .execute(&context, Input { level: 3 }, &State, &mut effect_handler)
+            .unwrap();
+        assert_eq!(output.level, 3);
+
+        let err = block
+            .execute(&context, Input { level: 11 }, &State, &mut effect_handler)
+            .unwrap_err();
+        assert_eq!(err.reason(), Some("level 11 too high"));
+    }
+}
@@ -0,0 +1,151 @@
+use ::block_macros::*;
+use ::block_traits::execution_context::MockExecutionContext;
+use ::block_traits::{BlockPackage, BlockSpec, Effect, ExecuteTrait, Intent};
+use ::channels::ChannelRegistry;
+
+make_defaults!(input, init_params);
+
+#[output]
+pub struct Output {
+    pub warmups: u32,
+    pub ticks: u32,
+}
+
+#[state]
+pub struct State {
+    pub warmups: u32,
+    pub ticks: u32,
+}
+
+/// Counts its warmup and regular executions separately.
+#[block]
+pub struct WarmupBlock;
+
+impl BlockSpec for WarmupBlock {
+    fn block_id(&self) -> u32 {
+        0
+    }
+
+    fn new_from_init_params(_params: &InitParams) -> Self {
+        WarmupBlock
+    }
+
+    fn init_state(&self) -> State {
+        State {
+            warmups: 0,
+            ticks: 0,
+        }
+    }
+
+    #[execute(first)]
+    fn warm_up(&self, state: &State) -> (Output, State) {
+        let warmups = state.warmups + 1;
+        let output = Output {
+            warmups,
+            ticks: state.ticks,
+        };
+        (output, State { warmups, ..*state })
+    }
+
+    #[execute]
+    fn execute(&self, state: &State) -> (Output, State) {
+        let ticks = state.ticks + 1;
+        let output = Output {
+            warmups: state.warmups,
+            ticks,
+        };
+        (output, State { ticks, ..*state })
+    }
+}
+
+#[test]
+fn first_execute_runs_exactly_once() {
+    const { assert!(WarmupBlock::USES_FIRST_EXECUTE) };
+
+    let mut registry = ChannelRegistry::new();
+    let package = BlockPackage::<WarmupBlock>::new(
+        InputKeys {},
+        OutputKeys {
+            warmups: "warmups".to_string(),
+            ticks: "ticks".to_string(),
+        },
+        InitParams {},
+        None,
+    );
+    package.register_channels(&mut registry).unwrap();
+    let block = package.weave(&mut registry).unwrap();
+    let warmups = registry.get::<u32>("warmups").unwrap();
+    let ticks = registry.get::<u32>("ticks").unwrap();
+
+    let context = MockExecutionContext::new();
+    let mut intents = |_intent: &Intent| {};
+    let mut effects = |_effect: Effect| {};
+    for expected_ticks in 0..3 {
+        block.execute(&context, &mut intents, &mut effects).unwrap();
+        assert_eq!(*warmups.borrow(), 1);
+        assert_eq!(*ticks.borrow(), expected_ticks);
+    }
+}
+
+fn warmup_package() -> BlockPackage<WarmupBlock> {
+    BlockPackage::new(
+        InputKeys {},
+        OutputKeys {
+            warmups: "warmups".to_string(),
+            ticks: "ticks".to_string(),
+        },
+        InitParams {},
+        None,
+    )
+}
+
+#[test]
+fn restored_blocks_do_not_run_first_execute_again() {
+    let context = MockExecutionContext::new();
+    let mut intents = |_intent: &Intent| {};
+    let mut effects = |_effect: Effect| {};
+
+    let mut registry = ChannelRegistry::new();
+    let package = warmup_package();
+    package.register_channels(&mut registry).unwrap();
+    let fresh = package.weave(&mut registry).unwrap();
+
+    fresh.execute(&context, &mut intents, &mut effects).unwrap();
+    let json = serde_json::to_string(&fresh.extract_package()).unwrap();
+    let checkpoint: BlockPackage<WarmupBlock> = serde_json::from_str(&json).unwrap();
+
+    let mut registry = ChannelRegistry::new();
+    checkpoint.register_channels(&mut registry).unwrap();
+    let restored = checkpoint.weave(&mut registry).unwrap();
+    let warmups = registry.get::<u32>("warmups").unwrap();
+    let ticks = registry.get::<u32>("ticks").unwrap();
+    restored
+        .execute(&context, &mut intents, &mut effects)
+        .unwrap();
+    assert_eq!(*warmups.borrow(), 1);
+    assert_eq!(*ticks.borrow(), 1);
+}
+
+#[test]
+fn sync_blocks_with_a_restored_state_do_not_run_first_execute_again() {
+    use ::block_traits::SyncBlock;
+    use std::sync::{Arc, Mutex};
+
+    let output = Arc::new(Mutex::new((0, 0)));
+    let written = output.clone();
+    let block = SyncBlock::<WarmupBlock>::new(
+        &InitParams {},
+        || Input {},
+        move |out: &Output| *written.lock().unwrap() = (out.warmups, out.ticks),
+    )
+    .with_state(State {
+        warmups: 1,
+        ticks: 4,
+    });
+
+    let context = MockExecutionContext::new();
+    let mut intents = |_intent: &Intent| {};
+    let mut effects = |_effect: Effect| {};
+    block.execute(&context, &mut intents, &mut effects).unwrap();
+    assert_eq!(*output.lock().unwrap(), (1, 5));
+}
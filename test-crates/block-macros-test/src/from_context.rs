@@ -0,0 +1,119 @@
+use ::block_macros::*;
+use ::block_traits::execution_context::MockExecutionContext;
+use ::block_traits::{BlockPackage, BlockSpec, Effect, ExecuteTrait, Intent};
+use ::channels::ChannelRegistry;
+
+make_defaults!(state);
+
+#[input]
+pub struct Input {
+    pub armed: bool,
+    #[from_context(time)]
+    pub now: u64,
+}
+
+#[output]
+pub struct Output {
+    pub fired_at: u64,
+}
+
+#[init_params]
+pub struct InitParams {
+    pub time: u64,
+}
+
+/// Writes the current time once armed and past `time`, and 0 otherwise.
+#[block]
+pub struct FireBlock {
+    time: u64,
+}
+
+impl BlockSpec for FireBlock {
+    fn block_id(&self) -> u32 {
+        0
+    }
+
+    fn new_from_init_params(params: &InitParams) -> Self {
+        FireBlock { time: params.time }
+    }
+
+    fn init_state(&self) -> State {
+        State
+    }
+
+    #[execute]
+    fn execute(&self, input: Input) -> Output {
+        let fired = input.armed && input.now > self.time;
+        Output {
+            fired_at: if fired { input.now } else { 0 },
+        }
+    }
+}
+
+#[test]
+fn context_fields_have_no_keys_and_read_the_context_time() {
+    use ::channels::ChannelKeys;
+
+    let input_keys = InputKeys {
+        armed: "armed".to_string(),
+    };
+    assert_eq!(input_keys.channel_names(), vec!["armed".to_string()]);
+
+    let mut registry = ChannelRegistry::new();
+    registry.put("armed", true).unwrap();
+    let package = BlockPackage::<FireBlock>::new(
+        input_keys,
+        OutputKeys {
+            fired_at: "fired_at".to_string(),
+        },
+        InitParams { time: 5 },
+        None,
+    );
+    package.register_channels(&mut registry).unwrap();
+    let block = package.weave(&mut registry).unwrap();
+    let fired_at = registry.get::<u64>("fired_at").unwrap();
+
+    let mut intents = |_intent: &Intent| {};
+    let mut effects = |_effect: Effect| {};
+    for (time, expected) in [(3, 0), (7, 7), (9, 9)] {
+        let context = MockExecutionContext::new().with_time(time);
+        block.execute(&context, &mut intents, &mut effects).unwrap();
+        assert_eq!(*fired_at.borrow(), expected, "time {time}");
+    }
+}
+
+#[test]
+fn traces_show_the_input_filled_from_the_context() {
+    use ::block_traits::execution_plan::ExecutionPlan;
+
+    let mut registry = ChannelRegistry::new();
+    registry.put("armed", true).unwrap();
+    let package = BlockPackage::<FireBlock>::new(
+        InputKeys {
+            armed: "armed".to_string(),
+        },
+        OutputKeys {
+            fired_at: "fired_at".to_string(),
+        },
+        InitParams { time: 5 },
+        None,
+    );
+    package.register_channels(&mut registry).unwrap();
+    let block = package.weave(&mut registry).unwrap();
+    let plan = ExecutionPlan::from(::weave::TopoOrdered(vec![block])).with_tracing();
+
+    let mut intents = |_intent: &Intent| {};
+    let mut effects = |_effect: Effect| {};
+    let context = MockExecutionContext::new().with_time(7);
+    plan.execute(&context, &mut intents, &mut effects).unwrap();
+
+    let trace = plan.last_trace().unwrap();
+    assert_eq!(
+        trace.blocks[0].input.as_deref(),
+        Some("Input { armed: true, now: 7 }")
+    );
+    assert_eq!(
+        trace.blocks[0].output.as_deref(),
+        Some("Output { fired_at: 7 }")
+    );
+}
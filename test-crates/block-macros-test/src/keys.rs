@@ -0,0 +1,152 @@
+use ::block_macros::*;
+use std::collections::HashSet;
+
+/// Prices and flags read by the test block.
+#[input]
+#[derive(PartialEq, Eq, Hash)]
+#[allow(dead_code)]
+struct Input {
+    /// Whether to trade.
+    go: bool,
+    limit: Option<u32>,
+}
+
+/// Written by the test block.
+#[output]
+#[derive(PartialEq, Eq, Hash, PartialOrd)]
+#[allow(dead_code)]
+struct Output {
+    done: bool,
+}
+
+#[test]
+fn user_derives_are_forwarded_to_input_keys() {
+    let keys = InputKeys {
+        go: "go".to_string(),
+        limit: None,
+    };
+    let mut set = HashSet::new();
+    set.insert(keys.clone());
+    set.insert(keys);
+    assert_eq!(set.len(), 1);
+
+    // The input struct itself keeps the derives as well.
+    let input = Input {
+        go: true,
+        limit: Some(1),
+    };
+    assert_eq!(input.clone(), input);
+}
+
+#[test]
+fn user_derives_are_forwarded_to_output_keys() {
+    let a = OutputKeys {
+        done: "a".to_string(),
+    };
+    let b = OutputKeys {
+        done: "b".to_string(),
+    };
+    assert!(a < b);
+    assert_eq!(HashSet::from([a.clone(), a]).len(), 1);
+}
+
+#[test]
+fn unset_optional_input_keys_are_left_out_of_json() {
+    let keys = InputKeys {
+        go: "go".to_string(),
+        limit: None,
+    };
+    let json = serde_json::to_string(&keys).unwrap();
+    assert_eq!(json, r#"{"go":"go"}"#);
+    assert_eq!(serde_json::from_str::<InputKeys>(&json).unwrap(), keys);
+
+    let keys = InputKeys {
+        limit: Some("limit".to_string()),
+        ..keys
+    };
+    let json = serde_json::to_string(&keys).unwrap();
+    assert_eq!(json, r#"{"go":"go","limit":"limit"}"#);
+}
+
+#[test]
+fn mistyped_input_channel_is_a_reader_construction_error() {
+    use ::channels::{ChannelRegistry, InputKeys as _, RegistryError};
+
+    let mut registry = ChannelRegistry::new();
+    registry.put("go", 1_i32).unwrap();
+    let keys = InputKeys {
+        go: "go".to_string(),
+        limit: None,
+    };
+    match keys.reader(&registry) {
+        Err(RegistryError::ReaderConstruction { key, reason }) => {
+            assert_eq!(key, "go");
+            assert!(matches!(*reason, RegistryError::TypeMismatch { .. }));
+        }
+        Err(err) => panic!("expected a reader construction error, got {err}"),
+        Ok(_) => panic!("expected a reader construction error"),
+    }
+}
+
+mod defaults {
+    use ::block_macros::*;
+    use ::channels::{ChannelRegistry, InputKeys as _};
+
+    /// Has no `Default`, so it cannot be created for a missing channel.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Threshold(u32);
+
+    #[input]
+    #[allow(dead_code)]
+    pub struct StrictInput {
+        threshold: Threshold,
+    }
+
+    #[input(defaults)]
+    #[allow(dead_code)]
+    pub struct LenientInput {
+        go: bool,
+        limit: Option<u32>,
+    }
+
+    #[test]
+    fn inputs_without_default_types_do_not_register_defaults() {
+        let mut registry = ChannelRegistry::new();
+        let keys = StrictInputKeys {
+            threshold: "threshold".to_string(),
+        };
+        keys.register_defaults(&mut registry).unwrap();
+        assert!(!registry.has("threshold"));
+
+        registry.put("threshold", Threshold(3)).unwrap();
+        let input = keys.reader(&registry).unwrap().read();
+        assert_eq!(input.threshold, Threshold(3));
+    }
+
+    #[test]
+    fn inputs_with_defaults_create_missing_channels() {
+        let mut registry = ChannelRegistry::new();
+        registry.put("limit", 7_u32).unwrap();
+        let keys = LenientInputKeys {
+            go: "go".to_string(),
+            limit: Some("limit".to_string()),
+        };
+        keys.register_defaults(&mut registry).unwrap();
+
+        assert!(!*registry.get::<bool>("go").unwrap().borrow());
+        assert_eq!(*registry.get::<u32>("limit").unwrap().borrow(), 7);
+    }
+
+    #[test]
+    fn optional_inputs_are_not_created_with_defaults() {
+        let mut registry = ChannelRegistry::new();
+        let keys = LenientInputKeys {
+            go: "go".to_string(),
+            limit: Some("limit".to_string()),
+        };
+        keys.register_defaults(&mut registry).unwrap();
+
+        assert!(registry.has("go"));
+        assert!(!registry.has("limit"));
+    }
+}
@@ -1,5 +1,32 @@
+#[cfg(test)]
+mod block;
+
+#[cfg(test)]
+mod domain_types;
+
 #[cfg(test)]
 mod execute;
 
+#[cfg(test)]
+mod first_execute;
+
+#[cfg(test)]
+mod from_context;
+
 #[cfg(test)]
 mod init_params;
+
+#[cfg(test)]
+mod keys;
+
+#[cfg(test)]
+mod output_order;
+
+#[cfg(test)]
+mod prev_output;
+
+#[cfg(test)]
+mod state;
+
+#[cfg(test)]
+mod sync_block;
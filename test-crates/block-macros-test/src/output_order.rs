@@ -0,0 +1,49 @@
+use ::block_macros::*;
+use ::channels::{ChannelRegistry, OutputKeys as _};
+use std::cell::RefCell;
+
+thread_local! {
+    static WRITES: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Value that logs its name when cloned, which the writer does just before
+/// storing it in the field's channel.
+#[derive(Debug, Default)]
+struct Logged(&'static str);
+
+impl Clone for Logged {
+    fn clone(&self) -> Self {
+        WRITES.with(|writes| writes.borrow_mut().push(self.0));
+        Logged(self.0)
+    }
+}
+
+#[output]
+#[allow(dead_code)]
+struct Output {
+    zeta: Logged,
+    alpha: Logged,
+    mid: Logged,
+}
+
+#[test]
+fn writer_writes_fields_in_declaration_order() {
+    let keys = OutputKeys {
+        zeta: "a".to_string(),
+        alpha: "b".to_string(),
+        mid: "c".to_string(),
+    };
+    let mut registry = ChannelRegistry::new();
+    keys.register(&mut registry).unwrap();
+    let writer = keys.writer(&registry).unwrap();
+
+    writer.write(&Output {
+        zeta: Logged("zeta"),
+        alpha: Logged("alpha"),
+        mid: Logged("mid"),
+    });
+    assert_eq!(
+        WRITES.with(|w| w.borrow().clone()),
+        ["zeta", "alpha", "mid"]
+    );
+}
@@ -0,0 +1,81 @@
+use ::block_macros::*;
+use ::block_traits::execution_context::MockExecutionContext;
+use ::block_traits::{BlockPackage, BlockSpec, Effect, ExecuteTrait, Intent};
+use ::channels::ChannelRegistry;
+
+make_defaults!(state, init_params);
+
+#[input]
+pub struct Input {
+    pub value: i64,
+}
+
+#[output]
+pub struct Output {
+    pub value: i64,
+    pub delta: i64,
+}
+
+/// Writes its input and how much it changed since the last execution.
+#[block]
+pub struct DifferenceBlock;
+
+impl BlockSpec for DifferenceBlock {
+    fn block_id(&self) -> u32 {
+        0
+    }
+
+    fn new_from_init_params(_params: &InitParams) -> Self {
+        DifferenceBlock
+    }
+
+    fn init_state(&self) -> State {
+        State
+    }
+
+    #[execute]
+    fn execute(&self, input: Input, prev_output: Option<&Output>) -> Output {
+        let delta = prev_output.map_or(0, |prev| input.value - prev.value);
+        Output {
+            value: input.value,
+            delta,
+        }
+    }
+}
+
+#[test]
+fn difference_block_sees_its_previous_output() {
+    const { assert!(DifferenceBlock::USES_PREV_OUTPUT) };
+
+    let mut registry = ChannelRegistry::new();
+    let value = registry.ensure::<i64>("value").unwrap();
+    let package = BlockPackage::<DifferenceBlock>::new(
+        InputKeys {
+            value: "value".to_string(),
+        },
+        OutputKeys {
+            value: "last".to_string(),
+            delta: "delta".to_string(),
+        },
+        InitParams {},
+        None,
+    );
+    package.register_channels(&mut registry).unwrap();
+    let block = package.weave(&mut registry).unwrap();
+    let delta = registry.get::<i64>("delta").unwrap();
+
+    let context = MockExecutionContext::new();
+    let mut intents = |_intent: &Intent| {};
+    let mut effects = |_effect: Effect| {};
+    for (input, expected) in [(5, 0), (8, 3), (6, -2)] {
+        *value.borrow_mut() = input;
+        block.execute(&context, &mut intents, &mut effects).unwrap();
+        assert_eq!(*delta.borrow(), expected, "input {input}");
+    }
+
+    // Called directly, `execute` behaves like a first execution.
+    let (output, _, _) = DifferenceBlock
+        .execute(&context, Input { value: 4 }, &State, &mut effects)
+        .unwrap();
+    assert_eq!(output.delta, 0);
+}
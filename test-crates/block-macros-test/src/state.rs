@@ -0,0 +1,37 @@
+use ::block_macros::*;
+
+#[state]
+#[derive(PartialEq)]
+struct State {
+    count: u32,
+    /// Rebuilt from `count` when needed, so not worth checkpointing.
+    #[persist = false]
+    cache: Vec<u32>,
+    #[persist = true]
+    label: String,
+}
+
+#[test]
+fn non_persisted_field_resets_after_checkpoint_roundtrip() {
+    let state = State {
+        count: 3,
+        cache: vec![1, 2, 3],
+        label: "ticks".to_string(),
+    };
+
+    let checkpoint = serde_json::to_value(&state).unwrap();
+    assert_eq!(
+        checkpoint,
+        serde_json::json!({ "count": 3, "label": "ticks" })
+    );
+
+    let restored: State = serde_json::from_value(checkpoint).unwrap();
+    assert_eq!(
+        restored,
+        State {
+            count: 3,
+            cache: Vec::new(),
+            label: "ticks".to_string(),
+        }
+    );
+}
@@ -0,0 +1,195 @@
+use ::block_macros::*;
+use ::block_traits::execution_context::MockExecutionContext;
+use ::block_traits::{BlockSpec, Effect, ExecuteTrait, Intent, SyncBlock, SyncBlockTrait};
+use ::channels::SyncChannelRegistry;
+
+#[input]
+pub struct Input {
+    pub value: i32,
+}
+
+#[output]
+pub struct Output {
+    pub sum: i32,
+}
+
+#[state]
+pub struct State {
+    pub sum: i32,
+}
+
+#[init_params]
+pub struct InitParams {
+    pub scale: i32,
+}
+
+#[block]
+pub struct SumBlock {
+    scale: i32,
+}
+
+impl BlockSpec for SumBlock {
+    fn block_id(&self) -> u32 {
+        0
+    }
+
+    fn new_from_init_params(params: &InitParams) -> Self {
+        SumBlock {
+            scale: params.scale,
+        }
+    }
+
+    fn init_state(&self) -> State {
+        State { sum: 0 }
+    }
+
+    #[execute]
+    fn execute(&self, input: Input, state: &State) -> (Output, State) {
+        let sum = state.sum + self.scale * input.value;
+        (Output { sum }, State { sum })
+    }
+}
+
+type BoxedSyncBlock = Box<dyn SyncBlockTrait<MockExecutionContext, fn(&Intent), fn(Effect)>>;
+
+fn assert_send<T: Send>(_: &T) {}
+
+#[test]
+fn sync_block_executes_on_another_thread() {
+    let mut registry = SyncChannelRegistry::new();
+    registry.put("value", 3);
+    let value = registry.get::<i32>("value").unwrap();
+    let sum = registry.ensure::<i32>("sum").unwrap();
+
+    let block = {
+        let sum = sum.clone();
+        SyncBlock::<SumBlock>::new(
+            &InitParams { scale: 2 },
+            move || Input {
+                value: *value.read().unwrap(),
+            },
+            move |output: &Output| *sum.write().unwrap() = output.sum,
+        )
+    };
+    assert_send(&block);
+
+    let block = std::thread::spawn(move || {
+        let context = MockExecutionContext::new();
+        for _ in 0..2 {
+            let mut intents = |_intent: &Intent| {};
+            let mut effects = |_effect: Effect| {};
+            block.execute(&context, &mut intents, &mut effects).unwrap();
+        }
+        block
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(*sum.read().unwrap(), 12);
+    assert_eq!(block.state().sum, 12);
+}
+
+#[test]
+fn sync_registry_reports_type_mismatch() {
+    let mut registry = SyncChannelRegistry::new();
+    registry.put("value", 3);
+    assert!(registry.get::<String>("value").is_err());
+    assert!(registry.ensure::<String>("value").is_err());
+    assert!(registry.get::<i32>("missing").is_err());
+}
+
+#[test]
+fn boxed_sync_blocks_execute_on_another_thread() {
+    let mut registry = SyncChannelRegistry::new();
+    registry.put("value", 1);
+    let sums: Vec<_> = (0..2)
+        .map(|i| registry.ensure::<i32>(format!("sum{i}")).unwrap())
+        .collect();
+
+    let blocks: Vec<BoxedSyncBlock> = sums
+        .iter()
+        .zip([1, 10])
+        .map(|(sum, scale)| {
+            let value = registry.get::<i32>("value").unwrap();
+            let sum = sum.clone();
+            Box::new(SyncBlock::<SumBlock>::new(
+                &InitParams { scale },
+                move || Input {
+                    value: *value.read().unwrap(),
+                },
+                move |output: &Output| *sum.write().unwrap() = output.sum,
+            )) as BoxedSyncBlock
+        })
+        .collect();
+
+    std::thread::spawn(move || {
+        let context = MockExecutionContext::new();
+        for block in &blocks {
+            let mut intents: fn(&Intent) = |_| {};
+            let mut effects: fn(Effect) = |_| {};
+            block.execute(&context, &mut intents, &mut effects).unwrap();
+        }
+    })
+    .join()
+    .unwrap();
+
+    let sums: Vec<i32> = sums.iter().map(|sum| *sum.read().unwrap()).collect();
+    assert_eq!(sums, vec![1, 10]);
+}
+
+#[test]
+fn sync_block_keeps_executing_after_a_panic() {
+    let mut registry = SyncChannelRegistry::new();
+    registry.put("value", 100);
+    let value = registry.get::<i32>("value").unwrap();
+
+    // The output function rejects large sums by panicking.
+    let block = {
+        let value = value.clone();
+        SyncBlock::<SumBlock>::new(
+            &InitParams { scale: 2 },
+            move || Input {
+                value: *value.read().unwrap(),
+            },
+            |output: &Output| assert!(output.sum < 100, "sum too large"),
+        )
+    };
+    let context = MockExecutionContext::new();
+    let execute = |block: &SyncBlock<SumBlock>| {
+        let mut intents = |_intent: &Intent| {};
+        let mut effects = |_effect: Effect| {};
+        block.execute(&context, &mut intents, &mut effects)
+    };
+
+    let panicked =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| execute(&block))).is_err();
+    assert!(panicked);
+
+    // The panicked execution did not change the state.
+    *value.write().unwrap() = 3;
+    execute(&block).unwrap();
+    assert_eq!(block.state().sum, 6);
+}
+
+#[test]
+fn sync_block_traces_its_input_and_output() {
+    let block = SyncBlock::<SumBlock>::new(
+        &InitParams { scale: 2 },
+        || Input { value: 3 },
+        |_output: &Output| {},
+    );
+
+    let mut trace = ::block_traits::execution_plan::BlockTrace::default();
+    let mut intents = |_intent: &Intent| {};
+    let mut effects = |_effect: Effect| {};
+    block
+        .execute_traced(
+            &MockExecutionContext::new(),
+            &mut intents,
+            &mut effects,
+            &mut trace,
+        )
+        .unwrap();
+    assert_eq!(trace.input.as_deref(), Some("Input { value: 3 }"));
+    assert_eq!(trace.output.as_deref(), Some("Output { sum: 6 }"));
+}
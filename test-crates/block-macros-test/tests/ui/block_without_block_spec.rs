@@ -0,0 +1,10 @@
+use block_macros::*;
+
+make_defaults!(input, output, state, init_params);
+
+#[block]
+pub struct ForgotSpecBlock {
+    pub block_id: u32,
+}
+
+fn main() {}
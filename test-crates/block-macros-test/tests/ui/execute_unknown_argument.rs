@@ -0,0 +1,29 @@
+use block_macros::*;
+
+make_defaults!(input, output, state, init_params);
+
+#[block]
+pub struct MisspelledBlock {
+    pub block_id: u32,
+}
+
+impl block_traits::BlockSpec for MisspelledBlock {
+    fn block_id(&self) -> u32 {
+        self.block_id
+    }
+
+    fn new_from_init_params(_params: &InitParams) -> Self {
+        MisspelledBlock { block_id: 0 }
+    }
+
+    fn init_state(&self) -> State {
+        State
+    }
+
+    #[execute(frist)]
+    fn execute(&self) -> Output {
+        Output
+    }
+}
+
+fn main() {}
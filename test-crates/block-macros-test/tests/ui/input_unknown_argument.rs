@@ -0,0 +1,8 @@
+use block_macros::input;
+
+#[input(default)]
+pub struct Input {
+    pub go: bool,
+}
+
+fn main() {}
@@ -10,23 +10,29 @@ mod tests {
     #[test]
     fn weave_after_and_simple_order() {
         let after_node = BlockPackage::<AfterBlock> {
+            block_id: 0,
             input_keys: blocks::after::InputKeys {},
             output_keys: blocks::after::OutputKeys {
                 is_after: "after_output".to_string(),
             },
-            init_params: blocks::after::InitParams { time: 42 },
+            init_params: blocks::after::InitParams {
+                time: 42,
+                mode: blocks::after::TimeMode::Absolute,
+            },
             state: None,
         };
         // SimpleOrderBlock expects InitParams { contract: Contract }
         let order_node = BlockPackage::<SimpleOrderBlock> {
+            block_id: 0,
             input_keys: blocks::simple_order::InputKeys {
                 should_execute: "after_output".to_string(),
+                price: None,
             },
             output_keys: blocks::simple_order::OutputKeys {},
             init_params: blocks::simple_order::InitParams {
                 contract: Contract::new("ABC-123"),
                 side: trade_types::Side::Buy,
-                price: trade_types::Price::from(trade_types::Cents(100)),
+                price: trade_types::Price::from(trade_types::Cents(100)).into(),
                 quantity: trade_types::Quantity::from(trade_types::Kw(1)),
             },
             state: None,
@@ -41,4 +47,388 @@ mod tests {
         let blocks = result.unwrap();
         assert_eq!(blocks.len(), 2);
     }
+
+    #[test]
+    fn resolve_producers_maps_channels_to_producing_nodes() {
+        use block_traits::execution_plan::BlockIds;
+
+        let after_node = BlockPackage::<AfterBlock> {
+            block_id: 0,
+            input_keys: blocks::after::InputKeys {},
+            output_keys: blocks::after::OutputKeys {
+                is_after: "after_output".to_string(),
+            },
+            init_params: blocks::after::InitParams {
+                time: 42,
+                mode: blocks::after::TimeMode::Absolute,
+            },
+            state: None,
+        };
+        let delete_node = BlockPackage::<blocks::delete::DeleteBlock> {
+            block_id: 0,
+            input_keys: blocks::delete::InputKeys {
+                should_delete: "after_output".to_string(),
+            },
+            output_keys: blocks::delete::OutputKeys {},
+            init_params: blocks::delete::InitParams {},
+            state: None,
+        };
+        let blocks: Vec<BlockPackages> = vec![
+            delete_node.with_block_id(1).into(),
+            after_node.clone().with_block_id(2).into(),
+        ];
+
+        let producers = resolve_producers(&blocks).unwrap();
+        assert_eq!(producers.len(), 1);
+        assert_eq!(producers["after_output"], 2);
+
+        // The ids are the block ids of the woven producers.
+        let woven = weave_nodes(&blocks, &mut ChannelRegistry::new()).unwrap();
+        let after = woven
+            .iter()
+            .find(|block| matches!(block, blocks::BlockEmbeddings::After(_)))
+            .unwrap();
+        assert_eq!(after.block_ids(), vec![producers["after_output"]]);
+
+        let twice: Vec<BlockPackages> = vec![after_node.clone().into(), after_node.into()];
+        assert_eq!(
+            resolve_producers(&twice).unwrap_err(),
+            channels::RegistryError::DuplicateOutputKey("after_output".to_string())
+        );
+    }
+
+    #[allow(clippy::large_enum_variant)]
+    enum Woven {
+        Fn(&'static str),
+        Block(blocks::BlockEmbeddings),
+    }
+
+    type BuildFn = fn(&mut ChannelRegistry) -> Result<Woven, channels::RegistryError>;
+
+    enum Node {
+        Fn(FnNode<Woven, BuildFn>),
+        Block(BlockPackages),
+    }
+
+    impl WeaveNode<Woven> for Node {
+        fn input_channels(&self) -> Vec<String> {
+            match self {
+                Node::Fn(n) => n.input_channels(),
+                Node::Block(b) => WeaveNode::input_channels(b),
+            }
+        }
+        fn output_channels(&self) -> Vec<String> {
+            match self {
+                Node::Fn(n) => n.output_channels(),
+                Node::Block(b) => WeaveNode::output_channels(b),
+            }
+        }
+        fn register_channels(
+            &self,
+            channels: &mut ChannelRegistry,
+        ) -> Result<(), channels::RegistryError> {
+            match self {
+                Node::Fn(n) => n.register_channels(channels),
+                Node::Block(b) => WeaveNode::register_channels(b, channels),
+            }
+        }
+        fn weave_priority(&self) -> i32 {
+            match self {
+                Node::Fn(n) => n.weave_priority(),
+                Node::Block(b) => WeaveNode::weave_priority(b),
+            }
+        }
+        fn weave(&self, channels: &mut ChannelRegistry) -> Result<Woven, channels::RegistryError> {
+            match self {
+                Node::Fn(n) => n.weave(channels),
+                Node::Block(b) => WeaveNode::weave(b, channels).map(Woven::Block),
+            }
+        }
+    }
+
+    #[test]
+    fn weave_fn_node_alongside_block() {
+        let build: BuildFn = |_| Ok(Woven::Fn("flag"));
+        let flag_node = FnNode::new(Vec::<String>::new(), ["flag"], build)
+            .with_register(|r| r.put("flag", true));
+        let order_node = BlockPackage::<SimpleOrderBlock> {
+            block_id: 0,
+            input_keys: blocks::simple_order::InputKeys {
+                should_execute: "flag".to_string(),
+                price: None,
+            },
+            output_keys: blocks::simple_order::OutputKeys {},
+            init_params: blocks::simple_order::InitParams {
+                contract: Contract::new("ABC-123"),
+                side: trade_types::Side::Buy,
+                price: trade_types::Price::from(trade_types::Cents(100)).into(),
+                quantity: trade_types::Quantity::from(trade_types::Kw(1)),
+            },
+            state: None,
+        };
+
+        // Put the consumer first so weaving has to sort the nodes.
+        let nodes = vec![Node::Block(order_node.into()), Node::Fn(flag_node)];
+        let mut registry = ChannelRegistry::default();
+        let woven = weave_nodes(&nodes, &mut registry).unwrap();
+
+        assert_eq!(woven.len(), 2);
+        assert!(matches!(woven[0], Woven::Fn("flag")));
+        assert!(matches!(
+            woven[1],
+            Woven::Block(blocks::BlockEmbeddings::SimpleOrder(_))
+        ));
+    }
+
+    #[test]
+    fn fn_node_without_registration_leaves_consumer_unresolved() {
+        let build: BuildFn = |_| Ok(Woven::Fn("flag"));
+        let flag_node = FnNode::new(Vec::<String>::new(), ["flag"], build);
+        let consumer: BuildFn = |r| {
+            r.get::<bool>("flag")?;
+            Ok(Woven::Fn("consumer"))
+        };
+        let consumer_node = FnNode::new(["flag"], Vec::<String>::new(), consumer);
+
+        let nodes = vec![Node::Fn(consumer_node), Node::Fn(flag_node)];
+        let mut registry = ChannelRegistry::default();
+        assert!(weave_nodes(&nodes, &mut registry).is_err());
+    }
+
+    #[test]
+    fn cycle_error_names_the_channels_forming_the_cycle() {
+        let build: BuildFn = |_| Ok(Woven::Fn("cyclic"));
+        let nodes = vec![
+            Node::Fn(FnNode::new(["pong"], ["ping"], build)),
+            Node::Fn(FnNode::new(["ping"], ["pong"], build)),
+        ];
+        let mut registry = ChannelRegistry::default();
+        match weave_nodes(&nodes, &mut registry) {
+            Err(channels::RegistryError::CycleDetected(details)) => {
+                assert!(details.contains("node 0 -> node 1 via 'ping'"), "{details}");
+                assert!(details.contains("node 1 -> node 0 via 'pong'"), "{details}");
+            }
+            Err(err) => panic!("expected a cycle error, got {err}"),
+            Ok(_) => panic!("expected a cycle error"),
+        }
+    }
+
+    #[test]
+    fn independent_nodes_weave_in_priority_order() {
+        let low: BuildFn = |_| Ok(Woven::Fn("low"));
+        let high: BuildFn = |_| Ok(Woven::Fn("high"));
+        let names = |woven: &[Woven]| -> Vec<&'static str> {
+            woven
+                .iter()
+                .map(|w| match w {
+                    Woven::Fn(name) => *name,
+                    Woven::Block(_) => "block",
+                })
+                .collect()
+        };
+
+        // Without priorities the nodes keep their given order.
+        let nodes = vec![
+            Node::Fn(FnNode::new(Vec::<String>::new(), ["a"], low)),
+            Node::Fn(FnNode::new(Vec::<String>::new(), ["b"], high)),
+        ];
+        let woven = weave_nodes(&nodes, &mut ChannelRegistry::default()).unwrap();
+        assert_eq!(names(&woven), ["low", "high"]);
+
+        let nodes = vec![
+            Node::Fn(FnNode::new(Vec::<String>::new(), ["a"], low).with_priority(-1)),
+            Node::Fn(FnNode::new(Vec::<String>::new(), ["b"], high).with_priority(5)),
+        ];
+        let woven = weave_nodes(&nodes, &mut ChannelRegistry::default()).unwrap();
+        assert_eq!(names(&woven), ["high", "low"]);
+    }
+
+    fn order_package(trigger: &str) -> BlockPackages {
+        BlockPackage::<SimpleOrderBlock> {
+            block_id: 0,
+            input_keys: blocks::simple_order::InputKeys {
+                should_execute: trigger.to_string(),
+                price: None,
+            },
+            output_keys: blocks::simple_order::OutputKeys {},
+            init_params: blocks::simple_order::InitParams {
+                contract: Contract::new("ABC-123"),
+                side: trade_types::Side::Buy,
+                price: trade_types::Price::from(trade_types::Cents(100)).into(),
+                quantity: trade_types::Quantity::from(trade_types::Kw(1)),
+            },
+            state: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn lint_graph_flags_order_block_with_unconnected_trigger() {
+        let after_node: BlockPackages = BlockPackage::<AfterBlock> {
+            block_id: 0,
+            input_keys: blocks::after::InputKeys {},
+            output_keys: blocks::after::OutputKeys {
+                is_after: "after_output".to_string(),
+            },
+            init_params: blocks::after::InitParams {
+                time: 42,
+                mode: blocks::after::TimeMode::Absolute,
+            },
+            state: None,
+        }
+        .into();
+
+        let connected = vec![after_node.clone(), order_package("after_output")];
+        assert!(lint_graph(&connected, &[]).is_empty());
+
+        let unconnected = vec![after_node, order_package("typo")];
+        let lints = lint_graph(&unconnected, &[]);
+        assert_eq!(
+            lints,
+            vec![GraphLint::UnconnectedTrigger {
+                node: 1,
+                channel: "typo".to_string(),
+            }]
+        );
+        assert!(lints[0].to_string().contains("'typo'"));
+
+        // Channels fed from outside the graph are fine when declared.
+        assert!(lint_graph(&unconnected, &["typo"]).is_empty());
+    }
+
+    fn after_package(output: &str) -> BlockPackages {
+        BlockPackage::<AfterBlock> {
+            block_id: 0,
+            input_keys: blocks::after::InputKeys {},
+            output_keys: blocks::after::OutputKeys {
+                is_after: output.to_string(),
+            },
+            init_params: blocks::after::InitParams {
+                time: 42,
+                mode: blocks::after::TimeMode::Absolute,
+            },
+            state: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn subgraphs_flatten_for_weaving_and_cluster_in_dot() {
+        let flat = flatten_subgraphs([
+            Subgraph::new(
+                "execution",
+                vec![order_package("go"), order_package("typo")],
+            ),
+            Subgraph::new("signals", vec![after_package("go")]),
+        ]);
+        assert_eq!(flat.subgraph_of(0), Some("execution"));
+        assert_eq!(flat.subgraph_of(2), Some("signals"));
+        assert_eq!(flat.subgraph_of(3), None);
+
+        let dot = flat.to_dot();
+        assert_eq!(
+            dot,
+            "digraph weave {\n\
+             \x20   subgraph cluster_0 {\n\
+             \x20       label=\"execution\";\n\
+             \x20       n0;\n\
+             \x20       n1;\n\
+             \x20   }\n\
+             \x20   subgraph cluster_1 {\n\
+             \x20       label=\"signals\";\n\
+             \x20       n2;\n\
+             \x20   }\n\
+             \x20   n2 -> n0 [label=\"go\"];\n\
+             }\n"
+        );
+
+        let lints = flat.lint(&[]);
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].0, "execution");
+
+        // Grouping does not change weaving: the producer in "signals" is still
+        // ordered before its consumer in "execution", so it cannot come last.
+        let mut registry = ChannelRegistry::default();
+        registry.put("typo", false).unwrap();
+        let woven = weave_nodes(&flat.nodes, &mut registry).unwrap();
+        assert_eq!(woven.len(), 3);
+        assert!(!matches!(woven[2], blocks::BlockEmbeddings::After(_)));
+    }
+
+    #[test]
+    fn lenient_weave_creates_missing_inputs_with_warnings() {
+        let nodes = vec![after_package("go"), order_package("not_wired")];
+
+        let mut registry = ChannelRegistry::default();
+        assert!(weave_nodes(&nodes, &mut registry).is_err());
+
+        let mut registry = ChannelRegistry::default();
+        let options = WeaveOptions { lenient: true };
+        let (woven, warnings) = weave_nodes_with_options(&nodes, &mut registry, options).unwrap();
+        assert_eq!(woven.len(), 2);
+        assert_eq!(
+            warnings,
+            vec![WeaveWarning::MissingProducer {
+                node: 1,
+                channel: "not_wired".to_string(),
+            }]
+        );
+        assert!(!*registry.get::<bool>("not_wired").unwrap().borrow());
+    }
+
+    #[test]
+    fn lenient_weave_does_not_place_orders_at_a_default_price() {
+        // The order reads its price from "px", which nothing produces.
+        let order: BlockPackages = BlockPackage::<SimpleOrderBlock>::new(
+            blocks::simple_order::InputKeys {
+                should_execute: "go".to_string(),
+                price: Some("px".to_string()),
+            },
+            blocks::simple_order::OutputKeys {},
+            blocks::simple_order::InitParams {
+                contract: Contract::new("ABC-123"),
+                side: trade_types::Side::Buy,
+                price: blocks::simple_order::PriceSource::Channel("px".to_string()),
+                quantity: trade_types::Quantity::from(trade_types::Kw(1)),
+            },
+            None,
+        )
+        .into();
+        let nodes = vec![after_package("go"), order];
+
+        // Rather than creating the price channel with a price of 0, which the
+        // order would place at, the weave fails.
+        let mut registry = ChannelRegistry::default();
+        let options = WeaveOptions { lenient: true };
+        assert!(matches!(
+            weave_nodes_with_options(&nodes, &mut registry, options),
+            Err(channels::RegistryError::MissingProducer(_))
+        ));
+        assert!(!registry.has("px"));
+    }
+
+    #[test]
+    fn validate_weave_checks_graphs_without_registering_channels() {
+        let registry = ChannelRegistry::default();
+
+        let good = vec![order_package("go"), after_package("go")];
+        assert!(validate_weave(&good, &registry).is_ok());
+        assert!(!registry.has("go"));
+
+        let build: BuildFn = |_| Ok(Woven::Fn("cyclic"));
+        let cyclic = vec![
+            Node::Fn(FnNode::new(["pong"], ["ping"], build)),
+            Node::Fn(FnNode::new(["ping"], ["pong"], build)),
+        ];
+        assert!(matches!(
+            validate_weave(&cyclic, &registry),
+            Err(channels::RegistryError::CycleDetected(_))
+        ));
+        assert!(!registry.has("ping"));
+        assert!(!registry.has("pong"));
+
+        // The validated graph still weaves into the untouched registry.
+        let mut registry = registry;
+        assert_eq!(weave_nodes(&good, &mut registry).unwrap().len(), 2);
+    }
 }